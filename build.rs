@@ -0,0 +1,44 @@
+//! Generates the top-N variants of the frequency lists in `res/frequency/`
+//! (e.g. `english-1k` from `res/frequency/english`) and their metadata,
+//! embedded along with the other texts.
+
+use std::{env, fs, path::Path};
+
+/// Sizes of the generated variants, with their name suffix. A variant is only
+/// generated when the frequency list has enough words.
+const SIZES: [(usize, &str); 3] = [(200, "200"), (1000, "1k"), (10000, "10k")];
+
+fn main() {
+    println!("cargo::rerun-if-changed=res/frequency");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let texts = Path::new(&out_dir).join("texts");
+    fs::create_dir_all(&texts).expect("Couldn't create generated texts directory");
+
+    let lists = fs::read_dir("res/frequency").expect("Couldn't read res/frequency");
+    for entry in lists.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let content = fs::read_to_string(entry.path()).expect("Couldn't read frequency list");
+        let words: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        for (size, suffix) in SIZES {
+            if words.len() >= size {
+                let variant = format!("{}-{}", name, suffix);
+                fs::write(texts.join(&variant), words[..size].join("\n") + "\n")
+                    .expect("Couldn't write generated text");
+                fs::write(
+                    texts.join(format!("{}.meta.toml", variant)),
+                    format!(
+                        "language = \"{}\"\ndescription = \"The {} most frequent words\"\nmodes = [\"clock\", \"words\"]\n",
+                        name, size
+                    ),
+                )
+                .expect("Couldn't write generated text metadata");
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+const groupBy = (items, key) =>
+    items.reduce((groups, item) => {
+        (groups[item[key]] ||= []).push(item);
+        return groups;
+    }, {});
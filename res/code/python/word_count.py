@@ -0,0 +1,7 @@
+from collections import Counter
+
+
+def word_count(path):
+    with open(path) as f:
+        words = f.read().split()
+    return Counter(words).most_common(10)
@@ -4,17 +4,27 @@
 //! Global controls (ESC, TAB, arrows...) are handled here, with mode-specific
 //! input delegated to the active game mode.
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, poll};
+use chrono::Utc;
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind, poll,
+};
+use ratatui::layout::Position;
 
 use crate::{
     app::{
         App, State,
-        modes::{Direction, Mode, create_mode},
+        history_screen::{HistoryScreen, HistoryView},
+        modes::{Direction, create_mode},
+        results_screen::{ResultsScreen, ResultsTab},
+        ui::char::CharState,
     },
     config::Config,
+    history::{self, KeyStats, TestRecord, WordStats},
+    replay::{Replay, replays_dir},
 };
 
 /// Defines the intent of an input event after being processed by a mode.
@@ -24,45 +34,135 @@ use crate::{
 pub enum Action {
     /// The input was consumed or ignored; no global state change is needed.
     None,
-    /// Request to completely replace the current game mode (e.g., from 'Clock' to 'Words').
-    SwitchMode(Mode),
+    /// Request to completely replace the current game mode by the mode of this
+    /// name (e.g., from 'clock' to 'words'), with its configured settings.
+    SwitchMode(String),
     /// Request to transition the application's lifecycle state (e.g., from [`State::Home`] to [`State::Running`]).
     SwitchState(State),
     /// Request to quit the application.
     Quit,
+    /// Request to act on a click, e.g. on an option of the Home screen.
+    MouseSelect(MouseTarget),
+}
+
+/// What clicking a part of the UI does.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MouseTarget {
+    /// Focuses and selects an option of the Home screen, 0 being the mode
+    /// selector, as if ENTER was pressed on it.
+    Option(usize),
+    /// Presses a key, e.g. the key of a footer hint.
+    Key(KeyEvent),
 }
 
+/// Amount of words listed in the missed words view of the History screen.
+const MISSED_WORDS: usize = 15;
+
 /// Polls for and processes terminal events.
 pub fn handle_events(app: &mut App, config: &Config) -> Result<()> {
     if !poll(Duration::from_millis(100))? {
+        // Timed modes can run out while the user isn't typing
+        if app.state == State::Running && !app.warming_up && app.mode.is_complete() {
+            complete_test(app);
+            app.state = State::Complete;
+        }
         return Ok(());
     }
 
-    if let Event::Key(key) = event::read()? {
-        if key.kind == KeyEventKind::Release {
-            return Ok(());
+    let action = match event::read()? {
+        Event::Key(key) if key.kind != KeyEventKind::Release => {
+            app.last_key = Instant::now();
+            handle_key(app, key)?
         }
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            ..
+        }) => app
+            .mouse_targets
+            .iter()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map_or(Action::None, |(_, target)| Action::MouseSelect(*target)),
+        _ => return Ok(()),
+    };
 
-        let action = match app.state {
-            State::Home => handle_home_input(app, key)?,
-            State::Running => handle_running_input(app, key)?,
-            State::Complete => handle_complete_input(app, key)?,
-        };
+    execute_action(app, action, config)?;
 
-        execute_action(app, action, config)?;
+    // Anything done on Home may change the text, the next test is timed from there
+    if app.state == State::Home {
+        app.shown_at = Instant::now();
     }
 
     Ok(())
 }
 
+/// Handles a key press in the current state.
+///
+/// `?` opens the help overlay outside of tests, unless a mode option is being
+/// typed in or the test starts with a `?`. While it's open, keys are ignored
+/// until `ESC` or `?` closes it.
+fn handle_key(app: &mut App, key: KeyEvent) -> Result<Action> {
+    if app.show_help {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('?')) {
+            app.show_help = false;
+        }
+        return Ok(Action::None);
+    }
+    if key.code == KeyCode::Char('?')
+        && !matches!(app.state, State::Splash | State::Running)
+        && !app.mode.is_option_editing()
+        && !types_question_mark(app)
+    {
+        app.show_help = true;
+        return Ok(Action::None);
+    }
+
+    match app.state {
+        State::Splash => Ok(Action::SwitchState(State::Home)),
+        State::Home => handle_home_input(app, key),
+        State::Running => handle_running_input(app, key),
+        State::Complete => handle_complete_input(app, key),
+        State::History => Ok(handle_history_input(app, key)),
+    }
+}
+
+/// Whether `?` is typed on the Home screen, starting a test whose first
+/// character is a `?`.
+fn types_question_mark(app: &App) -> bool {
+    app.state == State::Home
+        && app
+            .mode
+            .get_characters()
+            .iter()
+            .find(|c| c.state == CharState::Cursor)
+            .is_some_and(|c| c.symbol == "?")
+}
+
 /// Handles input on the Home screen (options navigation, mode selection, typing start).
 fn handle_home_input(app: &mut App, key: KeyEvent) -> Result<Action> {
     // Check if mode is editing a custom option
     let mode_editing = app.mode.is_option_editing();
 
     let action = match key.code {
+        // Close the mode menu, keeping the current mode
+        KeyCode::Esc if app.is_editing && app.focused_option == 0 => {
+            app.is_editing = false;
+            app.editing_mode = None;
+            Action::None
+        }
         KeyCode::Esc => Action::Quit,
 
+        // The mode menu is a list, down goes to the next mode
+        KeyCode::Up | KeyCode::Down if app.is_editing && app.focused_option == 0 => {
+            let direction = match key.code {
+                KeyCode::Up => Direction::Left,
+                _ => Direction::Right,
+            };
+            app.adjust_current_option(direction)?;
+            Action::None
+        }
+
         KeyCode::Left | KeyCode::Down => {
             if app.is_editing || mode_editing {
                 app.adjust_current_option(Direction::Left)?;
@@ -83,14 +183,26 @@ fn handle_home_input(app: &mut App, key: KeyEvent) -> Result<Action> {
 
         KeyCode::Enter | KeyCode::Char(' ') => {
             if let Some(mode_name) = app.select_current_option()? {
-                Action::SwitchMode(Mode::default_for(&mode_name))
+                Action::SwitchMode(mode_name)
             } else {
                 Action::None
             }
         }
 
-        // Any typing character starts the game
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            // An unreadable history is shown as empty
+            app.history = HistoryScreen::new(
+                TestRecord::load_all().unwrap_or_default(),
+                WordStats::load()
+                    .map(|stats| stats.most_missed(MISSED_WORDS))
+                    .unwrap_or_default(),
+            );
+            Action::SwitchState(State::History)
+        }
+
+        // Any typing character starts the game, or its warm-up
         KeyCode::Char(_) => {
+            app.start_warmup();
             let action = app.mode.handle_input(key);
             if matches!(action, Action::None) {
                 Action::SwitchState(State::Running)
@@ -109,14 +221,27 @@ fn handle_home_input(app: &mut App, key: KeyEvent) -> Result<Action> {
 ///
 /// **Globally handled keys:**
 /// - `ESC`: Quit the application.
-/// - `TAB`: Reset the mode and return to Home.
+/// - The restart key (`TAB` by default)/`SHIFT+TAB`: Reset the mode and return
+///   to Home. Modes capturing the restart key (see
+///   [`Handler::captures_key`](super::modes::Handler::captures_key)) receive it
+///   instead, leaving `SHIFT+TAB` to restart. Without quick restart, the key
+///   has to be pressed twice in a row.
 ///
 /// **Delegated to game mode:** All other keys (typing, backspace, etc.).
 fn handle_running_input(app: &mut App, key: KeyEvent) -> Result<Action> {
+    let restart = (app.behavior.restart_key.matches(&key) || key.code == KeyCode::BackTab)
+        && !app.mode.captures_key(&key);
+    let confirmed = std::mem::take(&mut app.confirming_restart);
+
     match key.code {
         KeyCode::Esc => Ok(Action::Quit),
-        KeyCode::Tab => {
-            app.mode.reset()?;
+        _ if restart && !app.behavior.quick_restart && !confirmed => {
+            app.confirming_restart = true;
+            Ok(Action::None)
+        }
+        _ if restart => {
+            app.resume_mode();
+            app.reset_mode();
             app.focused_option = 0;
             app.is_editing = false;
             Ok(Action::SwitchState(State::Home))
@@ -125,8 +250,11 @@ fn handle_running_input(app: &mut App, key: KeyEvent) -> Result<Action> {
             let action = app.mode.handle_input(key);
 
             // Check for completion after input
-            if app.mode.is_complete() {
-                app.mode.on_complete();
+            if app.warming_up && app.mode.is_complete() {
+                app.finish_warmup();
+                Ok(Action::None)
+            } else if app.mode.is_complete() {
+                complete_test(app);
                 Ok(Action::SwitchState(State::Complete))
             } else {
                 Ok(action)
@@ -135,12 +263,89 @@ fn handle_running_input(app: &mut App, key: KeyEvent) -> Result<Action> {
     }
 }
 
-/// Handles input on the completion screen (restart or quit only).
+/// Finalizes the active test and records its results in the history.
+///
+/// Every mode goes through this hook once its test is complete.
+fn complete_test(app: &mut App) {
+    app.confirming_restart = false;
+    app.results = ResultsScreen::default();
+    app.completed_at = Instant::now();
+    app.mode.on_complete();
+    let mut stats = app.mode.get_stats();
+    if let Some(first_key) = app.first_key_latency() {
+        stats = stats.with_first_key(first_key);
+    }
+    app.last_stats = Some(stats);
+    app.word_times = app.mode.word_times();
+
+    // History is best-effort, failing to write it shouldn't end the session.
+    // Retry rounds aren't tests of the suspended mode, only their words count.
+    app.new_pb = false;
+    if app.suspended_mode.is_none() {
+        let mut record = TestRecord::new(app.current_config(), &app.mode.get_stats());
+        record.tag = app.tag.clone();
+        record.invalid = app.behavior.is_invalid(record.accuracy);
+        if app.record_replays {
+            record.replay = save_replay(app, &record);
+        }
+        app.new_pb = !record.invalid && app.personal_bests.update(&record.mode, record.wpm);
+        app.activity.record(&record);
+        let _ = record.append();
+    }
+    let _ = history::record(&app.mode.word_results());
+
+    let key_results = app.mode.key_results();
+    app.key_stats = KeyStats::from_results(&key_results);
+    let _ = history::record_keys(&key_results);
+}
+
+/// Saves a replay of the completed test, returns its file name.
+fn save_replay(app: &App, record: &TestRecord) -> Option<String> {
+    let session = app.mode.session()?;
+    let replay = session.to_replay(record.mode.name(), record.text.as_deref().unwrap_or(""));
+    let name = Replay::file_name(&record.timestamp);
+
+    replay
+        .save(&replays_dir().ok()?.join(&name))
+        .ok()
+        .map(|_| name)
+}
+
+/// Handles input on the completion screen (restart, retry missed words or quit).
 fn handle_complete_input(app: &mut App, key: KeyEvent) -> Result<Action> {
     match key.code {
         KeyCode::Esc => Ok(Action::Quit),
-        KeyCode::Tab => {
-            app.mode.reset()?;
+        // Ctrl+R can be the restart key
+        KeyCode::Char('r')
+            if !key.modifiers.contains(KeyModifiers::CONTROL) && app.start_retry() =>
+        {
+            Ok(Action::SwitchState(State::Running))
+        }
+        KeyCode::Left => {
+            app.results.cycle_tab(Direction::Left, app.ui.results_chart);
+            Ok(Action::None)
+        }
+        KeyCode::Right => {
+            app.results
+                .cycle_tab(Direction::Right, app.ui.results_chart);
+            Ok(Action::None)
+        }
+        KeyCode::Up | KeyCode::Down => {
+            let rows = match app.results.tab {
+                ResultsTab::Errors => app.mistyped_words().len(),
+                ResultsTab::RawData => app.word_times.len(),
+                _ => return Ok(Action::None),
+            };
+            let direction = match key.code {
+                KeyCode::Up => Direction::Left,
+                _ => Direction::Right,
+            };
+            app.results.scroll(direction, rows);
+            Ok(Action::None)
+        }
+        _ if app.behavior.restart_key.matches(&key) => {
+            app.resume_mode();
+            app.reset_mode();
             app.focused_option = 0;
             app.is_editing = false;
             Ok(Action::SwitchState(State::Home))
@@ -149,20 +354,98 @@ fn handle_complete_input(app: &mut App, key: KeyEvent) -> Result<Action> {
     }
 }
 
+/// Handles input on the History screen (views, filters, back to Home or quit).
+fn handle_history_input(app: &mut App, key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Esc => Action::Quit,
+        KeyCode::Tab => Action::SwitchState(State::Home),
+        KeyCode::Enter => {
+            app.history.toggle_view();
+            Action::None
+        }
+        KeyCode::Char('r') if app.history.view == HistoryView::Chart => {
+            // Missing or unreadable replays are ignored
+            if let Some(replay) = app
+                .history
+                .latest_replay(Utc::now())
+                .and_then(|name| Replay::load(&replays_dir().ok()?.join(name)).ok())
+            {
+                app.start_playback(replay);
+            }
+            Action::None
+        }
+        // Practice the missed words right away
+        KeyCode::Char('p') if app.history.view == HistoryView::Missed => {
+            app.state = State::Home;
+            Action::SwitchMode("weak-words".to_string())
+        }
+        KeyCode::Char('t') => {
+            app.history.cycle_tag();
+            Action::None
+        }
+        KeyCode::Left => {
+            app.history.cycle_mode(Direction::Left);
+            Action::None
+        }
+        KeyCode::Right => {
+            app.history.cycle_mode(Direction::Right);
+            Action::None
+        }
+        KeyCode::Down => {
+            app.history.cycle_range(Direction::Left);
+            Action::None
+        }
+        KeyCode::Up => {
+            app.history.cycle_range(Direction::Right);
+            Action::None
+        }
+        _ => Action::None,
+    }
+}
+
 /// Executes the given action, updating application state accordingly.
 fn execute_action(app: &mut App, action: Action, config: &Config) -> Result<()> {
     match action {
         Action::None => {}
-        Action::SwitchMode(mode) => {
-            app.mode_config = mode.clone();
+        Action::SwitchMode(name) => {
+            let mode = config.mode(&name);
             let mut new_mode = create_mode(&mode);
-            new_mode.initialize(config)?;
+            new_mode.set_behavior(&config.behavior);
+            // Stay on the current mode if the new one can't start
+            if let Err(error) = new_mode.initialize(config) {
+                app.notice = Some(error.to_string());
+                return Ok(());
+            }
+            app.notice = None;
+            app.mode_config = mode;
             app.mode = new_mode;
-            app.focused_option = 0;
+            // The first test of the new mode gets its own warm-up
+            app.warmed_up = false;
+            // Go on with the options of the new mode
+            app.focused_option = usize::from(app.mode.option_count() > 0);
             app.is_editing = false;
             app.editing_mode = None;
         }
-        Action::SwitchState(state) => app.state = state,
+        Action::SwitchState(state) => {
+            if state != State::Home {
+                app.notice = None;
+            }
+            app.state = state;
+        }
+        Action::MouseSelect(MouseTarget::Option(index)) => {
+            if app.focused_option != index {
+                app.focused_option = index;
+                app.is_editing = false;
+                app.editing_mode = None;
+            }
+            if let Some(mode_name) = app.select_current_option()? {
+                execute_action(app, Action::SwitchMode(mode_name), config)?;
+            }
+        }
+        Action::MouseSelect(MouseTarget::Key(key)) => {
+            let action = handle_key(app, key)?;
+            execute_action(app, action, config)?;
+        }
         Action::Quit => app.should_exit = true,
     }
 
@@ -0,0 +1,217 @@
+//! # History Screen Module
+//!
+//! This module holds the state of the History screen: the recorded tests, the
+//! filters (mode, date range, tag) narrowing down which of them are charted, and the most missed words.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    app::modes::Direction,
+    history::{TestRecord, WordStat},
+};
+
+/// Date ranges cycled through on the History screen, in days (`None` for all time).
+const RANGES: [Option<i64>; 4] = [None, Some(7), Some(30), Some(365)];
+
+/// Views of the History screen.
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum HistoryView {
+    /// WPM of past tests.
+    #[default]
+    Chart,
+    /// Words mistyped the most often.
+    Missed,
+}
+
+/// Past tests, filtered by mode, date range and tag.
+#[derive(Default)]
+pub struct HistoryScreen {
+    /// Every recorded test, oldest first.
+    pub records: Vec<TestRecord>,
+    /// Words with the most errors across every test, most errors first.
+    pub missed: Vec<(String, WordStat)>,
+    pub view: HistoryView,
+    /// Index into [`HistoryScreen::modes`], 0 shows every mode.
+    mode: usize,
+    /// Index into [`RANGES`].
+    range: usize,
+    /// Index into [`HistoryScreen::tags`], 0 shows every test.
+    tag: usize,
+}
+
+impl HistoryScreen {
+    pub fn new(records: Vec<TestRecord>, missed: Vec<(String, WordStat)>) -> Self {
+        Self {
+            records,
+            missed,
+            ..Self::default()
+        }
+    }
+
+    /// Switches between the WPM chart and the missed words.
+    pub fn toggle_view(&mut self) {
+        self.view = match self.view {
+            HistoryView::Chart => HistoryView::Missed,
+            HistoryView::Missed => HistoryView::Chart,
+        };
+    }
+
+    /// Mode filters: "all", then every mode found in the records.
+    pub fn modes(&self) -> Vec<&'static str> {
+        let mut modes = vec!["all"];
+        for record in &self.records {
+            let name = record.mode.name();
+            if !modes.contains(&name) {
+                modes.push(name);
+            }
+        }
+        modes
+    }
+
+    /// The selected mode filter.
+    pub fn mode_label(&self) -> &'static str {
+        self.modes().get(self.mode).copied().unwrap_or("all")
+    }
+
+    /// Tag filters: "all", then every tag found in the records.
+    pub fn tags(&self) -> Vec<&str> {
+        let mut tags = vec!["all"];
+        for tag in self
+            .records
+            .iter()
+            .filter_map(|record| record.tag.as_deref())
+        {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+
+    /// The selected tag filter.
+    pub fn tag_label(&self) -> &str {
+        self.tags().get(self.tag).copied().unwrap_or("all")
+    }
+
+    /// The selected date range.
+    pub fn range_label(&self) -> String {
+        match RANGES[self.range] {
+            Some(days) => format!("last {} days", days),
+            None => "all time".to_string(),
+        }
+    }
+
+    /// Cycles through the mode filters.
+    pub fn cycle_mode(&mut self, direction: Direction) {
+        self.mode = cycle(self.mode, self.modes().len(), direction);
+    }
+
+    /// Cycles through the date ranges.
+    pub fn cycle_range(&mut self, direction: Direction) {
+        self.range = cycle(self.range, RANGES.len(), direction);
+    }
+
+    /// Cycles through the tag filters.
+    pub fn cycle_tag(&mut self) {
+        self.tag = cycle(self.tag, self.tags().len(), Direction::Right);
+    }
+
+    /// Records matching the filters, as of `now`.
+    pub fn filtered(&self, now: DateTime<Utc>) -> Vec<&TestRecord> {
+        let mode = self.mode_label();
+        let since = RANGES[self.range].map(|days| now - Duration::days(days));
+        let tag = Some(self.tag_label()).filter(|tag| *tag != "all");
+
+        self.records
+            .iter()
+            .filter(|record| mode == "all" || record.mode.name() == mode)
+            .filter(|record| since.is_none_or(|since| record.timestamp >= since))
+            .filter(|record| tag.is_none_or(|tag| record.tag.as_deref() == Some(tag)))
+            .collect()
+    }
+
+    /// The most recent record matching the filters that has a replay.
+    pub fn latest_replay(&self, now: DateTime<Utc>) -> Option<&str> {
+        self.filtered(now)
+            .into_iter()
+            .rev()
+            .find_map(|record| record.replay.as_deref())
+    }
+}
+
+/// Index next to `index` in `direction`, wrapping around `len`.
+pub(crate) fn cycle(index: usize, len: usize, direction: Direction) -> usize {
+    match direction {
+        Direction::Left => (index + len - 1) % len,
+        Direction::Right => (index + 1) % len,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::modes::{GameStats, Mode};
+
+    fn record(mode: &str, days_ago: i64, now: DateTime<Utc>) -> TestRecord {
+        let mut record =
+            TestRecord::new(Mode::default_for(mode), &GameStats::new(50.0, 95.0, 30.0));
+        record.timestamp = now - Duration::days(days_ago);
+        record
+    }
+
+    #[test]
+    fn filters_by_mode_and_range() {
+        let now = Utc::now();
+        let mut screen = HistoryScreen::new(
+            vec![
+                record("clock", 40, now),
+                record("words", 20, now),
+                record("clock", 2, now),
+            ],
+            Vec::new(),
+        );
+
+        assert_eq!(screen.modes(), vec!["all", "clock", "words"]);
+        assert_eq!(screen.filtered(now).len(), 3);
+
+        screen.cycle_mode(Direction::Right);
+        assert_eq!(screen.mode_label(), "clock");
+        assert_eq!(screen.filtered(now).len(), 2);
+
+        // Last 30 days
+        screen.cycle_range(Direction::Right);
+        screen.cycle_range(Direction::Right);
+        assert_eq!(screen.filtered(now).len(), 1);
+
+        screen.cycle_mode(Direction::Left);
+        assert_eq!(screen.filtered(now).len(), 2);
+    }
+
+    #[test]
+    fn filters_by_tag() {
+        let now = Utc::now();
+        let mut work = record("clock", 1, now);
+        work.tag = Some("work".to_string());
+        let mut screen = HistoryScreen::new(vec![record("clock", 2, now), work], Vec::new());
+
+        assert_eq!(screen.tags(), vec!["all", "work"]);
+        assert_eq!(screen.filtered(now).len(), 2);
+
+        screen.cycle_tag();
+        assert_eq!(screen.tag_label(), "work");
+        assert_eq!(screen.filtered(now).len(), 1);
+
+        screen.cycle_tag();
+        assert_eq!(screen.tag_label(), "all");
+    }
+
+    #[test]
+    fn latest_replay_skips_records_without_one() {
+        let now = Utc::now();
+        let mut with_replay = record("clock", 3, now);
+        with_replay.replay = Some("replay.json".to_string());
+        let screen = HistoryScreen::new(vec![with_replay, record("clock", 1, now)], Vec::new());
+
+        assert_eq!(screen.latest_replay(now), Some("replay.json"));
+    }
+}
@@ -4,17 +4,30 @@
 //! loop, state transitions, and the orchestration of events and rendering.
 
 pub mod events;
+pub mod history_screen;
 pub mod modes;
+pub mod results_screen;
 pub mod ui;
 
+use std::time::Instant;
+
 use anyhow::Result;
-use ratatui::DefaultTerminal;
+use ratatui::{Terminal, backend::Backend, layout::Rect};
 use strum::VariantNames;
 
 use crate::{
-    app::modes::{Direction, GameMode, Mode, create_mode},
+    app::events::MouseTarget,
+    app::history_screen::HistoryScreen,
+    app::modes::{
+        Direction, GameMode, Handler, Mode, create_mode, default_text, playback::Playback,
+        retry::Retry, util::Dictionary, warmup::WarmUp,
+    },
+    app::modes::{GameStats, WordResult, WordTime},
+    app::results_screen::ResultsScreen,
     app::ui::theme::Theme,
-    config::Config,
+    config::{Behavior, ChartOptions, Config, Goal, UiOptions, WordRules},
+    history::{Activity, KeyStats, PersonalBests},
+    replay::Replay,
 };
 
 /// The container for the application's state and logic.
@@ -33,13 +46,74 @@ pub struct App {
     pub focused_option: usize,
     /// Whether we're currently editing an option value.
     pub is_editing: bool,
-    /// Mode name being edited in the mode selector.
+    /// Mode name selected in the mode menu, while it's open.
     pub editing_mode: Option<String>,
+    /// Configured settings and description of every mode, listed in the mode
+    /// menu.
+    pub modes: Vec<(Mode, String)>,
+    /// The mode put aside during a warm-up or while retyping its missed words.
+    pub suspended_mode: Option<Box<dyn GameMode>>,
+    /// Amount of words of the warm-up round, 0 disables it.
+    pub warmup: usize,
+    /// Words left out of the warm-up round, or the only ones drawn.
+    pub words: WordRules,
+    /// Whether the warm-up round is in progress.
+    pub warming_up: bool,
+    /// Whether the warm-up round was completed, it only runs once per session
+    /// and mode.
+    pub warmed_up: bool,
+    /// Best WPM for each mode and options, from the history.
+    pub personal_bests: PersonalBests,
+    /// Whether the last completed test set a new personal best.
+    pub new_pb: bool,
+    /// Per-key accuracy and latency of the last completed test.
+    pub key_stats: KeyStats,
+    /// Time taken on each correctly typed word of the last completed test.
+    pub word_times: Vec<WordTime>,
+    /// When the text of the next test was last shown or changed, the first
+    /// keystroke latency is measured from there.
+    pub shown_at: Instant,
+    /// When the last test was completed, for the confetti of a new PB.
+    pub completed_at: Instant,
+    /// When the last key was pressed, the cursor doesn't blink while typing.
+    pub last_key: Instant,
+    /// Clickable parts of the last drawn frame, see [`ui::draw`].
+    pub mouse_targets: Vec<(Rect, MouseTarget)>,
+    /// Whether the help overlay listing the keys is shown.
+    pub show_help: bool,
+    /// Stats of the last completed test.
+    pub last_stats: Option<GameStats>,
+    /// Past tests shown on the History screen, loaded when it's opened.
+    pub history: HistoryScreen,
+    /// Tab of the results screen, back to the summary after every test.
+    pub results: ResultsScreen,
+    /// Tests completed per day, for the streak and daily goal.
+    pub activity: Activity,
+    /// Daily practice goal.
+    pub goal: Goal,
+    /// Lines drawn on the WPM charts.
+    pub chart: ChartOptions,
+    /// Appearance of the UI.
+    pub ui: UiOptions,
+    /// How typing tests react to input.
+    pub behavior: Behavior,
+    /// Whether the restart key was pressed once during the test, and has to
+    /// be pressed again to restart, see [`Behavior::quick_restart`].
+    pub confirming_restart: bool,
+    /// Whether to save a replay of every completed test.
+    pub record_replays: bool,
+    /// Tag added to the records of this session.
+    pub tag: Option<String>,
+    /// Warning shown on the Home screen until the next test, e.g. when a text
+    /// couldn't be loaded.
+    pub notice: Option<String>,
 }
 
 /// Represents the lifecycle of the application.
 #[derive(Default, Clone, Copy, PartialEq)]
 pub enum State {
+    /// The logo shown at startup with `ui.splash`, left with any key.
+    Splash,
     /// The main menu of the application.
     #[default]
     Home,
@@ -47,32 +121,221 @@ pub enum State {
     Running,
     /// The test has finished, results should be displayed.
     Complete,
+    /// Charts of past tests, reached from Home.
+    History,
+}
+
+/// Checks that the text of the configured mode can be loaded, replacing it
+/// with the default text otherwise.
+///
+/// Called before the UI starts. Returns a warning to show when the text was
+/// replaced.
+pub fn fallback_text(config: &mut Config) -> Option<String> {
+    let mut mode = config.start_mode();
+    let text = mode.text()?.to_string();
+    let error = Dictionary::load(&text, config).err()?;
+
+    let default = default_text();
+    if text == default || Dictionary::load(&default, config).is_err() {
+        return None;
+    }
+    mode.set_text(default.clone());
+    config.modes.remove(mode.name());
+    config.defaults.mode = mode;
+    Some(format!("{}, using \"{}\" instead", error, default))
 }
 
 impl App {
     /// Creates a new application instance based on the provided configuration.
     pub fn from_config(config: &Config) -> Result<Self> {
-        let mode_config = config.defaults.mode.clone();
+        let mode_config = config.start_mode();
         let mut mode = create_mode(&mode_config);
+        mode.set_behavior(&config.behavior);
         mode.initialize(config)?;
 
         Ok(App {
             should_exit: false,
-            state: State::default(),
+            state: if config.ui.splash {
+                State::Splash
+            } else {
+                State::default()
+            },
             mode,
             mode_config,
             theme: config.theme.clone(),
             focused_option: 0,
             is_editing: false,
             editing_mode: None,
+            modes: Mode::VARIANTS
+                .iter()
+                .map(|name| {
+                    let mode = config.mode(name);
+                    let description = mode.description();
+                    (mode, description)
+                })
+                .collect(),
+            suspended_mode: None,
+            warmup: config.defaults.warmup,
+            words: config.words.clone(),
+            warming_up: false,
+            warmed_up: false,
+            // Personal bests are only shown, a broken history shouldn't prevent starting
+            personal_bests: PersonalBests::load().unwrap_or_default(),
+            new_pb: false,
+            key_stats: KeyStats::default(),
+            word_times: Vec::new(),
+            shown_at: Instant::now(),
+            completed_at: Instant::now(),
+            last_key: Instant::now(),
+            mouse_targets: Vec::new(),
+            show_help: false,
+            last_stats: None,
+            history: HistoryScreen::default(),
+            results: ResultsScreen::default(),
+            activity: Activity::load().unwrap_or_default(),
+            goal: config.goal.clone(),
+            chart: config.chart.clone(),
+            ui: config.ui.clone(),
+            behavior: config.behavior.clone(),
+            confirming_restart: false,
+            record_replays: config.defaults.record_replays,
+            tag: None,
+            notice: None,
         })
     }
 
+    /// Resets the mode, showing the error as a notice if it fails (e.g. a
+    /// text removed while the app is running) rather than quitting.
+    pub fn reset_mode(&mut self) {
+        if let Err(error) = self.mode.reset() {
+            self.notice = Some(error.to_string());
+        }
+    }
+
+    /// Stats of the test in progress, or of the last completed one.
+    ///
+    /// Warm-up rounds aren't tests, they are left out.
+    pub fn final_stats(&self) -> Option<GameStats> {
+        if self.state == State::Running && !self.warming_up {
+            Some(self.mode.get_stats())
+        } else {
+            self.last_stats.clone()
+        }
+    }
+
     /// Returns the current mode name.
     pub fn current_mode_name(&self) -> &'static str {
         self.mode_config.name()
     }
 
+    /// Words typed incorrectly in the last test, in order of appearance.
+    pub fn mistyped_words(&self) -> Vec<WordResult> {
+        self.mode
+            .word_results()
+            .into_iter()
+            .filter(|result| !result.is_correct())
+            .collect()
+    }
+
+    /// Distinct words typed incorrectly in the last test, in order of appearance.
+    pub fn missed_words(&self) -> Vec<String> {
+        let mut missed: Vec<String> = Vec::new();
+
+        for result in self.mistyped_words() {
+            if !missed.contains(&result.target) {
+                missed.push(result.target);
+            }
+        }
+
+        missed
+    }
+
+    /// Replaces the current mode with a round of its missed words.
+    ///
+    /// Returns `false` if there is nothing to retype. The original mode is
+    /// brought back by [`App::resume_mode`].
+    pub fn start_retry(&mut self) -> bool {
+        let missed = self.missed_words();
+        if missed.is_empty() {
+            return false;
+        }
+
+        let mut retry = Retry::new(missed);
+        retry.set_behavior(&self.behavior);
+        let previous = std::mem::replace(&mut self.mode, Box::new(retry));
+        // Retrying a retry keeps the original mode aside
+        if self.suspended_mode.is_none() {
+            self.suspended_mode = Some(previous);
+        }
+        true
+    }
+
+    /// Replaces the current mode with the playback of a replay, and starts it.
+    ///
+    /// A warm-up or retry round in progress is dropped, the original mode is
+    /// brought back by [`App::resume_mode`].
+    pub fn start_playback(&mut self, replay: Replay) {
+        self.resume_mode();
+        let previous = std::mem::replace(&mut self.mode, Box::new(Playback::new(replay)));
+        self.suspended_mode = Some(previous);
+        self.state = State::Running;
+    }
+
+    /// Replaces the current mode with a warm-up round, if enabled and not
+    /// done yet.
+    pub fn start_warmup(&mut self) {
+        if self.warmup == 0 || self.warmed_up || self.suspended_mode.is_some() {
+            return;
+        }
+
+        // A missing default text only skips the warm-up
+        if let Ok(mut warmup) = WarmUp::new(self.warmup, &self.words) {
+            warmup.set_behavior(&self.behavior);
+            self.suspended_mode = Some(std::mem::replace(&mut self.mode, Box::new(warmup)));
+            self.warming_up = true;
+        }
+    }
+
+    /// Ends the warm-up round, the actual test starts with the next keystroke.
+    pub fn finish_warmup(&mut self) {
+        self.warming_up = false;
+        self.warmed_up = true;
+        self.resume_mode();
+        self.shown_at = Instant::now();
+    }
+
+    /// Seconds between the text being shown and the first keystroke of the
+    /// current test, `None` until it starts.
+    pub fn first_key_latency(&self) -> Option<f64> {
+        let start = self.mode.session()?.start?;
+        Some(start.saturating_duration_since(self.shown_at).as_secs_f64())
+    }
+
+    /// The slowest correctly typed words of the last completed test, slowest first.
+    pub fn slowest_words(&self, count: usize) -> Vec<&WordTime> {
+        let mut words: Vec<&WordTime> = self.word_times.iter().collect();
+        words.sort_by(|a, b| a.wpm.total_cmp(&b.wpm));
+        words.truncate(count);
+        words
+    }
+
+    /// Brings back the mode put aside by [`App::start_retry`] or
+    /// [`App::start_warmup`], if any.
+    pub fn resume_mode(&mut self) {
+        self.warming_up = false;
+        if let Some(mode) = self.suspended_mode.take() {
+            self.mode = mode;
+        }
+    }
+
+    /// The configuration of the current mode, including options changed
+    /// from the Home screen.
+    pub fn current_config(&self) -> Mode {
+        self.mode
+            .config()
+            .unwrap_or_else(|| self.mode_config.clone())
+    }
+
     /// Total number of options (1 for mode selector + mode-specific options).
     pub fn total_options(&self) -> usize {
         1 + self.mode.option_count()
@@ -115,7 +378,7 @@ impl App {
             // Mode-specific option adjustment
             let option_index = self.focused_option - 1;
             self.mode.adjust_option(option_index, direction);
-            self.mode.reset()?
+            self.reset_mode();
         }
 
         Ok(())
@@ -143,7 +406,7 @@ impl App {
             // Mode-specific option
             let option_index = self.focused_option - 1;
             self.mode.select_option(option_index);
-            self.mode.reset()?;
+            self.reset_mode();
         }
         Ok(None)
     }
@@ -157,10 +420,54 @@ impl App {
 ///
 /// # Errors
 /// Returns an [`anyhow::Result`] if the terminal fails to draw or if event polling fails.
-pub fn run(terminal: &mut DefaultTerminal, app: &mut App, config: &Config) -> Result<()> {
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, app: &mut App, config: &Config) -> Result<()> {
     while !app.should_exit {
         terminal.draw(|frame| ui::draw(frame, app))?;
         events::handle_events(app, config)?;
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_texts_fall_back_to_the_default() {
+        let mut config = Config::default();
+        assert_eq!(fallback_text(&mut config), None);
+
+        config.defaults.mode.set_text("missing text".to_string());
+        let notice = fallback_text(&mut config).unwrap();
+
+        assert!(notice.contains("missing text"));
+        assert_eq!(config.defaults.mode.text(), Some(default_text().as_str()));
+    }
+
+    #[test]
+    fn playback_gives_back_the_original_mode() {
+        let replay = Replay {
+            mode: "words".to_string(),
+            text: default_text(),
+            target_words: vec!["hi".to_string()],
+            keystrokes: Vec::new(),
+            duration: 1.0,
+            stop_on_error: Default::default(),
+            backspace: Default::default(),
+        };
+        let mut app = App::from_config(&Config::default()).unwrap();
+        let original = app.mode.config().map(|mode| mode.parameters());
+        assert!(original.is_some());
+        app.warmup = 5;
+        app.start_warmup();
+        assert!(app.warming_up);
+
+        app.start_playback(replay);
+        assert!(!app.warming_up);
+        assert!(app.mode.config().is_none());
+
+        app.resume_mode();
+        assert!(app.suspended_mode.is_none());
+        assert_eq!(app.mode.config().map(|mode| mode.parameters()), original);
+    }
+}
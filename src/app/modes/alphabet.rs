@@ -0,0 +1,184 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{Direction, GameStats, Handler, OptionGroup, Renderer, util::TypingSession},
+        ui::char::StyledChar,
+    },
+    config::Config,
+    replay::{Key, Keystroke},
+};
+
+const ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+/// Amount of slowest letters listed on the results screen.
+const SLOWEST_SHOWN: usize = 5;
+
+/// Time taken to reach each letter of `target` from the previous one, in seconds.
+///
+/// A letter is reached once everything typed so far matches the target up
+/// to it, so time spent on mistakes counts against the letter being typed.
+/// The first letter starts the test and has no latency.
+pub fn letter_latencies(target: &str, keystrokes: &[Keystroke]) -> Vec<(char, f64)> {
+    let target: Vec<char> = target.chars().collect();
+    let mut typed: Vec<char> = Vec::new();
+    let mut latencies = Vec::new();
+    let mut reached = 0;
+    let mut previous = 0.0;
+
+    for keystroke in keystrokes {
+        match keystroke.key {
+            Key::Char(c) => typed.push(c),
+            Key::Backspace => {
+                typed.pop();
+            }
+            Key::ClearWord => typed.clear(),
+        }
+
+        if typed.len() > reached && target.starts_with(&typed) {
+            if reached > 0 {
+                latencies.push((target[typed.len() - 1], keystroke.time - previous));
+            }
+            reached = typed.len();
+            previous = keystroke.time;
+        }
+    }
+
+    latencies
+}
+
+pub struct Alphabet {
+    session: TypingSession,
+}
+
+impl Alphabet {
+    pub fn new() -> Self {
+        Self {
+            session: TypingSession::new(),
+        }
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Handler for Alphabet {
+    fn initialize(&mut self, _config: &Config) -> Result<()> {
+        self.session.set_target(vec![ALPHABET.to_string()]);
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.session.clear();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Alphabet {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{:.2}s", self.session.elapsed().as_secs_f64())
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        let mut latencies = letter_latencies(ALPHABET, &self.session.keystrokes);
+        if latencies.is_empty() {
+            return vec![];
+        }
+
+        let average = latencies.iter().map(|(_, t)| t).sum::<f64>() / latencies.len() as f64;
+
+        latencies.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let slowest = latencies
+            .iter()
+            .take(SLOWEST_SHOWN)
+            .map(|(c, t)| format!("{} {:.0}ms", c, t * 1000.0))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        vec![
+            format!("Average latency: {:.0}ms", average * 1000.0),
+            format!("Slowest letters: {}", slowest),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latencies_include_corrections() {
+        let keystroke = |time, key| Keystroke { time, key };
+        let keystrokes = [
+            keystroke(0.0, Key::Char('a')),
+            keystroke(0.1, Key::Char('b')),
+            keystroke(0.2, Key::Char('x')),
+            keystroke(0.3, Key::Backspace),
+            keystroke(0.5, Key::Char('c')),
+        ];
+
+        let latencies = letter_latencies("abc", &keystrokes);
+        assert_eq!(latencies.len(), 2);
+        assert_eq!(latencies[0].0, 'b');
+        assert!((latencies[0].1 - 0.1).abs() < 1e-9);
+        assert_eq!(latencies[1].0, 'c');
+        assert!((latencies[1].1 - 0.4).abs() < 1e-9);
+    }
+}
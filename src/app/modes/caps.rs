@@ -0,0 +1,188 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer, WordResult,
+            util::{Dictionary, TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+const PROBABILITIES: [u8; 4] = [10, 30, 50, 100];
+const WORD_COUNT: usize = 50;
+
+/// Uppercases each letter of `word` with the given probability (in percent).
+pub fn capitalize_randomly<R: Rng + ?Sized>(word: &str, probability: u8, rng: &mut R) -> String {
+    let probability = f64::from(probability.min(100)) / 100.0;
+
+    word.chars()
+        .flat_map(|c| {
+            if rng.random_bool(probability) {
+                c.to_uppercase().collect::<Vec<char>>()
+            } else {
+                vec![c]
+            }
+        })
+        .collect()
+}
+
+pub struct Caps {
+    probability: u8,
+    session: TypingSession,
+    dictionary: Dictionary,
+    text: String,
+    rng: ChaCha8Rng,
+}
+
+impl Caps {
+    pub fn new(probability: u8, text: &str) -> Self {
+        Self {
+            probability: probability.min(100),
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        let rng = &mut self.rng;
+        let words = self
+            .dictionary
+            .pick(WORD_COUNT, rng)
+            .iter()
+            .map(|word| capitalize_randomly(word, self.probability, rng))
+            .collect();
+
+        self.session.set_target(words);
+    }
+}
+
+impl Handler for Caps {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Caps { probability, text } = &config.mode("caps") {
+            self.probability = (*probability).min(100);
+            self.text = text.clone();
+        }
+
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Caps {
+            probability: self.probability,
+            text: self.text.clone(),
+        })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Caps {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let items = PROBABILITIES
+            .iter()
+            .enumerate()
+            .map(|(i, &probability)| OptionItem {
+                label: format!("{}%", probability),
+                is_active: self.probability == probability,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(&probability) = PROBABILITIES.get(index) {
+            self.probability = probability;
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        PROBABILITIES.len()
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{}/{}", self.session.typed_words.len(), WORD_COUNT)
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capitalization_probability_bounds() {
+        let mut rng = rand::rng();
+
+        assert_eq!(capitalize_randomly("hello", 0, &mut rng), "hello");
+        assert_eq!(capitalize_randomly("hello", 100, &mut rng), "HELLO");
+        assert_eq!(
+            capitalize_randomly("hello", 50, &mut rng).to_lowercase(),
+            "hello"
+        );
+    }
+}
@@ -1,16 +1,16 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-use anyhow::{Context, Result};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use rand::seq::SliceRandom;
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
 use crate::{
-    Resource,
     app::{
         events::Action,
         modes::{
-            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer,
-            util::build_styled_chars,
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer, WordResult,
+            util::{Dictionary, InputOutcome, REFILL_AHEAD, TypingSession, word_rng},
         },
         ui::char::StyledChar,
     },
@@ -18,16 +18,21 @@ use crate::{
 };
 
 const DURATIONS: [u64; 4] = [15, 30, 60, 120];
-
+/// Penalties cycled through from the option strip, in seconds.
+const PENALTIES: [u64; 4] = [0, 1, 2, 5];
+/// Option index of the penalty toggle.
+const PENALTY_OPTION: usize = 5;
 pub struct Clock {
     duration: Duration,
     custom_duration: u64,
     is_editing_custom: bool,
-    start: Option<Instant>,
-    target_words: Vec<String>,
-    typed_words: Vec<String>,
-    timestamps: Vec<(usize, Instant)>,
+    session: TypingSession,
+    dictionary: Dictionary,
     text: String,
+    /// Seconds removed for each incorrect character.
+    penalty: u64,
+    mistakes: u32,
+    rng: ChaCha8Rng,
 }
 
 impl Clock {
@@ -43,117 +48,111 @@ impl Clock {
             duration,
             custom_duration,
             is_editing_custom: false,
-            start: None,
-            target_words: Vec::new(),
-            typed_words: Vec::new(),
-            timestamps: Vec::new(),
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
             text: text.to_owned(),
+            penalty: 0,
+            mistakes: 0,
+            rng: ChaCha8Rng::from_os_rng(),
         }
     }
 
-    fn generate_words(&mut self) -> Result<()> {
-        let bytes = Resource::get_text(&self.text)
-            .context(format!("Couldn't find \"{}\" text", &self.text))?;
+    /// Removes `penalty` seconds from the clock for each incorrect character.
+    pub fn with_penalty(mut self, penalty: u64) -> Self {
+        self.penalty = penalty;
+        self
+    }
 
-        let text: Vec<&str> = std::str::from_utf8(&bytes)
-            .context("Text contains non-utf8 characters")?
-            .lines()
-            .collect();
+    fn generate_words(&mut self) {
+        let words = self.dictionary.pick(100, &mut self.rng);
+        self.session.set_target(words);
+        self.mistakes = 0;
+    }
 
-        let mut words: Vec<String> = text
-            .iter()
-            .cycle()
-            .take(100)
-            .map(|s| s.to_string())
-            .collect();
+    /// Total time removed by penalties.
+    fn penalties(&self) -> Duration {
+        Duration::from_secs(self.penalty * u64::from(self.mistakes))
+    }
 
-        let mut rng = rand::rng();
-        words.shuffle(&mut rng);
+    /// Time the test actually lasts, once penalties are removed.
+    fn effective_duration(&self) -> Duration {
+        self.duration.saturating_sub(self.penalties())
+    }
 
-        self.target_words = words;
-        Ok(())
+    /// Time left before the test ends, the whole test until it starts.
+    fn time_left(&self) -> Duration {
+        let elapsed = self.session.start.map(|start| start.elapsed());
+        self.effective_duration()
+            .saturating_sub(elapsed.unwrap_or_default())
     }
 }
 
 impl Handler for Clock {
     fn initialize(&mut self, config: &Config) -> Result<()> {
-        self.typed_words.clear();
-        self.start = None;
-        if let Mode::Clock { duration, text } = &config.defaults.mode {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Clock {
+            duration,
+            text,
+            penalty,
+        } = &config.mode("clock")
+        {
             self.duration = Duration::from_secs(*duration);
+            self.penalty = *penalty;
             if !DURATIONS.contains(duration) {
                 self.custom_duration = *duration;
             }
             self.text = text.clone();
         }
-        self.generate_words()?;
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.generate_words();
         Ok(())
     }
 
-    fn handle_input(&mut self, key: KeyEvent) -> Action {
-        match key.code {
-            KeyCode::Char(c) => {
-                if self.start.is_none() {
-                    self.start = Some(Instant::now());
-                }
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Clock {
+            duration: self.duration.as_secs(),
+            text: self.text.clone(),
+            penalty: self.penalty,
+        })
+    }
 
-                if c == 'h' && key.modifiers.contains(KeyModifiers::CONTROL) {
-                    // Clear current word
-                    if let Some((typed_idx, typed_word)) =
-                        self.typed_words.iter_mut().enumerate().last()
-                        && let Some(target_word) = self.target_words.get(typed_idx)
-                        && typed_word != target_word
-                    {
-                        if typed_word.is_empty() {
-                            self.typed_words.pop();
-                        } else {
-                            typed_word.clear();
-                        }
-                    }
-                } else if c == ' ' {
-                    // Move to next word
-                    if let Some(last) = self.typed_words.last()
-                        && !last.is_empty()
-                    {
-                        self.timestamps
-                            .push((self.typed_words.len(), Instant::now()));
-                        self.typed_words.push(String::new());
-                    }
-                } else if let Some(word) = self.typed_words.last_mut() {
-                    word.push(c);
-                } else {
-                    self.typed_words.push(c.to_string());
-                }
-            }
-            KeyCode::Backspace => {
-                if let Some((typed_idx, typed_word)) =
-                    self.typed_words.iter_mut().enumerate().last()
-                    && let Some(target_word) = self.target_words.get(typed_idx)
-                    && typed_word != target_word
-                    && typed_word.pop().is_none()
-                {
-                    self.typed_words.pop();
-                }
-            }
-            _ => {}
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        if self.session.handle_input(key) == InputOutcome::Mistake {
+            self.mistakes += 1;
         }
-
+        self.session
+            .refill(&self.dictionary, REFILL_AHEAD, &mut self.rng);
         Action::None
     }
 
     fn reset(&mut self) -> Result<()> {
-        self.generate_words()?;
-        self.start = None;
-        self.typed_words.clear();
-        self.timestamps.clear();
+        self.dictionary.reload()?;
+        self.generate_words();
         Ok(())
     }
 
     fn is_complete(&self) -> bool {
-        self.start
-            .map(|s| s.elapsed() >= self.duration)
+        self.session
+            .start
+            .map(|s| s.elapsed() >= self.effective_duration())
             .unwrap_or(false)
     }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
 }
 
 impl Renderer for Clock {
@@ -179,11 +178,24 @@ impl Renderer for Clock {
             is_editing: self.is_editing_custom,
         });
 
+        items.push(OptionItem {
+            label: match self.penalty {
+                0 => "no penalty".to_string(),
+                penalty => format!("-{}s/typo", penalty),
+            },
+            is_active: self.penalty > 0,
+            is_focused: focused_index == Some(PENALTY_OPTION),
+            is_editing: false,
+        });
+
         OptionGroup { items }
     }
 
     fn select_option(&mut self, index: usize) {
-        if index < 4 {
+        if index == PENALTY_OPTION {
+            let current = PENALTIES.iter().position(|&p| p == self.penalty);
+            self.penalty = PENALTIES[current.map_or(0, |i| (i + 1) % PENALTIES.len())];
+        } else if index < 4 {
             self.duration = Duration::from_secs(DURATIONS[index]);
             self.is_editing_custom = false;
         } else {
@@ -216,40 +228,82 @@ impl Renderer for Clock {
     }
 
     fn option_count(&self) -> usize {
-        5
+        6
     }
 
     fn get_progress(&self) -> String {
-        match self.start {
-            Some(start) => {
-                let remaining = self.duration.saturating_sub(start.elapsed());
-                format!("{}", remaining.as_secs())
-            }
+        match self.session.start {
+            Some(_) => self.time_left().as_secs().to_string(),
             None => String::new(),
         }
     }
 
+    fn get_timer(&self) -> Option<(Duration, Duration)> {
+        self.session.start?;
+        Some((self.time_left(), self.duration))
+    }
+
+    fn progress_ratio(&self) -> Option<f64> {
+        let elapsed = self.session.start?.elapsed();
+        let duration = self.effective_duration().as_secs_f64();
+        Some((elapsed.as_secs_f64() / duration.max(1.0)).min(1.0))
+    }
+
     fn get_characters(&self) -> Vec<StyledChar> {
-        build_styled_chars(&self.target_words, &self.typed_words)
+        self.session.styled_chars()
     }
 
     fn get_stats(&self) -> GameStats {
-        GameStats::calculate(self.duration, &self.typed_words, &self.target_words)
+        // Penalties only end the test early, the stats cover the time typed
+        self.session
+            .stats(self.session.elapsed().min(self.duration))
     }
 
     fn get_wpm_data(&self) -> Vec<(f64, f64)> {
-        let mut data = vec![(0.0, 0.0)];
-
-        if let Some(start) = &self.start {
-            for (words, ts) in &self.timestamps {
-                let duration = ts.duration_since(*start);
-                let typed_words = &self.typed_words[..*words];
-                let target_words = &self.target_words[..*words];
-                let stats = GameStats::calculate(duration, typed_words, target_words);
-                data.push((duration.as_secs_f64(), stats.wpm()));
-            }
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        if self.penalty == 0 {
+            return vec![];
         }
 
-        data
+        vec![format!(
+            "Penalties: {} typos, -{}s",
+            self.mistakes,
+            self.penalties().as_secs()
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn penalties_shorten_the_test() {
+        let mut clock = Clock::new(Duration::from_secs(30), "english").with_penalty(2);
+        clock.mistakes = 3;
+        assert_eq!(clock.effective_duration(), Duration::from_secs(24));
+
+        clock.mistakes = 20;
+        assert_eq!(clock.effective_duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn late_penalty_ends_the_test_without_inflating_wpm() {
+        let mut clock = Clock::new(Duration::from_secs(30), "english").with_penalty(2);
+        let words = vec!["hello".to_string(), "world".to_string()];
+        clock.session.target_words = words.clone();
+        clock.session.typed_words = words;
+        let start = Instant::now() - Duration::from_secs(12);
+        clock.session.start = Some(start);
+        clock.session.end = Some(start + Duration::from_secs(12));
+        clock.mistakes = 10;
+
+        assert!(clock.is_complete());
+        // 11 characters in 12 seconds, not in the 10 seconds left by penalties
+        assert!((clock.get_stats().wpm() - 11.0).abs() < 1e-9);
     }
 }
@@ -0,0 +1,179 @@
+use anyhow::{Result, anyhow};
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::{SeedableRng, seq::IndexedRandom};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    Resource,
+    app::{
+        State,
+        events::Action,
+        modes::{
+            Direction, FooterHint, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer,
+            util::{TextSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::{Behavior, Config},
+    resource::store::TextStore,
+};
+
+const LANGUAGES: [&str; 3] = ["rust", "python", "javascript"];
+
+pub struct Code {
+    language: String,
+    session: TextSession,
+    snippet: Option<String>,
+    rng: ChaCha8Rng,
+}
+
+impl Code {
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.to_owned(),
+            session: TextSession::new(),
+            snippet: None,
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn pick_snippet(&mut self) -> Result<()> {
+        let snippets = Resource::list(&format!("code/{}", self.language));
+
+        let rng = &mut self.rng;
+        let name = snippets
+            .choose(rng)
+            .ok_or_else(|| anyhow!("No \"{}\" code snippets found", self.language))?;
+
+        let content = TextStore::text(name)?;
+
+        self.session.set_target(content.trim_end());
+        self.snippet = name.rsplit('/').next().map(ToString::to_string);
+        Ok(())
+    }
+}
+
+impl Handler for Code {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Code { language } = &config.mode("code") {
+            self.language = language.clone();
+        }
+        self.pick_snippet()
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Code {
+            language: self.language.clone(),
+        })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.pick_snippet()
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn set_behavior(&mut self, behavior: &Behavior) {
+        self.session.set_behavior(behavior);
+    }
+
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Tab | KeyCode::Enter)
+    }
+}
+
+impl Renderer for Code {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let items = LANGUAGES
+            .iter()
+            .enumerate()
+            .map(|(i, &language)| OptionItem {
+                label: language.to_string(),
+                is_active: self.language == language,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(language) = LANGUAGES.get(index) {
+            self.language = language.to_string();
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        LANGUAGES.len()
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            let lines = |chars: &[String]| chars.iter().filter(|c| *c == "\n").count() + 1;
+            format!(
+                "{}/{} lines",
+                lines(&self.session.typed),
+                lines(&self.session.target)
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        match &self.snippet {
+            Some(snippet) => vec![format!("Snippet: {}", snippet)],
+            None => vec![],
+        }
+    }
+
+    fn footer_hints(&self) -> Vec<FooterHint> {
+        vec![
+            FooterHint::new("SHIFT+TAB", "Restart", vec![State::Running]),
+            FooterHint::new("TAB", "Indent", vec![State::Running]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_snippets_exist() {
+        for language in LANGUAGES {
+            assert!(!Resource::list(&format!("code/{}", language)).is_empty());
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, KeyResult, Mode, OptionGroup, Renderer, WordResult,
+            WordTime,
+            numbers::digit_groups,
+            util::{Dictionary, TypingSession, punctuate, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::{Behavior, Config},
+    texts::TextMeta,
+};
+
+/// Words generated per section, more than anyone can type in its time.
+const SECTION_WORDS: usize = 100;
+/// Digits per group in the numbers section.
+const NUMBER_LENGTH: usize = 4;
+
+/// The kind of text typed in a section.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SectionKind {
+    Words,
+    Numbers,
+    Punctuation,
+}
+
+impl SectionKind {
+    fn label(&self) -> &'static str {
+        match self {
+            SectionKind::Words => "Words",
+            SectionKind::Numbers => "Numbers",
+            SectionKind::Punctuation => "Punctuation",
+        }
+    }
+}
+
+/// Sections of the test, in order, with their duration in seconds.
+const SECTIONS: [(SectionKind, u64); 3] = [
+    (SectionKind::Words, 30),
+    (SectionKind::Numbers, 15),
+    (SectionKind::Punctuation, 15),
+];
+
+struct Section {
+    kind: SectionKind,
+    duration: Duration,
+    session: TypingSession,
+}
+
+pub struct Combined {
+    sections: Vec<Section>,
+    dictionary: Dictionary,
+    text: String,
+    start: Option<Instant>,
+    /// Index of the section receiving input.
+    active: usize,
+    /// Whether the text allows punctuation, see [`TextMeta`].
+    punctuation: bool,
+    rng: ChaCha8Rng,
+}
+
+impl Combined {
+    pub fn new(text: &str) -> Self {
+        let sections = SECTIONS
+            .iter()
+            .map(|&(kind, seconds)| Section {
+                kind,
+                duration: Duration::from_secs(seconds),
+                session: TypingSession::new(),
+            })
+            .collect();
+
+        Self {
+            sections,
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            start: None,
+            active: 0,
+            punctuation: true,
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        let rng = &mut self.rng;
+
+        for section in &mut self.sections {
+            let words = match section.kind {
+                SectionKind::Words => self.dictionary.pick(SECTION_WORDS, rng),
+                SectionKind::Numbers => digit_groups(NUMBER_LENGTH, SECTION_WORDS, rng),
+                SectionKind::Punctuation if self.punctuation => {
+                    punctuate(self.dictionary.pick(SECTION_WORDS, rng), rng)
+                }
+                SectionKind::Punctuation => self.dictionary.pick(SECTION_WORDS, rng),
+            };
+            section.session.set_target(words);
+        }
+
+        self.start = None;
+        self.active = 0;
+    }
+
+    fn total_duration(&self) -> Duration {
+        self.sections.iter().map(|s| s.duration).sum()
+    }
+
+    /// Index of the section running at `elapsed`, and the time left in it.
+    fn section_at(&self, elapsed: Duration) -> (usize, Duration) {
+        let mut end = Duration::ZERO;
+
+        for (i, section) in self.sections.iter().enumerate() {
+            end += section.duration;
+            if elapsed < end {
+                return (i, end - elapsed);
+            }
+        }
+
+        (self.sections.len() - 1, Duration::ZERO)
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.start.map(|s| s.elapsed()).unwrap_or_default()
+    }
+}
+
+impl Handler for Combined {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Combined { text } = &config.mode("combined") {
+            self.text = text.clone();
+        }
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        // Metadata is optional, a broken sidecar shouldn't prevent typing
+        self.punctuation = TextMeta::load(&self.text)
+            .map(|meta| meta.allows_punctuation())
+            .unwrap_or(true);
+        self.generate_words();
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let (section, _) = self.section_at(start.elapsed());
+
+        if section != self.active {
+            self.sections[self.active].session.finish();
+            self.active = section;
+        }
+
+        self.sections[self.active].session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.start.is_some() && self.elapsed() >= self.total_duration()
+    }
+
+    fn on_complete(&mut self) {
+        for section in &mut self.sections {
+            section.session.finish();
+        }
+    }
+
+    fn set_behavior(&mut self, behavior: &Behavior) {
+        for section in &mut self.sections {
+            section.session.set_behavior(behavior);
+        }
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        // Numbers aren't words worth tracking
+        self.sections
+            .iter()
+            .filter(|s| s.kind == SectionKind::Words)
+            .flat_map(|s| s.session.word_results())
+            .collect()
+    }
+
+    fn key_results(&self) -> Vec<KeyResult> {
+        self.sections
+            .iter()
+            .flat_map(|s| s.session.key_results())
+            .collect()
+    }
+
+    fn word_times(&self) -> Vec<WordTime> {
+        self.sections
+            .iter()
+            .filter(|s| s.kind == SectionKind::Words)
+            .flat_map(|s| s.session.word_times())
+            .collect()
+    }
+}
+
+impl Renderer for Combined {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.start.is_none() {
+            return String::new();
+        }
+
+        let (current, remaining) = self.section_at(self.elapsed());
+        let sections = self
+            .sections
+            .iter()
+            .enumerate()
+            .map(|(i, section)| {
+                if i == current {
+                    format!("[{}]", section.kind.label())
+                } else {
+                    section.kind.label().to_string()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" › ");
+
+        format!("{} {}", sections, remaining.as_secs() + 1)
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        let (current, _) = self.section_at(self.elapsed());
+        self.sections[current].session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        let mut typed_words = Vec::new();
+        let mut target_words = Vec::new();
+
+        for section in &self.sections {
+            let session = &section.session;
+            let typed = session.typed_words.len().min(session.target_words.len());
+            typed_words.extend_from_slice(&session.typed_words[..typed]);
+            target_words.extend_from_slice(&session.target_words[..typed]);
+        }
+
+        let corrected = self.sections.iter().map(|s| s.session.corrected).sum();
+
+        GameStats::calculate(self.total_duration(), &typed_words, &target_words)
+            .with_corrected_errors(corrected)
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        let Some(start) = self.start else {
+            return vec![(0.0, 0.0)];
+        };
+
+        let mut data = vec![(0.0, 0.0)];
+        for section in &self.sections {
+            if let Some(section_start) = section.session.start {
+                let offset = section_start.duration_since(start).as_secs_f64();
+                data.extend(
+                    section
+                        .session
+                        .wpm_data()
+                        .into_iter()
+                        .skip(1)
+                        .map(|(time, wpm)| (time + offset, wpm)),
+                );
+            }
+        }
+
+        data
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        self.sections
+            .iter()
+            .map(|section| {
+                let stats = section.session.stats(section.duration);
+                format!(
+                    "{}: {:.1} WPM, {:.1}%",
+                    section.kind.label(),
+                    stats.wpm(),
+                    stats.accuracy()
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_follow_each_other() {
+        let combined = Combined::new("english");
+
+        assert_eq!(
+            combined.section_at(Duration::from_secs(0)),
+            (0, Duration::from_secs(30))
+        );
+        assert_eq!(
+            combined.section_at(Duration::from_secs(40)),
+            (1, Duration::from_secs(5))
+        );
+        assert_eq!(
+            combined.section_at(Duration::from_secs(50)),
+            (2, Duration::from_secs(10))
+        );
+        assert_eq!(combined.section_at(Duration::from_secs(90)).0, 2);
+    }
+}
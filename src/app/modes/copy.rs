@@ -0,0 +1,199 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, anyhow};
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::{SeedableRng, seq::IndexedRandom};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    Resource,
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, Renderer,
+            util::{TextSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    article,
+    config::{Behavior, Config},
+};
+
+/// Normalizes line endings and strips trailing whitespace from every line,
+/// keeping paragraphs and blank lines as written.
+pub fn normalize_text(content: &str) -> String {
+    content
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+pub struct CopyTyping {
+    file: Option<PathBuf>,
+    url: Option<String>,
+    session: TextSession,
+    /// Name of the passage or file being typed.
+    source: Option<String>,
+    rng: ChaCha8Rng,
+}
+
+impl CopyTyping {
+    pub fn new(file: Option<PathBuf>, url: Option<String>) -> Self {
+        Self {
+            file,
+            url,
+            session: TextSession::new(),
+            source: None,
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn load_text(&mut self) -> Result<()> {
+        if let Some(url) = &self.url {
+            let article = article::fetch(url)?;
+            self.session.set_target(&article.text);
+            self.source = Some(article.title.unwrap_or_else(|| url.clone()));
+            return Ok(());
+        }
+
+        let (name, bytes) = match &self.file {
+            Some(path) => {
+                let bytes = Resource::get_file(path)
+                    .context(format!("Couldn't read \"{}\"", path.display()))?;
+                (path.display().to_string(), bytes)
+            }
+            None => {
+                let passages = Resource::list("passages");
+                let name = passages
+                    .choose(&mut self.rng)
+                    .ok_or_else(|| anyhow!("No passages found"))?;
+                let bytes =
+                    Resource::get_text(name).context(format!("Couldn't find \"{}\" text", name))?;
+                let name = name.rsplit('/').next().unwrap_or(name).replace('-', " ");
+                (name, bytes)
+            }
+        };
+
+        let content = std::str::from_utf8(&bytes).context("Text contains non-utf8 characters")?;
+        self.session.set_target(&normalize_text(content));
+        self.source = Some(name);
+        Ok(())
+    }
+}
+
+impl Handler for CopyTyping {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Copy { file, url } = &config.mode("copy")
+            && (file.is_some() || url.is_some())
+        {
+            self.file = file.clone();
+            self.url = url.clone();
+        }
+        self.load_text()
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        // Standard input can only be read once and articles are downloaded
+        // once, keep typing the same text
+        if self.file.is_some() || self.url.is_some() {
+            self.session.clear();
+            Ok(())
+        } else {
+            self.load_text()
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn set_behavior(&mut self, behavior: &Behavior) {
+        self.session.set_behavior(behavior);
+    }
+
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        key.code == KeyCode::Enter
+    }
+}
+
+impl Renderer for CopyTyping {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            let lines = |chars: &[String]| chars.iter().filter(|c| *c == "\n").count() + 1;
+            format!(
+                "{}/{} lines",
+                lines(&self.session.typed),
+                lines(&self.session.target)
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        match &self.source {
+            Some(source) => vec![format!("Text: {}", source)],
+            None => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_keeps_paragraphs() {
+        let text = "\nFirst line  \r\nsecond line\r\n\r\nNew paragraph\t\n\n";
+        assert_eq!(
+            normalize_text(text),
+            "First line\nsecond line\n\nNew paragraph"
+        );
+    }
+
+    #[test]
+    fn embedded_passages_exist() {
+        assert!(!Resource::list("passages").is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use crossterm::event::KeyEvent;
+
+use crate::{
+    Resource,
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, Renderer, WordResult,
+            util::TypingSession,
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+/// Text used when no file was provided.
+const PLACEHOLDER: &str =
+    "Use ttt run custom --file <path> to type your own text, or pipe it in with --file -";
+
+pub struct Custom {
+    file: Option<PathBuf>,
+    session: TypingSession,
+    words: Vec<String>,
+}
+
+impl Custom {
+    pub fn new(file: Option<PathBuf>) -> Self {
+        Self {
+            file,
+            session: TypingSession::new(),
+            words: Vec::new(),
+        }
+    }
+}
+
+impl Handler for Custom {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        if let Mode::Custom { file } = &config.mode("custom")
+            && file.is_some()
+        {
+            self.file = file.clone();
+        }
+
+        let content = match &self.file {
+            Some(path) => {
+                let bytes = Resource::get_file(path)
+                    .context(format!("Couldn't read \"{}\"", path.display()))?;
+                String::from_utf8(bytes).context("Text contains non-utf8 characters")?
+            }
+            None => PLACEHOLDER.to_string(),
+        };
+
+        self.words = content
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect();
+        if self.words.is_empty() {
+            bail!("Text doesn't contain any word");
+        }
+        self.session.set_target(self.words.clone());
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.session.set_target(self.words.clone());
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Custom {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!(
+                "{}/{}",
+                self.session.typed_words.len(),
+                self.session.target_words.len()
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        match &self.file {
+            Some(path) if path.as_os_str() != "-" => vec![format!("File: {}", path.display())],
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_file_order() {
+        let path = std::env::temp_dir().join("ttt_custom_mode_test.txt");
+        std::fs::write(&path, "first second\n\nthird  fourth\n").unwrap();
+
+        let mut mode = Custom::new(Some(path.clone()));
+        mode.initialize(&Config::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            mode.session.target_words,
+            vec!["first", "second", "third", "fourth"]
+        );
+    }
+
+    #[test]
+    fn rejects_empty_text() {
+        let path = std::env::temp_dir().join("ttt_custom_mode_empty_test.txt");
+        std::fs::write(&path, " \n\n").unwrap();
+
+        let mut mode = Custom::new(Some(path.clone()));
+        let result = mode.initialize(&Config::default());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,260 @@
+use anyhow::{Context, Result, bail};
+use chrono::{Datelike, NaiveDate, Utc};
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    Resource,
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, OptionGroup, Renderer, WordResult, default_text,
+            util::{TypingSession, pick_words},
+        },
+        ui::char::StyledChar,
+    },
+    config::{Config, WordRules},
+    history::{DailyResult, DailyResults},
+};
+
+const WORD_COUNT: usize = 50;
+
+/// The embedded word list the challenge is drawn from, which users can't
+/// override, without the words left out by `rules`.
+///
+/// Word rules change the challenge, it's only shared by users without any.
+fn daily_dictionary(rules: &WordRules) -> Result<Vec<String>> {
+    let bytes = Resource::get_embedded_text(&default_text())?;
+    let text = String::from_utf8(bytes).context("Text contains non-utf8 characters")?;
+    let words: Vec<String> = text
+        .lines()
+        .filter(|word| rules.accepts(word))
+        .map(ToString::to_string)
+        .collect();
+
+    if words.is_empty() {
+        bail!("No word of the daily challenge matches the word rules");
+    }
+    Ok(words)
+}
+
+/// The words of the challenge of the given day.
+///
+/// ChaCha gives the same numbers on every platform and version, unlike
+/// `StdRng`, so everyone gets the same test.
+fn daily_words(dictionary: &[String], date: NaiveDate) -> Vec<String> {
+    let mut rng = ChaCha8Rng::seed_from_u64(daily_seed(date));
+    pick_words(dictionary, WORD_COUNT, &mut rng)
+}
+
+/// Seed shared by every user on the given (UTC) day.
+pub fn daily_seed(date: NaiveDate) -> u64 {
+    date.num_days_from_ce() as u64
+}
+
+pub struct Daily {
+    date: NaiveDate,
+    session: TypingSession,
+    /// Score of the first completion of today's challenge, if any.
+    result: Option<DailyResult>,
+    /// Whether the last completed run was the one recorded for today.
+    recorded: bool,
+    /// Why the last completed run couldn't be recorded, if it wasn't.
+    error: Option<String>,
+    rules: WordRules,
+}
+
+impl Default for Daily {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Daily {
+    pub fn new() -> Self {
+        Self {
+            date: Utc::now().date_naive(),
+            session: TypingSession::new(),
+            result: None,
+            recorded: false,
+            error: None,
+            rules: WordRules::default(),
+        }
+    }
+
+    fn date_key(&self) -> String {
+        self.date.format("%Y-%m-%d").to_string()
+    }
+
+    fn generate_words(&mut self) -> Result<()> {
+        self.date = Utc::now().date_naive();
+        self.recorded = false;
+        self.error = None;
+        self.result = DailyResults::load()
+            .ok()
+            .and_then(|results| results.days.get(&self.date_key()).copied());
+
+        let dictionary = daily_dictionary(&self.rules)?;
+        self.session.set_target(daily_words(&dictionary, self.date));
+        Ok(())
+    }
+
+    /// Stores `result` as today's score, keeping the other days.
+    ///
+    /// Fails without writing anything when the stored results can't be read,
+    /// so they aren't replaced.
+    fn record(&self, result: DailyResult) -> Result<()> {
+        let mut results = DailyResults::load()?;
+        results.days.insert(self.date_key(), result);
+        results.save()
+    }
+}
+
+impl Handler for Daily {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rules = config.words.clone();
+        self.generate_words()
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.generate_words()
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+
+        if self.result.is_some() {
+            return;
+        }
+
+        let stats = self.session.stats(self.session.elapsed());
+        let result = DailyResult {
+            wpm: stats.wpm(),
+            accuracy: stats.accuracy(),
+        };
+
+        match self.record(result) {
+            Ok(()) => self.recorded = true,
+            Err(err) => self.error = Some(format!("{:#}", err)),
+        }
+        self.result = Some(result);
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Daily {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{}/{}", self.session.typed_words.len(), WORD_COUNT)
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        let mut details = vec![format!("Daily challenge {}", self.date_key())];
+
+        match (self.result, self.recorded) {
+            (Some(result), true) => details.push(format!(
+                "Score recorded: {:.1} WPM, {:.1}%",
+                result.wpm, result.accuracy
+            )),
+            (Some(result), false) => details.push(format!(
+                "Already completed today with {:.1} WPM, {:.1}% (practice run)",
+                result.wpm, result.accuracy
+            )),
+            (None, _) => {}
+        }
+        if let Some(error) = &self.error {
+            details.push(format!("Score not recorded: {}", error));
+        }
+
+        details
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_day_same_words() {
+        let dictionary = daily_dictionary(&WordRules::default()).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 14).unwrap();
+
+        assert_eq!(
+            daily_words(&dictionary, date),
+            daily_words(&dictionary, date)
+        );
+        assert_ne!(
+            daily_words(&dictionary, date),
+            daily_words(&dictionary, date.succ_opt().unwrap())
+        );
+        // Pinned, a different RNG would hand out a different challenge
+        assert_eq!(
+            daily_words(&dictionary, date)[..4],
+            ["there", "when", "good", "because"]
+        );
+    }
+
+    #[test]
+    fn rules_leave_words_out() {
+        let rules = WordRules {
+            exclude: vec!["there".to_string()],
+            include_only: vec![],
+        };
+        let dictionary = daily_dictionary(&rules).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 14).unwrap();
+
+        assert!(!daily_words(&dictionary, date).contains(&"there".to_string()));
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use crossterm::event::KeyEvent;
+use rand::{Rng, SeedableRng, seq::IndexedRandom};
+use rand_chacha::ChaCha8Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer,
+            util::{TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+const WORD_COUNT: usize = 50;
+const MIN_WORD_LEN: usize = 2;
+const MAX_WORD_LEN: usize = 5;
+
+/// A group of keys on a QWERTY keyboard to restrict drills to.
+#[derive(
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    ValueEnum,
+    Display,
+    EnumIter,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Debug,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum KeyGroup {
+    #[default]
+    HomeRow,
+    TopRow,
+    BottomRow,
+    LeftHand,
+    RightHand,
+}
+
+impl KeyGroup {
+    /// The keys belonging to the group.
+    pub fn keys(&self) -> &'static [char] {
+        match self {
+            KeyGroup::HomeRow => &['a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l'],
+            KeyGroup::TopRow => &['q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p'],
+            KeyGroup::BottomRow => &['z', 'x', 'c', 'v', 'b', 'n', 'm'],
+            KeyGroup::LeftHand => &[
+                'q', 'w', 'e', 'r', 't', 'a', 's', 'd', 'f', 'g', 'z', 'x', 'c', 'v', 'b',
+            ],
+            KeyGroup::RightHand => &['y', 'u', 'i', 'o', 'p', 'h', 'j', 'k', 'l', 'n', 'm'],
+        }
+    }
+}
+
+/// Generates `count` pseudo-words made only of the keys in `group`.
+pub fn generate_drill<R: Rng + ?Sized>(group: KeyGroup, count: usize, rng: &mut R) -> Vec<String> {
+    let keys = group.keys();
+
+    (0..count)
+        .map(|_| {
+            let len = rng.random_range(MIN_WORD_LEN..=MAX_WORD_LEN);
+            (0..len).filter_map(|_| keys.choose(rng)).collect()
+        })
+        .collect()
+}
+
+pub struct Drill {
+    group: KeyGroup,
+    session: TypingSession,
+    rng: ChaCha8Rng,
+}
+
+impl Drill {
+    pub fn new(group: KeyGroup) -> Self {
+        Self {
+            group,
+            session: TypingSession::new(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        let words = generate_drill(self.group, WORD_COUNT, &mut self.rng);
+        self.session.set_target(words);
+    }
+}
+
+impl Handler for Drill {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Drill { keys } = &config.mode("drill") {
+            self.group = *keys;
+        }
+        self.generate_words();
+        Ok(())
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Drill { keys: self.group })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Drill {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let items = KeyGroup::iter()
+            .enumerate()
+            .map(|(i, group)| OptionItem {
+                label: group.to_string().replace('-', " "),
+                is_active: self.group == group,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(group) = KeyGroup::iter().nth(index) {
+            self.group = group;
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        KeyGroup::iter().count()
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{}/{}", self.session.typed_words.len(), WORD_COUNT)
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drill_only_uses_group_keys() {
+        for group in KeyGroup::iter() {
+            let words = generate_drill(group, 100, &mut rand::rng());
+
+            assert_eq!(words.len(), 100);
+            assert!(words.iter().all(|w| {
+                (MIN_WORD_LEN..=MAX_WORD_LEN).contains(&w.len())
+                    && w.chars().all(|c| group.keys().contains(&c))
+            }));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        State,
+        events::Action,
+        modes::{
+            Direction, FooterHint, GameStats, Handler, Mode, OptionGroup, Renderer, WordResult,
+            util::{Dictionary, REFILL_AHEAD, TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+pub struct Endless {
+    session: TypingSession,
+    dictionary: Dictionary,
+    text: String,
+    rng: ChaCha8Rng,
+}
+
+impl Endless {
+    pub fn new(text: &str) -> Self {
+        Self {
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        self.session.set_target(Vec::new());
+        self.session
+            .refill(&self.dictionary, REFILL_AHEAD, &mut self.rng);
+    }
+}
+
+impl Handler for Endless {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Endless { text } = &config.mode("endless") {
+            self.text = text.clone();
+        }
+
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        if key.code == KeyCode::Enter {
+            // Enter completes the session
+            if self.session.start.is_some() {
+                self.session.finish();
+            }
+        } else {
+            self.session.handle_input(key);
+            self.session
+                .refill(&self.dictionary, REFILL_AHEAD, &mut self.rng);
+        }
+
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.end.is_some()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        key.code == KeyCode::Enter
+    }
+}
+
+impl Renderer for Endless {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{} words", self.session.typed_words.len().saturating_sub(1))
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn footer_hints(&self) -> Vec<FooterHint> {
+        vec![FooterHint::new("ENTER", "Finish", vec![State::Running])]
+    }
+}
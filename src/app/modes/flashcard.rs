@@ -0,0 +1,324 @@
+use anyhow::{Result, bail};
+use crossterm::event::KeyEvent;
+use rand::{SeedableRng, seq::SliceRandom};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    Resource,
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer, WordResult,
+            util::{TypingSession, build_styled_chars, word_rng},
+        },
+        ui::char::{CharState, StyledChar},
+    },
+    config::Config,
+    resource::store::TextStore,
+};
+
+/// Cards asked per round.
+const CARDS_PER_ROUND: usize = 10;
+/// Shown instead of the characters left to type, so the term isn't given away.
+const HIDDEN_CHAR: char = '_';
+
+/// A term and the definition prompting it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Card {
+    pub term: String,
+    pub definition: String,
+}
+
+impl Card {
+    /// Parses cards from tab-separated lines: `term<TAB>definition`.
+    ///
+    /// Lines without a term or a definition are skipped.
+    pub fn parse_all(content: &str) -> Vec<Card> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let (term, definition) = line.split_once('\t')?;
+                let (term, definition) = (term.trim(), definition.trim());
+
+                (!term.is_empty() && !definition.is_empty()).then(|| Card {
+                    term: term.to_string(),
+                    definition: definition.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+pub struct Flashcard {
+    deck: String,
+    decks: Vec<String>,
+    session: TypingSession,
+    cards: Vec<Card>,
+    /// Cards of the current round.
+    round: Vec<Card>,
+    /// Index of the first word of each card of the round.
+    offsets: Vec<usize>,
+    rng: ChaCha8Rng,
+}
+
+impl Flashcard {
+    pub fn new(deck: &str) -> Self {
+        Self {
+            deck: deck.to_owned(),
+            decks: Vec::new(),
+            session: TypingSession::new(),
+            cards: Vec::new(),
+            round: Vec::new(),
+            offsets: Vec::new(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    /// Lists the available decks, user decks added since the last test
+    /// included.
+    fn load_decks(&mut self) {
+        self.decks = Resource::list("flashcards")
+            .iter()
+            .filter_map(|name| name.strip_prefix("flashcards/"))
+            .map(ToString::to_string)
+            .collect();
+    }
+
+    fn load_deck(&mut self) -> Result<()> {
+        let name = format!("flashcards/{}", self.deck);
+        let content = TextStore::text(&name)?;
+
+        self.cards = Card::parse_all(&content);
+        if self.cards.is_empty() {
+            bail!("No cards found in the \"{}\" deck", self.deck);
+        }
+        Ok(())
+    }
+
+    fn deal(&mut self) {
+        let mut cards = self.cards.clone();
+        cards.shuffle(&mut self.rng);
+        cards.truncate(CARDS_PER_ROUND);
+
+        let mut words = Vec::new();
+        self.offsets.clear();
+        for card in &cards {
+            self.offsets.push(words.len());
+            words.extend(card.term.split_whitespace().map(ToString::to_string));
+        }
+
+        self.round = cards;
+        self.session.set_target(words);
+    }
+
+    /// Index of the card being typed.
+    fn current_card(&self) -> usize {
+        let word = self.session.typed_words.len().saturating_sub(1);
+        self.offsets
+            .iter()
+            .rposition(|&offset| offset <= word)
+            .unwrap_or(0)
+    }
+
+    /// Range of the words of the given card.
+    fn card_words(&self, card: usize) -> std::ops::Range<usize> {
+        let start = self.offsets.get(card).copied().unwrap_or(0);
+        let end = self
+            .offsets
+            .get(card + 1)
+            .copied()
+            .unwrap_or(self.session.target_words.len());
+        start..end
+    }
+}
+
+impl Handler for Flashcard {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Flashcard { deck } = &config.mode("flashcard") {
+            self.deck = deck.clone();
+        }
+
+        self.load_decks();
+        self.load_deck()?;
+        self.deal();
+        Ok(())
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Flashcard {
+            deck: self.deck.clone(),
+        })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.load_decks();
+        self.load_deck()?;
+        self.deal();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Flashcard {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let items = self
+            .decks
+            .iter()
+            .enumerate()
+            .map(|(i, deck)| OptionItem {
+                label: deck.clone(),
+                is_active: self.deck == *deck,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(deck) = self.decks.get(index) {
+            self.deck = deck.clone();
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        self.decks.len()
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{}/{}", self.current_card() + 1, self.round.len())
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        let words = self.card_words(self.current_card());
+        let typed = &self.session.typed_words;
+        let typed = &typed[words.start.min(typed.len())..words.end.min(typed.len())];
+
+        build_styled_chars(&self.session.target_words[words], typed, None)
+            .into_iter()
+            .map(|mut c| {
+                if matches!(c.state, CharState::Pending | CharState::Cursor) && c.symbol != " " {
+                    c.symbol = HIDDEN_CHAR.to_string();
+                }
+                c
+            })
+            .collect()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        let results = self.session.word_results();
+        let missed: Vec<&str> = self
+            .round
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                results
+                    .get(self.card_words(*i))
+                    .is_none_or(|words| words.iter().any(|w| !w.is_correct()))
+            })
+            .map(|(_, card)| card.term.as_str())
+            .collect();
+
+        let mut details = vec![format!(
+            "Recalled {}/{} cards",
+            self.round.len() - missed.len(),
+            self.round.len()
+        )];
+        if !missed.is_empty() {
+            details.push(format!("Missed: {}", missed.join(", ")));
+        }
+        details
+    }
+
+    fn get_prompt(&self) -> Option<String> {
+        self.round
+            .get(self.current_card())
+            .map(|card| card.definition.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cards() {
+        let cards = Card::parse_all(
+            "Paris\tCapital of France\nno definition\n\t\nOslo\t Capital of Norway ",
+        );
+
+        assert_eq!(
+            cards,
+            vec![
+                Card {
+                    term: "Paris".to_string(),
+                    definition: "Capital of France".to_string(),
+                },
+                Card {
+                    term: "Oslo".to_string(),
+                    definition: "Capital of Norway".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn cards_map_to_words() {
+        let mut flashcard = Flashcard::new("capitals");
+        flashcard.cards = Card::parse_all("New York\tBig apple\nParis\tCity of light");
+        flashcard.deal();
+
+        let sizes: Vec<usize> = (0..2).map(|i| flashcard.card_words(i).len()).collect();
+        let expected: Vec<usize> = flashcard
+            .round
+            .iter()
+            .map(|c| c.term.split_whitespace().count())
+            .collect();
+        assert_eq!(sizes, expected);
+        assert_eq!(flashcard.current_card(), 0);
+    }
+}
@@ -0,0 +1,284 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, Renderer, WordResult,
+            util::{Dictionary, TypingSession, build_styled_chars, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::{Behavior, Config},
+    history::data_dir,
+    replay::Replay,
+};
+
+const WORD_COUNT: usize = 25;
+
+pub struct Ghost {
+    session: TypingSession,
+    /// The run being raced, if one was recorded for the current text.
+    ghost: Option<Replay>,
+    /// Whether the run that just completed replaced the ghost.
+    new_ghost: bool,
+    dictionary: Dictionary,
+    text: String,
+    rng: ChaCha8Rng,
+    behavior: Behavior,
+}
+
+impl Ghost {
+    pub fn new(text: &str) -> Self {
+        Self {
+            session: TypingSession::new(),
+            ghost: None,
+            new_ghost: false,
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            rng: ChaCha8Rng::from_os_rng(),
+            behavior: Behavior::default(),
+        }
+    }
+
+    /// Location of the ghost recorded for the current text.
+    fn ghost_path(&self) -> Option<PathBuf> {
+        Some(
+            data_dir()?
+                .join("ghosts")
+                .join(format!("{}.json", self.text)),
+        )
+    }
+
+    fn generate_words(&mut self) {
+        self.new_ghost = false;
+        self.ghost = self
+            .ghost_path()
+            .and_then(|path| Replay::load(&path).ok())
+            .filter(|ghost| ghost.text == self.text);
+
+        let words = match &self.ghost {
+            Some(ghost) => ghost.target_words.clone(),
+            None => self.dictionary.pick(WORD_COUNT, &mut self.rng),
+        };
+
+        self.session.set_target(words);
+    }
+
+    /// Ghost cursor position at the current point of the race.
+    fn ghost_cursor(&self) -> Option<(usize, usize)> {
+        let ghost = self.ghost.as_ref()?;
+        self.session.start?;
+
+        let ghost_session = TypingSession::from_replay(ghost, self.session.elapsed().as_secs_f64());
+        Some(ghost_session.cursor())
+    }
+
+    /// Stats of the run being raced.
+    fn ghost_stats(&self) -> Option<GameStats> {
+        let ghost = self.ghost.as_ref()?;
+        let ghost_session = TypingSession::from_replay(ghost, ghost.duration);
+        Some(ghost_session.stats(Duration::from_secs_f64(ghost.duration)))
+    }
+
+    /// Whether the run beats the ghost: a higher net WPM, at an accuracy
+    /// that counts towards personal bests.
+    fn beats_ghost(&self, stats: &GameStats) -> bool {
+        !self.behavior.is_invalid(stats.accuracy())
+            && self
+                .ghost_stats()
+                .is_none_or(|ghost| stats.wpm() > ghost.wpm())
+    }
+}
+
+impl Handler for Ghost {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        self.behavior = config.behavior.clone();
+        if let Mode::Ghost { text } = &config.mode("ghost") {
+            self.text = text.clone();
+        }
+
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+
+        if self.beats_ghost(&self.get_stats())
+            && let Some(path) = self.ghost_path()
+        {
+            let replay = self.session.to_replay("ghost", &self.text);
+            self.new_ghost = replay.save(&path).is_ok();
+        }
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Ghost {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_none() {
+            return String::new();
+        }
+
+        let words = format!(
+            "{}/{}",
+            self.session.typed_words.len(),
+            self.session.target_words.len()
+        );
+
+        match self.ghost_cursor() {
+            Some((ghost_word, _)) => format!("{} (ghost: {})", words, ghost_word + 1),
+            None => words,
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        build_styled_chars(
+            &self.session.target_words,
+            &self.session.typed_words,
+            self.ghost_cursor(),
+        )
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        let stats = self.get_stats();
+
+        let verdict = match self.ghost_stats() {
+            _ if self.behavior.is_invalid(stats.accuracy()) => {
+                "Below the minimum accuracy, the ghost stays".to_string()
+            }
+            None => "No ghost yet, this run is the one to beat".to_string(),
+            Some(ghost) if self.beats_ghost(&stats) => format!(
+                "You win! Ahead of the ghost by {:.0} WPM",
+                stats.wpm() - ghost.wpm()
+            ),
+            Some(ghost) => format!(
+                "The ghost wins, behind by {:.0} WPM",
+                ghost.wpm() - stats.wpm()
+            ),
+        };
+
+        let mut details = vec![verdict];
+        if self.new_ghost && self.ghost.is_some() {
+            details.push("New ghost recorded".to_string());
+        }
+        details
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Backspace, StopOnError};
+    use crate::replay::{Key, Keystroke};
+
+    #[test]
+    fn ghost_cursor_follows_replay() {
+        let replay = Replay {
+            mode: "ghost".to_string(),
+            text: "english".to_string(),
+            target_words: vec!["ab".to_string(), "cd".to_string()],
+            keystrokes: ['a', 'b', ' ', 'c']
+                .iter()
+                .enumerate()
+                .map(|(i, &c)| Keystroke {
+                    time: i as f64,
+                    key: Key::Char(c),
+                })
+                .collect(),
+            duration: 4.0,
+            stop_on_error: StopOnError::Off,
+            backspace: Backspace::Strict,
+        };
+
+        assert_eq!(TypingSession::from_replay(&replay, 1.5).cursor(), (0, 2));
+        assert_eq!(TypingSession::from_replay(&replay, 3.0).cursor(), (1, 1));
+    }
+
+    #[test]
+    fn ghost_is_replaced_by_faster_valid_runs_only() {
+        let replay = Replay {
+            mode: "ghost".to_string(),
+            text: "english".to_string(),
+            target_words: vec!["ab".to_string(), "cd".to_string()],
+            keystrokes: ['a', 'b', ' ', 'c', 'd']
+                .iter()
+                .enumerate()
+                .map(|(i, &c)| Keystroke {
+                    time: i as f64,
+                    key: Key::Char(c),
+                })
+                .collect(),
+            duration: 5.0,
+            stop_on_error: StopOnError::Off,
+            backspace: Backspace::Strict,
+        };
+
+        let mut ghost = Ghost::new("english");
+        ghost.ghost = Some(replay);
+        ghost.behavior.min_accuracy = 90.0;
+        let wpm = ghost.ghost_stats().unwrap().wpm();
+
+        assert!(ghost.beats_ghost(&GameStats::new(wpm + 1.0, 100.0, 1.0)));
+        assert!(!ghost.beats_ghost(&GameStats::new(wpm, 100.0, 1.0)));
+        assert!(!ghost.beats_ghost(&GameStats::new(wpm + 50.0, 80.0, 1.0)));
+    }
+}
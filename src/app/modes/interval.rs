@@ -0,0 +1,254 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        State,
+        events::Action,
+        modes::{
+            Direction, FooterHint, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer,
+            WordResult,
+            util::{Dictionary, REFILL_AHEAD, TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+const ROUNDS: [u32; 4] = [2, 4, 6, 8];
+/// Length of the relaxed segment of a round.
+const RELAX: Duration = Duration::from_secs(30);
+/// Length of the sprint segment of a round.
+const SPRINT: Duration = Duration::from_secs(15);
+
+/// A timed segment of an interval round.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    Relax,
+    Sprint,
+}
+
+/// The segment running at `elapsed` and the time left in it.
+pub fn segment_at(elapsed: Duration) -> (Segment, Duration) {
+    let round = RELAX + SPRINT;
+    let offset = Duration::from_secs_f64(elapsed.as_secs_f64() % round.as_secs_f64());
+
+    if offset < RELAX {
+        (Segment::Relax, RELAX - offset)
+    } else {
+        (Segment::Sprint, round - offset)
+    }
+}
+
+pub struct Interval {
+    rounds: u32,
+    session: TypingSession,
+    dictionary: Dictionary,
+    text: String,
+    rng: ChaCha8Rng,
+}
+
+impl Interval {
+    pub fn new(rounds: u32, text: &str) -> Self {
+        Self {
+            rounds: rounds.max(1),
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        let words = self.dictionary.pick(100, &mut self.rng);
+        self.session.set_target(words);
+    }
+
+    fn duration(&self) -> Duration {
+        (RELAX + SPRINT) * self.rounds
+    }
+
+    /// End times of every segment, relative to the start of the test.
+    fn segment_ends(&self) -> Vec<Duration> {
+        (0..self.rounds)
+            .flat_map(|round| {
+                let start = (RELAX + SPRINT) * round;
+                [start + RELAX, start + RELAX + SPRINT]
+            })
+            .collect()
+    }
+}
+
+impl Handler for Interval {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Interval { rounds, text } = &config.mode("interval") {
+            self.rounds = (*rounds).max(1);
+            self.text = text.clone();
+        }
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Interval {
+            rounds: self.rounds,
+            text: self.text.clone(),
+        })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        self.session
+            .refill(&self.dictionary, REFILL_AHEAD, &mut self.rng);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session
+            .start
+            .map(|s| s.elapsed() >= self.duration())
+            .unwrap_or(false)
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Interval {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let items = ROUNDS
+            .iter()
+            .enumerate()
+            .map(|(i, &rounds)| OptionItem {
+                label: format!("{} rounds", rounds),
+                is_active: self.rounds == rounds,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(&rounds) = ROUNDS.get(index) {
+            self.rounds = rounds;
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        ROUNDS.len()
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_none() {
+            return String::new();
+        }
+
+        let elapsed = self.session.elapsed();
+        let round = (elapsed.as_secs_f64() / (RELAX + SPRINT).as_secs_f64()) as u32 + 1;
+        let (segment, remaining) = segment_at(elapsed);
+        let label = match segment {
+            Segment::Relax => "Relax",
+            Segment::Sprint => "Sprint",
+        };
+
+        format!(
+            "{}/{} {} {}",
+            round.min(self.rounds),
+            self.rounds,
+            label,
+            remaining.as_secs() + 1
+        )
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.duration())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        self.session
+            .split_wpm(&self.segment_ends())
+            .chunks(2)
+            .enumerate()
+            .map(|(i, round)| match round {
+                [relax, sprint] => format!(
+                    "Round {}: relax {:.1} WPM, sprint {:.1} WPM",
+                    i + 1,
+                    relax,
+                    sprint
+                ),
+                _ => format!("Round {}: relax {:.1} WPM", i + 1, round[0]),
+            })
+            .collect()
+    }
+
+    fn footer_hints(&self) -> Vec<FooterHint> {
+        let hint = match segment_at(self.session.elapsed()).0 {
+            Segment::Relax => FooterHint::new("RELAX", "Steady pace", vec![State::Running]),
+            Segment::Sprint => FooterHint::new("SPRINT", "Go all out", vec![State::Running]),
+        };
+
+        vec![hint]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_alternate() {
+        assert_eq!(
+            segment_at(Duration::from_secs(0)),
+            (Segment::Relax, Duration::from_secs(30))
+        );
+        assert_eq!(
+            segment_at(Duration::from_secs(35)),
+            (Segment::Sprint, Duration::from_secs(10))
+        );
+        assert_eq!(
+            segment_at(Duration::from_secs(50)),
+            (Segment::Relax, Duration::from_secs(25))
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use anyhow::{Result, bail};
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, Renderer, WordResult,
+            util::{Dictionary, TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+    history::LadderRecords,
+};
+
+/// Words per round.
+const ROUND_WORDS: usize = 15;
+
+/// Result of a single round of the ladder.
+struct Round {
+    threshold: u32,
+    wpm: f64,
+}
+
+pub struct Ladder {
+    thresholds: Vec<u32>,
+    session: TypingSession,
+    dictionary: Dictionary,
+    text: String,
+    rounds: Vec<Round>,
+    /// Words typed in previous rounds, so they still count towards history.
+    results: Vec<WordResult>,
+    finished: bool,
+    best: usize,
+    /// Why the new best couldn't be saved, if it couldn't.
+    error: Option<String>,
+    rng: ChaCha8Rng,
+}
+
+impl Ladder {
+    pub fn new(thresholds: Vec<u32>, text: &str) -> Self {
+        Self {
+            thresholds,
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            rounds: Vec::new(),
+            results: Vec::new(),
+            finished: false,
+            best: 0,
+            error: None,
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    /// Amount of levels cleared so far.
+    fn cleared(&self) -> usize {
+        self.rounds
+            .iter()
+            .filter(|round| round.wpm >= f64::from(round.threshold))
+            .count()
+    }
+
+    fn start_round(&mut self) {
+        let words = self.dictionary.pick(ROUND_WORDS, &mut self.rng);
+        self.session.set_target(words);
+    }
+
+    /// Scores the finished round, then either starts the next level or ends the ladder.
+    fn end_round(&mut self) {
+        self.session.finish();
+        let wpm = self.session.stats(self.session.elapsed()).wpm();
+        let threshold = self.thresholds[self.rounds.len()];
+
+        self.rounds.push(Round { threshold, wpm });
+        self.results.extend(self.session.word_results());
+
+        if wpm < f64::from(threshold) || self.rounds.len() == self.thresholds.len() {
+            self.finished = true;
+        } else {
+            self.start_round();
+        }
+    }
+
+    /// Saves the levels cleared as the best of this ladder.
+    fn record(&self, cleared: usize) -> Result<()> {
+        let mut records = LadderRecords::load()?;
+        records
+            .ladders
+            .insert(LadderRecords::key(&self.thresholds), cleared);
+        records.save()
+    }
+}
+
+impl Handler for Ladder {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Ladder { thresholds, text } = &config.mode("ladder") {
+            self.thresholds = thresholds.clone();
+            self.text = text.clone();
+        }
+
+        if self.thresholds.is_empty() {
+            bail!("The speed ladder needs at least one threshold");
+        }
+
+        self.best = LadderRecords::load()
+            .ok()
+            .and_then(|records| {
+                records
+                    .ladders
+                    .get(&LadderRecords::key(&self.thresholds))
+                    .copied()
+            })
+            .unwrap_or(0);
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.reset()
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+
+        if self.session.is_finished() {
+            self.end_round();
+        }
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.rounds.clear();
+        self.results.clear();
+        self.finished = false;
+        self.error = None;
+        self.start_round();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.finished
+    }
+
+    fn on_complete(&mut self) {
+        let cleared = self.cleared();
+        if cleared <= self.best {
+            return;
+        }
+
+        self.best = cleared;
+        if let Err(err) = self.record(cleared) {
+            self.error = Some(format!("{:#}", err));
+        }
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.results.clone()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Ladder {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        let level = self.rounds.len().min(self.thresholds.len() - 1);
+        format!(
+            "Level {}/{} · {} WPM · {}/{}",
+            level + 1,
+            self.thresholds.len(),
+            self.thresholds[level],
+            self.session.typed_words.len(),
+            ROUND_WORDS
+        )
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        let cleared = self.cleared();
+        let mut details: Vec<String> = self
+            .rounds
+            .iter()
+            .enumerate()
+            .map(|(i, round)| {
+                format!(
+                    "Level {}: {:.1}/{} WPM {}",
+                    i + 1,
+                    round.wpm,
+                    round.threshold,
+                    if round.wpm >= f64::from(round.threshold) {
+                        "✓"
+                    } else {
+                        "✗"
+                    }
+                )
+            })
+            .collect();
+
+        details.push(format!(
+            "Cleared {}/{} levels (best: {})",
+            cleared,
+            self.thresholds.len(),
+            self.best
+        ));
+        if let Some(error) = &self.error {
+            details.push(format!("Best not recorded: {}", error));
+        }
+        details
+    }
+}
@@ -8,16 +8,42 @@
 //!
 //! Check [crate::app::events] for more details.
 
+pub mod alphabet;
+pub mod caps;
 pub mod clock;
+pub mod code;
+pub mod combined;
+pub mod copy;
+pub mod custom;
+pub mod daily;
+pub mod drill;
+pub mod endless;
+pub mod flashcard;
+pub mod ghost;
+pub mod interval;
+pub mod ladder;
+pub mod ngram;
+pub mod numbers;
+pub mod playback;
+pub mod quote;
+pub mod retry;
+pub mod sprint;
+pub mod stamina;
+pub mod sudden_death;
+pub mod survival;
+pub mod symbols;
 pub mod util;
+pub mod warmup;
+pub mod weak_words;
 pub mod words;
 pub mod zen;
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use anyhow::Result;
-use clap::Subcommand;
+use clap::{Subcommand, builder::RangedU64ValueParser};
 use crossterm::event::KeyEvent;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter, VariantNames};
 
@@ -25,20 +51,75 @@ use crate::{
     app::{
         State,
         events::Action,
-        modes::{clock::Clock, words::Words, zen::Zen},
+        modes::{
+            alphabet::Alphabet,
+            caps::Caps,
+            clock::Clock,
+            code::Code,
+            combined::Combined,
+            copy::CopyTyping,
+            custom::Custom,
+            daily::Daily,
+            drill::{Drill, KeyGroup},
+            endless::Endless,
+            flashcard::Flashcard,
+            ghost::Ghost,
+            interval::Interval,
+            ladder::Ladder,
+            ngram::Ngram,
+            numbers::Numbers,
+            quote::Quote,
+            sprint::Sprint,
+            stamina::Stamina,
+            sudden_death::SuddenDeath,
+            survival::Survival,
+            symbols::Symbols,
+            util::TypingSession,
+            weak_words::WeakWords,
+            words::Words,
+            zen::Zen,
+        },
         ui::char::StyledChar,
     },
-    config::Config,
+    cli::TextParser,
+    config::{Behavior, Config},
+    resource::quotes::QuoteLength,
 };
 
 /// Factory function to create a new boxed [`GameMode`] based on a [`Mode`] configuration.
 pub fn create_mode(mode: &Mode) -> Box<dyn GameMode> {
     match mode {
-        Mode::Clock { duration, text } => {
-            Box::new(Clock::new(Duration::from_secs(*duration), text))
-        }
+        Mode::Clock {
+            duration,
+            text,
+            penalty,
+        } => Box::new(Clock::new(Duration::from_secs(*duration), text).with_penalty(*penalty)),
         Mode::Words { count, text } => Box::new(Words::new(*count, text)),
         Mode::Zen => Box::new(Zen::new()),
+        Mode::Quote { length } => Box::new(Quote::new(*length)),
+        Mode::SuddenDeath { text } => Box::new(SuddenDeath::new(text)),
+        Mode::Code { language } => Box::new(Code::new(language)),
+        Mode::Numbers { length } => Box::new(Numbers::new(*length)),
+        Mode::Endless { text } => Box::new(Endless::new(text)),
+        Mode::Custom { file } => Box::new(Custom::new(file.clone())),
+        Mode::Ghost { text } => Box::new(Ghost::new(text)),
+        Mode::Sprint { duration, text } => {
+            Box::new(Sprint::new(Duration::from_secs(*duration), text))
+        }
+        Mode::Ngram { size } => Box::new(Ngram::new(*size)),
+        Mode::WeakWords => Box::new(WeakWords::new()),
+        Mode::Daily => Box::new(Daily::new()),
+        Mode::Drill { keys } => Box::new(Drill::new(*keys)),
+        Mode::Caps { probability, text } => Box::new(Caps::new(*probability, text)),
+        Mode::Symbols => Box::new(Symbols::new()),
+        Mode::Survival { pace, text } => Box::new(Survival::new(*pace, text)),
+        Mode::Stamina { duration, text } => Box::new(Stamina::new(*duration, text)),
+        Mode::Copy { file, url } => Box::new(CopyTyping::new(file.clone(), url.clone())),
+        Mode::Alphabet => Box::new(Alphabet::new()),
+        Mode::Interval { rounds, text } => Box::new(Interval::new(*rounds, text)),
+        Mode::Ladder { thresholds, text } => Box::new(Ladder::new(thresholds.clone(), text)),
+        Mode::Combined { text } => Box::new(Combined::new(text)),
+        Mode::Flashcard { deck } => Box::new(Flashcard::new(deck)),
     }
 }
 
@@ -46,7 +127,7 @@ pub fn create_mode(mode: &Mode) -> Box<dyn GameMode> {
 ///
 /// This enum serves three roles:
 /// 1. **CLI Subcommands**: Via `clap::Subcommand`, each variant becomes a CLI subcommand
-///    with its fields as arguments (e.g., `ttt clock -d 60 -t english`).
+///    with its fields as arguments (e.g., `ttt run clock -d 60 -t english`).
 /// 2. **Configuration Schema**: Via `serde`, defines the structure for `config.toml`.
 /// 3. **Mode Factory Input**: Used by [`create_mode`] to instantiate the appropriate [`GameMode`].
 ///
@@ -62,17 +143,19 @@ pub fn create_mode(mode: &Mode) -> Box<dyn GameMode> {
 /// # CLI Usage
 ///
 /// ```bash
-/// ttt clock -d 60 -t spanish
-/// ttt words -c 100
+/// ttt run clock -d 60 -t spanish
+/// ttt run words -c 100
 /// ```
-#[derive(Serialize, Deserialize, Subcommand, Display, EnumIter, VariantNames, Clone)]
-#[strum(serialize_all = "lowercase")]
-#[serde(tag = "mode", rename_all = "lowercase")]
+#[derive(
+    Serialize, Deserialize, JsonSchema, Subcommand, Display, EnumIter, VariantNames, Clone,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(tag = "mode", rename_all = "kebab-case")]
 pub enum Mode {
     /// Timer-based game mode.
     Clock {
         /// The text to use for the typing test.
-        #[arg(short, long, default_value_t = default_text())]
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
         #[serde(default = "default_text")]
         text: String,
 
@@ -80,12 +163,17 @@ pub enum Mode {
         #[arg(short, long, default_value_t = default_clock_duration())]
         #[serde(default = "default_clock_duration")]
         duration: u64,
+
+        /// Seconds removed from the clock for each incorrect character, 0 disables it.
+        #[arg(short, long, default_value_t = 0)]
+        #[serde(default)]
+        penalty: u64,
     },
 
     /// Word-count-based game mode.
     Words {
         /// The text to use for the typing test.
-        #[arg(short, long, default_value_t = default_text())]
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
         #[serde(default = "default_text")]
         text: String,
 
@@ -97,6 +185,203 @@ pub enum Mode {
 
     /// Free-typing mode with no target text.
     Zen,
+
+    /// Type a full, punctuated quote.
+    Quote {
+        /// Only pick quotes of the given length.
+        #[arg(short, long, value_enum, default_value_t = QuoteLength::default())]
+        #[serde(default)]
+        length: QuoteLength,
+    },
+
+    /// Words-based game mode that ends on the first mistake.
+    SuddenDeath {
+        /// The text to use for the typing test.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+    },
+
+    /// Type code snippets, indentation and newlines included.
+    Code {
+        /// The programming language of the snippets (rust, python, javascript).
+        #[arg(short, long, default_value_t = default_code_language())]
+        #[serde(default = "default_code_language")]
+        language: String,
+    },
+
+    /// Type random groups of digits to practice the number row.
+    Numbers {
+        /// The amount of digits per group.
+        #[arg(short, long, default_value_t = default_numbers_length())]
+        #[serde(default = "default_numbers_length")]
+        length: usize,
+    },
+
+    /// Marathon mode with no fixed end, words keep coming until you finish.
+    Endless {
+        /// The text to use for the typing test.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+    },
+
+    /// Type the exact contents of a file, in order.
+    Custom {
+        /// The file to type, `-` reads from standard input.
+        #[arg(short, long)]
+        #[serde(default)]
+        file: Option<PathBuf>,
+    },
+
+    /// Race against the ghost of your best run on the same words.
+    Ghost {
+        /// The text to use for the typing test.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+    },
+
+    /// Very short timer-based game mode for burst speed.
+    Sprint {
+        /// The text to use for the typing test.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+
+        /// The duration of the sprint.
+        #[arg(short, long, default_value_t = default_sprint_duration())]
+        #[serde(default = "default_sprint_duration")]
+        duration: u64,
+    },
+
+    /// Drill the most common English bigrams or trigrams.
+    Ngram {
+        /// The n-gram size (2 for bigrams, 3 for trigrams).
+        #[arg(
+            short,
+            long,
+            default_value_t = default_ngram_size(),
+            value_parser = RangedU64ValueParser::<usize>::new().range(2..=3)
+        )]
+        #[serde(default = "default_ngram_size")]
+        size: usize,
+    },
+
+    /// Practice the words you mistype the most, based on your history.
+    WeakWords,
+
+    /// Daily challenge, the same words for everyone each day.
+    Daily,
+
+    /// Drill pseudo-words restricted to a group of keys (home row, left hand...).
+    Drill {
+        /// The group of keys to practice.
+        #[arg(short, long, value_enum, default_value_t = KeyGroup::default())]
+        #[serde(default)]
+        keys: KeyGroup,
+    },
+
+    /// Words with randomly capitalized letters to train SHIFT usage.
+    Caps {
+        /// The text to use for the typing test.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+
+        /// The chance for each letter to be capitalized, in percent.
+        #[arg(short, long, default_value_t = default_caps_probability())]
+        #[serde(default = "default_caps_probability")]
+        probability: u8,
+    },
+
+    /// Drill the brackets, operators and punctuation common in code.
+    Symbols,
+
+    /// Clock that drains unless you keep pace, correct words add time and mistakes remove it.
+    Survival {
+        /// The text to use for the typing test.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+
+        /// The speed needed to keep the clock steady, in WPM.
+        #[arg(short, long, default_value_t = default_survival_pace())]
+        #[serde(default = "default_survival_pace")]
+        pace: u32,
+    },
+
+    /// Long timer-based game mode tracking how your speed holds up over time.
+    Stamina {
+        /// The duration of the test, in minutes.
+        #[arg(short, long, default_value_t = default_stamina_duration())]
+        #[serde(default = "default_stamina_duration")]
+        duration: u64,
+
+        /// The text to use for the typing test.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+    },
+
+    /// Copy a text as written, paragraphs and line breaks included.
+    Copy {
+        /// The file to type instead of an embedded passage, `-` reads from standard input.
+        #[arg(short, long)]
+        #[serde(default)]
+        file: Option<PathBuf>,
+
+        /// A web page to type the article of, such as a Wikipedia article.
+        #[arg(short, long, conflicts_with = "file")]
+        // Not read from the config, pages are only downloaded when asked for
+        #[serde(skip)]
+        url: Option<String>,
+    },
+
+    /// Type the alphabet as fast as possible, with per-letter latency.
+    Alphabet,
+
+    /// Alternate 30 second relaxed segments with 15 second sprints.
+    Interval {
+        /// The amount of relax and sprint rounds.
+        #[arg(short, long, default_value_t = default_interval_rounds())]
+        #[serde(default = "default_interval_rounds")]
+        rounds: u32,
+
+        /// The text to use for the typing test.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+    },
+
+    /// Climb levels by beating an increasing WPM threshold each round.
+    Ladder {
+        /// The WPM to reach for each level, comma separated.
+        #[arg(long, value_delimiter = ',', default_values_t = default_ladder_thresholds())]
+        #[serde(default = "default_ladder_thresholds")]
+        thresholds: Vec<u32>,
+
+        /// The text to use for the typing test.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+    },
+
+    /// Chain 30s of words, 15s of numbers and 15s of punctuated sentences.
+    Combined {
+        /// The text to use for the words sections.
+        #[arg(short, long, default_value_t = default_text(), value_parser = TextParser)]
+        #[serde(default = "default_text")]
+        text: String,
+    },
+
+    /// Recall the term matching each definition of a flashcard deck.
+    Flashcard {
+        /// The deck to practice, read from `texts/flashcards/`.
+        #[arg(short, long, default_value_t = default_flashcard_deck())]
+        #[serde(default = "default_flashcard_deck")]
+        deck: String,
+    },
 }
 
 impl Default for Mode {
@@ -104,6 +389,7 @@ impl Default for Mode {
         Mode::Clock {
             duration: default_clock_duration(),
             text: default_text(),
+            penalty: 0,
         }
     }
 }
@@ -115,12 +401,76 @@ impl Mode {
             "clock" => Mode::Clock {
                 duration: default_clock_duration(),
                 text: default_text(),
+                penalty: 0,
             },
             "words" => Mode::Words {
                 count: default_words_count(),
                 text: default_text(),
             },
             "zen" => Mode::Zen,
+            "quote" => Mode::Quote {
+                length: QuoteLength::default(),
+            },
+            "sudden-death" => Mode::SuddenDeath {
+                text: default_text(),
+            },
+            "code" => Mode::Code {
+                language: default_code_language(),
+            },
+            "numbers" => Mode::Numbers {
+                length: default_numbers_length(),
+            },
+            "endless" => Mode::Endless {
+                text: default_text(),
+            },
+            "custom" => Mode::Custom { file: None },
+            "ghost" => Mode::Ghost {
+                text: default_text(),
+            },
+            "sprint" => Mode::Sprint {
+                duration: default_sprint_duration(),
+                text: default_text(),
+            },
+            "ngram" => Mode::Ngram {
+                size: default_ngram_size(),
+            },
+            "weak-words" => Mode::WeakWords,
+            "daily" => Mode::Daily,
+            "drill" => Mode::Drill {
+                keys: KeyGroup::default(),
+            },
+            "caps" => Mode::Caps {
+                probability: default_caps_probability(),
+                text: default_text(),
+            },
+            "symbols" => Mode::Symbols,
+            "survival" => Mode::Survival {
+                pace: default_survival_pace(),
+                text: default_text(),
+            },
+            "stamina" => Mode::Stamina {
+                duration: default_stamina_duration(),
+                text: default_text(),
+            },
+            "copy" => Mode::Copy {
+                file: None,
+                url: None,
+            },
+            "alphabet" => Mode::Alphabet,
+            "interval" => Mode::Interval {
+                rounds: default_interval_rounds(),
+                text: default_text(),
+            },
+            "ladder" => Mode::Ladder {
+                thresholds: default_ladder_thresholds(),
+                text: default_text(),
+            },
+            "combined" => Mode::Combined {
+                text: default_text(),
+            },
+            "flashcard" => Mode::Flashcard {
+                deck: default_flashcard_deck(),
+            },
             _ => Mode::default(),
         }
     }
@@ -131,6 +481,112 @@ impl Mode {
             Mode::Clock { .. } => "clock",
             Mode::Words { .. } => "words",
             Mode::Zen => "zen",
+            Mode::Quote { .. } => "quote",
+            Mode::SuddenDeath { .. } => "sudden-death",
+            Mode::Code { .. } => "code",
+            Mode::Numbers { .. } => "numbers",
+            Mode::Endless { .. } => "endless",
+            Mode::Custom { .. } => "custom",
+            Mode::Ghost { .. } => "ghost",
+            Mode::Sprint { .. } => "sprint",
+            Mode::Ngram { .. } => "ngram",
+            Mode::WeakWords => "weak-words",
+            Mode::Daily => "daily",
+            Mode::Drill { .. } => "drill",
+            Mode::Caps { .. } => "caps",
+            Mode::Symbols => "symbols",
+            Mode::Survival { .. } => "survival",
+            Mode::Stamina { .. } => "stamina",
+            Mode::Copy { .. } => "copy",
+            Mode::Alphabet => "alphabet",
+            Mode::Interval { .. } => "interval",
+            Mode::Ladder { .. } => "ladder",
+            Mode::Combined { .. } => "combined",
+            Mode::Flashcard { .. } => "flashcard",
+        }
+    }
+
+    /// Short description of the mode, the one of its command.
+    pub fn description(&self) -> String {
+        Mode::augment_subcommands(clap::Command::new("ttt"))
+            .find_subcommand(self.name())
+            .and_then(|command| command.get_about())
+            .map(|about| about.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Settings of the mode as `name value` pairs, e.g. "duration 30 · text
+    /// english".
+    pub fn parameters(&self) -> String {
+        let Ok(toml::Value::Table(table)) = toml::Value::try_from(self) else {
+            return String::new();
+        };
+
+        table
+            .iter()
+            .filter(|(key, _)| *key != "mode")
+            .map(|(key, value)| match value {
+                toml::Value::String(value) => format!("{} {}", key, value),
+                value => format!("{} {}", key, value),
+            })
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+
+    /// Returns the name of the text the mode draws its words from, if any.
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            Mode::Clock { text, .. }
+            | Mode::Words { text, .. }
+            | Mode::SuddenDeath { text }
+            | Mode::Endless { text }
+            | Mode::Ghost { text }
+            | Mode::Sprint { text, .. }
+            | Mode::Caps { text, .. }
+            | Mode::Survival { text, .. }
+            | Mode::Stamina { text, .. }
+            | Mode::Interval { text, .. }
+            | Mode::Ladder { text, .. }
+            | Mode::Combined { text } => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Mutable text of the mode, see [`Mode::text`].
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Mode::Clock { text, .. }
+            | Mode::Words { text, .. }
+            | Mode::SuddenDeath { text }
+            | Mode::Endless { text }
+            | Mode::Ghost { text }
+            | Mode::Sprint { text, .. }
+            | Mode::Caps { text, .. }
+            | Mode::Survival { text, .. }
+            | Mode::Stamina { text, .. }
+            | Mode::Interval { text, .. }
+            | Mode::Ladder { text, .. }
+            | Mode::Combined { text } => Some(text),
+            Mode::Zen
+            | Mode::Quote { .. }
+            | Mode::Code { .. }
+            | Mode::Numbers { .. }
+            | Mode::Custom { .. }
+            | Mode::Ngram { .. }
+            | Mode::WeakWords
+            | Mode::Daily
+            | Mode::Drill { .. }
+            | Mode::Symbols
+            | Mode::Copy { .. }
+            | Mode::Alphabet
+            | Mode::Flashcard { .. } => None,
+        }
+    }
+
+    /// Replaces the text of modes drawing words from one, see [`Mode::text`].
+    pub fn set_text(&mut self, text: String) {
+        if let Some(current) = self.text_mut() {
+            *current = text;
         }
     }
 }
@@ -139,6 +595,10 @@ pub fn default_clock_duration() -> u64 {
     30
 }
 
+pub fn default_sprint_duration() -> u64 {
+    10
+}
+
 pub fn default_words_count() -> usize {
     50
 }
@@ -147,6 +607,42 @@ pub fn default_text() -> String {
     "english".to_string()
 }
 
+pub fn default_caps_probability() -> u8 {
+    30
+}
+
+pub fn default_survival_pace() -> u32 {
+    60
+}
+
+pub fn default_stamina_duration() -> u64 {
+    10
+}
+
+pub fn default_interval_rounds() -> u32 {
+    4
+}
+
+pub fn default_ladder_thresholds() -> Vec<u32> {
+    vec![30, 40, 50, 60, 70, 80, 90, 100]
+}
+
+pub fn default_flashcard_deck() -> String {
+    "capitals".to_string()
+}
+
+pub fn default_code_language() -> String {
+    "rust".to_string()
+}
+
+pub fn default_ngram_size() -> usize {
+    2
+}
+
+pub fn default_numbers_length() -> usize {
+    4
+}
+
 /// Represents a selectable option in the options bar.
 pub struct OptionItem {
     pub label: String,
@@ -160,6 +656,52 @@ pub struct OptionGroup {
     pub items: Vec<OptionItem>,
 }
 
+/// A target word and what was typed for it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WordResult {
+    pub target: String,
+    pub typed: String,
+}
+
+impl WordResult {
+    pub fn is_correct(&self) -> bool {
+        self.target == self.typed
+    }
+
+    /// Characters of the word styled as in the typing area once confirmed:
+    /// the target's characters against the typed ones, followed by the extra
+    /// typed characters.
+    pub fn styled_chars(&self) -> Vec<StyledChar> {
+        let mut chars = util::build_styled_chars(
+            std::slice::from_ref(&self.target),
+            &[self.typed.clone(), String::new()],
+            None,
+        );
+        // Space after the word
+        chars.pop();
+        chars
+    }
+}
+
+/// A single character typed against the target text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyResult {
+    /// The character that should have been typed.
+    pub key: char,
+    pub correct: bool,
+    /// Seconds since the previous keystroke, `None` for the first one.
+    pub latency: Option<f64>,
+}
+
+/// Time taken to type a word, from the previous word's confirmation to its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordTime {
+    pub word: String,
+    pub seconds: f64,
+    /// Speed on the word, counting its characters and the confirming space.
+    pub wpm: f64,
+}
+
 /// Direction for option adjustment.
 #[derive(Clone, Copy)]
 pub enum Direction {
@@ -210,6 +752,68 @@ pub trait Handler {
 
     /// Called when transitioning to Complete state.
     fn on_complete(&mut self) {}
+
+    /// The [`Mode`] matching the options currently selected, used to record
+    /// results. Modes without options return `None`, their configuration
+    /// can't change after creation.
+    fn config(&self) -> Option<Mode> {
+        None
+    }
+
+    /// Target/typed pairs of the words typed during the test, used for
+    /// per-word statistics. Modes without target words return nothing.
+    fn word_results(&self) -> Vec<WordResult> {
+        vec![]
+    }
+
+    /// The session of modes typing against [`TypingSession`], used to record
+    /// replays of completed tests.
+    fn session(&self) -> Option<&TypingSession> {
+        None
+    }
+
+    /// Characters typed during the test with their latency, used for
+    /// per-key statistics. Defaults to the keys of [`Handler::session`].
+    fn key_results(&self) -> Vec<KeyResult> {
+        self.session()
+            .map(TypingSession::key_results)
+            .unwrap_or_default()
+    }
+
+    /// Mutable access to [`Handler::session`].
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        None
+    }
+
+    /// Applies the `[behavior]` settings of the config, defaults to the
+    /// session of [`Handler::session_mut`]. Called whenever a mode is set up.
+    fn set_behavior(&mut self, behavior: &Behavior) {
+        if let Some(session) = self.session_mut() {
+            session.set_behavior(behavior);
+        }
+    }
+
+    /// Time taken on each correctly typed word, defaults to the words of
+    /// [`Handler::session`].
+    fn word_times(&self) -> Vec<WordTime> {
+        self.session()
+            .map(TypingSession::word_times)
+            .unwrap_or_default()
+    }
+
+    /// Seconds into the test at which errors were made, defaults to the
+    /// errors of [`Handler::session`].
+    fn error_times(&self) -> Vec<f64> {
+        self.session()
+            .map(TypingSession::error_times)
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the mode wants to receive a key that is normally handled
+    /// globally while running (e.g., TAB in modes where tabs are typeable).
+    fn captures_key(&self, _key: &KeyEvent) -> bool {
+        false
+    }
 }
 
 /// Data provider for the global renderer.
@@ -237,6 +841,17 @@ pub trait Renderer {
     /// Progress text to display (e.g., "45" for timer, "23/50" for word count).
     fn get_progress(&self) -> String;
 
+    /// Time left and total time of a running timed test, drawn before the
+    /// progress text in the timer style of the config.
+    fn get_timer(&self) -> Option<(Duration, Duration)> {
+        None
+    }
+
+    /// Optional share of the test done, from 0 to 1, drawn as a progress bar.
+    fn progress_ratio(&self) -> Option<f64> {
+        None
+    }
+
     /// Characters to display with their semantic states.
     fn get_characters(&self) -> Vec<StyledChar>;
 
@@ -246,6 +861,16 @@ pub trait Renderer {
     /// WPM data points for the chart: (time_seconds, wpm).
     fn get_wpm_data(&self) -> Vec<(f64, f64)>;
 
+    /// Optional mode-specific lines for the completion screen (e.g., a quote's author).
+    fn get_details(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Optional line shown above the typing area (e.g., a flashcard's definition).
+    fn get_prompt(&self) -> Option<String> {
+        None
+    }
+
     /// Optional mode-specific key hints for the footer.
     fn footer_hints(&self) -> Vec<FooterHint> {
         vec![]
@@ -260,21 +885,101 @@ impl<T: Handler + Renderer> GameMode for T {}
 ///
 /// This struct provides a standardized way for game modes to report performance
 /// metrics like Words Per Minute (WPM), accuracy percentage, and total elapsed time.
+#[derive(Serialize, Clone, Debug)]
 pub struct GameStats {
     wpm: f64,
+    raw_wpm: f64,
     accuracy: f64,
+    corrected_errors: Option<usize>,
     duration: f64,
+    peak_burst: Option<f64>,
+    segments: Vec<f64>,
+    first_key: Option<f64>,
 }
 
 impl GameStats {
     pub fn new(wpm: f64, accuracy: f64, duration: f64) -> Self {
         Self {
             wpm,
+            raw_wpm: wpm,
             accuracy,
+            corrected_errors: None,
             duration,
+            peak_burst: None,
+            segments: Vec::new(),
+            first_key: None,
         }
     }
 
+    /// Sets the gross WPM, counting every typed character whether it's
+    /// correct or not. Defaults to the net WPM.
+    pub fn with_raw_wpm(mut self, raw_wpm: f64) -> Self {
+        self.raw_wpm = raw_wpm;
+        self
+    }
+
+    pub fn raw_wpm(&self) -> f64 {
+        self.raw_wpm
+    }
+
+    /// Sets the amount of incorrect characters that were erased before the
+    /// end of the test.
+    pub fn with_corrected_errors(mut self, corrected_errors: usize) -> Self {
+        self.corrected_errors = Some(corrected_errors);
+        self
+    }
+
+    pub fn corrected_errors(&self) -> Option<usize> {
+        self.corrected_errors
+    }
+
+    /// Sets the fastest speed reached on a single word, in WPM.
+    pub fn with_peak_burst(mut self, peak_burst: f64) -> Self {
+        self.peak_burst = Some(peak_burst);
+        self
+    }
+
+    pub fn peak_burst(&self) -> Option<f64> {
+        self.peak_burst
+    }
+
+    /// Sets the WPM reached over consecutive segments of the test (e.g. each minute).
+    pub fn with_segments(mut self, segments: Vec<f64>) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    pub fn segments(&self) -> &[f64] {
+        &self.segments
+    }
+
+    /// Sets the seconds between the text being shown and the first keystroke.
+    pub fn with_first_key(mut self, first_key: f64) -> Self {
+        self.first_key = Some(first_key);
+        self
+    }
+
+    pub fn first_key(&self) -> Option<f64> {
+        self.first_key
+    }
+
+    /// Average WPM of the first and second half of the segments.
+    ///
+    /// With an odd amount of segments, the middle one is left out. Returns
+    /// `None` when there are less than 2 segments.
+    pub fn fatigue(&self) -> Option<(f64, f64)> {
+        let half = self.segments.len() / 2;
+        if half == 0 {
+            return None;
+        }
+
+        let average = |segments: &[f64]| segments.iter().sum::<f64>() / segments.len() as f64;
+        let first = average(&self.segments[..half]);
+        let second = average(&self.segments[self.segments.len() - half..]);
+
+        Some((first, second))
+    }
+
     pub fn wpm(&self) -> f64 {
         self.wpm
     }
@@ -300,14 +1005,13 @@ impl GameStats {
 
         for (i, typed) in typed_words.iter().enumerate() {
             if let Some(target) = target_words.get(i) {
-                total_chars += typed.len();
-
-                let min_len = typed.len().min(target.len());
-                for j in 0..min_len {
-                    if typed.chars().nth(j) == target.chars().nth(j) {
-                        correct_chars += 1;
-                    }
-                }
+                let typed_graphemes = util::graphemes(typed);
+                total_chars += typed_graphemes.len();
+                correct_chars += typed_graphemes
+                    .iter()
+                    .zip(util::graphemes(target))
+                    .filter(|(typed, target)| **typed == *target)
+                    .count();
 
                 if i < typed_words.len() - 1 {
                     total_chars += 1;
@@ -327,6 +1031,106 @@ impl GameStats {
         let gross_wpm = (total_chars as f64 / 5.0) / duration_mins;
         let wpm = gross_wpm * (accuracy / 100.0);
 
-        Self::new(wpm, accuracy, duration.as_secs_f64())
+        Self::new(wpm, accuracy, duration.as_secs_f64()).with_raw_wpm(gross_wpm)
+    }
+
+    /// Calculates statistics for character-level tests, where whitespace and
+    /// newlines are typed like any other character.
+    pub fn calculate_chars<T: PartialEq>(duration: Duration, typed: &[T], target: &[T]) -> Self {
+        let duration_mins = duration.as_secs_f64() / 60.0;
+
+        if typed.is_empty() || duration_mins == 0.0 {
+            return Self::new(0.0, 0.0, duration.as_secs_f64());
+        }
+
+        let correct_chars = typed
+            .iter()
+            .zip(target)
+            .filter(|(typed, target)| typed == target)
+            .count();
+
+        let accuracy = (correct_chars as f64 / typed.len() as f64) * 100.0;
+        let gross_wpm = (typed.len() as f64 / 5.0) / duration_mins;
+        let wpm = gross_wpm * (accuracy / 100.0);
+
+        Self::new(wpm, accuracy, duration.as_secs_f64()).with_raw_wpm(gross_wpm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_text_keeps_the_other_settings() {
+        let mut mode = Mode::Clock {
+            duration: 60,
+            text: default_text(),
+            penalty: 2,
+        };
+        mode.set_text("french".to_string());
+        assert_eq!(mode.text(), Some("french"));
+        assert_eq!(mode.parameters(), "duration 60 · penalty 2 · text french");
+
+        let mut mode = Mode::Zen;
+        mode.set_text("french".to_string());
+        assert_eq!(mode.text(), None);
+
+        for name in Mode::VARIANTS {
+            let mut mode = Mode::default_for(name);
+            assert_eq!(mode.text().is_some(), mode.text_mut().is_some(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn word_results_style_each_character() {
+        use crate::app::ui::char::CharState;
+
+        let result = WordResult {
+            target: "word".to_string(),
+            typed: "wrd".to_string(),
+        };
+        let chars = result.styled_chars();
+        let states: Vec<(&str, CharState)> =
+            chars.iter().map(|c| (c.symbol.as_str(), c.state)).collect();
+        assert_eq!(
+            states,
+            vec![
+                ("w", CharState::Correct),
+                ("o", CharState::Incorrect),
+                ("r", CharState::Incorrect),
+                ("d", CharState::Skipped),
+            ]
+        );
+
+        let result = WordResult {
+            target: "a".to_string(),
+            typed: "ab".to_string(),
+        };
+        let states: Vec<CharState> = result.styled_chars().iter().map(|c| c.state).collect();
+        assert_eq!(states, vec![CharState::Correct, CharState::Extra]);
+    }
+
+    #[test]
+    fn stats_count_accented_letters_once() {
+        // "é" as "e" and a combining accent
+        let typed = vec!["cafe\u{301}".to_string()];
+        let target = vec!["café".to_string(), "noir".to_string()];
+        let stats = GameStats::calculate(Duration::from_secs(12), &typed, &target);
+        assert_eq!(stats.accuracy(), 75.0);
+        assert!((stats.raw_wpm() - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn modes_describe_themselves() {
+        let mode = Mode::Words {
+            count: 25,
+            text: "french".to_string(),
+        };
+
+        assert_eq!(mode.description(), "Word-count-based game mode");
+        assert_eq!(mode.parameters(), "count 25 · text french");
+        assert_eq!(Mode::Zen.parameters(), "");
+        assert_eq!(Mode::Custom { file: None }.parameters(), "");
     }
 }
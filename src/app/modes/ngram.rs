@@ -0,0 +1,214 @@
+use anyhow::{Result, bail};
+use crossterm::event::KeyEvent;
+use rand::{Rng, SeedableRng, seq::IndexedRandom};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer,
+            util::{TypingSession, load_dictionary, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::{Config, WordRules},
+};
+
+/// Supported n-gram sizes, with their embedded text and menu label.
+const SIZES: [(usize, &str, &str); 2] = [
+    (2, "ngrams/bigrams", "bigrams"),
+    (3, "ngrams/trigrams", "trigrams"),
+];
+/// Distinct n-grams per drill.
+const DRILL_NGRAMS: usize = 15;
+/// Times each n-gram is repeated in a row.
+const REPEATS: usize = 3;
+
+/// Builds a drill of `count` n-grams picked from `ngrams`, each repeated `repeats` times.
+///
+/// The list is expected in frequency order, the most common n-grams are given
+/// a higher chance of being picked.
+pub fn generate_drill<R: Rng + ?Sized>(
+    ngrams: &[String],
+    count: usize,
+    repeats: usize,
+    rng: &mut R,
+) -> Vec<String> {
+    let weighted: Vec<(usize, &String)> = ngrams.iter().enumerate().collect();
+
+    (0..count)
+        .filter_map(|_| {
+            weighted
+                .choose_weighted(rng, |(rank, _)| ngrams.len() - rank)
+                .ok()
+        })
+        .flat_map(|(_, ngram)| std::iter::repeat_n((*ngram).clone(), repeats))
+        .collect()
+}
+
+pub struct Ngram {
+    size: usize,
+    session: TypingSession,
+    ngrams: Vec<String>,
+    rng: ChaCha8Rng,
+}
+
+impl Ngram {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            session: TypingSession::new(),
+            ngrams: Vec::new(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn load_ngrams(&mut self) -> Result<()> {
+        let Some((_, name, _)) = SIZES.iter().find(|(size, ..)| *size == self.size) else {
+            bail!("Unsupported n-gram size {}, expected 2 or 3", self.size);
+        };
+        // N-grams aren't dictionary words, the word rules don't apply to them
+        self.ngrams = load_dictionary(name, &WordRules::default())?;
+        Ok(())
+    }
+
+    fn generate_words(&mut self) {
+        let rng = &mut self.rng;
+        let words = generate_drill(&self.ngrams, DRILL_NGRAMS, REPEATS, rng);
+        self.session.set_target(words);
+    }
+}
+
+impl Handler for Ngram {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Ngram { size } = &config.mode("ngram") {
+            self.size = *size;
+        }
+
+        self.load_ngrams()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Ngram { size: self.size })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.load_ngrams()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Ngram {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let items = SIZES
+            .iter()
+            .enumerate()
+            .map(|(i, &(size, _, label))| OptionItem {
+                label: label.to_string(),
+                is_active: self.size == size,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(&(size, ..)) = SIZES.get(index) {
+            self.size = size;
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        SIZES.len()
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!(
+                "{}/{}",
+                self.session.typed_words.len(),
+                self.session.target_words.len()
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drill_repeats_each_ngram() {
+        let ngrams: Vec<String> = ["th", "he", "in"].iter().map(|s| s.to_string()).collect();
+        let drill = generate_drill(&ngrams, 4, 3, &mut rand::rng());
+
+        assert_eq!(drill.len(), 12);
+        for group in drill.chunks(3) {
+            assert!(group.iter().all(|ngram| ngram == &group[0]));
+            assert!(ngrams.contains(&group[0]));
+        }
+    }
+
+    #[test]
+    fn embedded_ngrams_have_expected_sizes() {
+        for (size, name, _) in SIZES {
+            let ngrams = load_dictionary(name, &WordRules::default()).unwrap();
+            assert!(!ngrams.is_empty());
+            assert!(ngrams.iter().all(|ngram| ngram.len() == size));
+        }
+    }
+
+    #[test]
+    fn unsupported_size_is_an_error() {
+        let mut mode = Ngram::new(4);
+        assert!(mode.load_ngrams().is_err());
+    }
+}
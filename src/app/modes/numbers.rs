@@ -0,0 +1,167 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer,
+            util::{TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+const GROUP_LENGTHS: [usize; 5] = [2, 3, 4, 5, 6];
+const GROUP_COUNT: usize = 50;
+
+/// Generates `count` groups of `length` random digits.
+pub fn digit_groups<R: Rng + ?Sized>(length: usize, count: usize, rng: &mut R) -> Vec<String> {
+    (0..count)
+        .map(|_| {
+            (0..length)
+                .map(|_| char::from(b'0' + rng.random_range(0..10)))
+                .collect()
+        })
+        .collect()
+}
+
+pub struct Numbers {
+    length: usize,
+    session: TypingSession,
+    rng: ChaCha8Rng,
+}
+
+impl Numbers {
+    pub fn new(length: usize) -> Self {
+        Self {
+            length: length.max(1),
+            session: TypingSession::new(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        let words = digit_groups(self.length, GROUP_COUNT, &mut self.rng);
+        self.session.set_target(words);
+    }
+}
+
+impl Handler for Numbers {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Numbers { length } = &config.mode("numbers") {
+            self.length = (*length).max(1);
+        }
+        self.generate_words();
+        Ok(())
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Numbers {
+            length: self.length,
+        })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Numbers {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let items = GROUP_LENGTHS
+            .iter()
+            .enumerate()
+            .map(|(i, &length)| OptionItem {
+                label: format!("{} digits", length),
+                is_active: self.length == length,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(&length) = GROUP_LENGTHS.get(index) {
+            self.length = length;
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        GROUP_LENGTHS.len()
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{}/{}", self.session.typed_words.len(), GROUP_COUNT)
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_digit_groups() {
+        let mut mode = Numbers::new(4);
+        mode.generate_words();
+
+        assert_eq!(mode.session.target_words.len(), GROUP_COUNT);
+        assert!(mode.session.target_words.iter().all(|w| w.len() == 4));
+        assert!(
+            mode.session
+                .target_words
+                .iter()
+                .all(|w| w.chars().all(|c| c.is_ascii_digit()))
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    app::{
+        State,
+        events::Action,
+        modes::{
+            Direction, FooterHint, GameStats, Handler, OptionGroup, Renderer, util::TypingSession,
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+    replay::Replay,
+};
+
+/// Playback speeds cycled through with the arrow keys.
+const SPEEDS: [f64; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+/// Index of the real time speed in [`SPEEDS`].
+const REAL_TIME: usize = 2;
+
+/// Plays a recorded session back, re-typing its keystrokes as they were typed.
+///
+/// This mode has no [`Mode`](super::Mode) variant, it is started with
+/// `ttt replay <file>` or from the History screen, see
+/// [`App::start_playback`](crate::app::App::start_playback).
+pub struct Playback {
+    replay: Replay,
+    /// Index into [`SPEEDS`].
+    speed: usize,
+    /// Replay seconds played before the last pause or speed change.
+    played: f64,
+    /// When playback last resumed, `None` while paused.
+    resumed: Option<Instant>,
+    /// WPM over the whole replay, computed once.
+    wpm_data: Vec<(f64, f64)>,
+}
+
+impl Playback {
+    pub fn new(replay: Replay) -> Self {
+        let wpm_data = wpm_data(&replay);
+
+        Self {
+            replay,
+            speed: REAL_TIME,
+            played: 0.0,
+            resumed: Some(Instant::now()),
+            wpm_data,
+        }
+    }
+
+    /// Replay seconds played so far.
+    fn position(&self) -> f64 {
+        let playing = self.resumed.map_or(0.0, |resumed| {
+            resumed.elapsed().as_secs_f64() * SPEEDS[self.speed]
+        });
+        (self.played + playing).min(self.replay.duration)
+    }
+
+    /// Folds the time played since the last resume into `played`.
+    fn checkpoint(&mut self) {
+        self.played = self.position();
+        if self.resumed.is_some() {
+            self.resumed = Some(Instant::now());
+        }
+    }
+
+    fn session(&self) -> TypingSession {
+        TypingSession::from_replay(&self.replay, self.position())
+    }
+}
+
+/// WPM at each second of the replay: (time_seconds, wpm).
+fn wpm_data(replay: &Replay) -> Vec<(f64, f64)> {
+    let mut data = vec![(0.0, 0.0)];
+    let mut second = 1.0;
+
+    while second < replay.duration + 1.0 {
+        let time = second.min(replay.duration);
+        let stats = TypingSession::from_replay(replay, time).stats(Duration::from_secs_f64(time));
+        data.push((time, stats.wpm()));
+        second += 1.0;
+    }
+
+    data
+}
+
+impl Handler for Playback {
+    fn initialize(&mut self, _config: &Config) -> Result<()> {
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Left => {
+                self.checkpoint();
+                self.speed = self.speed.saturating_sub(1);
+            }
+            KeyCode::Right => {
+                self.checkpoint();
+                self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+            }
+            KeyCode::Char(' ') => {
+                self.checkpoint();
+                self.resumed = match self.resumed {
+                    Some(_) => None,
+                    None => Some(Instant::now()),
+                };
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.played = 0.0;
+        self.resumed = Some(Instant::now());
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.position() >= self.replay.duration
+    }
+}
+
+impl Renderer for Playback {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        format!(
+            "Replay {:.1}s / {:.1}s at {}x{}",
+            self.position(),
+            self.replay.duration,
+            SPEEDS[self.speed],
+            if self.resumed.is_none() {
+                " (paused)"
+            } else {
+                ""
+            }
+        )
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session().styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session()
+            .stats(Duration::from_secs_f64(self.position()))
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.wpm_data.clone()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        vec![format!(
+            "Replay of a {} test on {}",
+            self.replay.mode, self.replay.text
+        )]
+    }
+
+    fn footer_hints(&self) -> Vec<FooterHint> {
+        vec![
+            FooterHint::new("← →", "Speed", vec![State::Running]),
+            FooterHint::new("SPACE", "Pause", vec![State::Running]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Backspace, StopOnError};
+    use crate::replay::{Key, Keystroke};
+
+    fn replay() -> Replay {
+        Replay {
+            mode: "words".to_string(),
+            text: "english".to_string(),
+            target_words: vec!["hi".to_string()],
+            keystrokes: vec![
+                Keystroke {
+                    time: 0.0,
+                    key: Key::Char('h'),
+                },
+                Keystroke {
+                    time: 1.5,
+                    key: Key::Char('i'),
+                },
+            ],
+            duration: 2.0,
+            stop_on_error: StopOnError::Off,
+            backspace: Backspace::Strict,
+        }
+    }
+
+    #[test]
+    fn paused_playback_keeps_its_position() {
+        let mut playback = Playback::new(replay());
+        playback.resumed = None;
+        playback.played = 1.0;
+
+        assert_eq!(playback.position(), 1.0);
+        assert_eq!(playback.session().typed_words, vec!["h".to_string()]);
+        assert!(!playback.is_complete());
+
+        playback.played = 5.0;
+        assert_eq!(playback.position(), 2.0);
+        assert!(playback.is_complete());
+    }
+
+    #[test]
+    fn wpm_data_covers_the_replay() {
+        let data = wpm_data(&replay());
+
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.last().map(|(time, _)| *time), Some(2.0));
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::{Result, anyhow};
+use crossterm::event::KeyEvent;
+use rand::{SeedableRng, seq::IndexedRandom};
+use rand_chacha::ChaCha8Rng;
+use strum::IntoEnumIterator;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer, WordResult,
+            util::{TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::{Config, WordRules},
+    resource::quotes::{self, QuoteEntry, QuoteLength},
+};
+
+pub struct Quote {
+    length: QuoteLength,
+    session: TypingSession,
+    quotes: Vec<QuoteEntry>,
+    current: Option<QuoteEntry>,
+    rng: ChaCha8Rng,
+    rules: WordRules,
+}
+
+impl Quote {
+    pub fn new(length: QuoteLength) -> Self {
+        Self {
+            length,
+            session: TypingSession::new(),
+            quotes: Vec::new(),
+            current: None,
+            rng: ChaCha8Rng::from_os_rng(),
+            rules: WordRules::default(),
+        }
+    }
+
+    /// Loads the quotes matching the selected length and the word rules.
+    fn load_quotes(&mut self) -> Result<()> {
+        self.quotes = quotes::filter(quotes::load()?, self.length, &self.rules);
+        Ok(())
+    }
+
+    fn pick_quote(&mut self) -> Result<()> {
+        let rng = &mut self.rng;
+        let quote = self
+            .quotes
+            .choose(rng)
+            .cloned()
+            .ok_or_else(|| anyhow!("No {} quotes available", self.length))?;
+
+        self.session.set_target(
+            quote
+                .text
+                .split_whitespace()
+                .map(ToString::to_string)
+                .collect(),
+        );
+        self.current = Some(quote);
+        Ok(())
+    }
+}
+
+impl Handler for Quote {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        self.rules = config.words.clone();
+        if let Mode::Quote { length } = &config.mode("quote") {
+            self.length = *length;
+        }
+
+        self.load_quotes()?;
+        self.pick_quote()
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Quote {
+            length: self.length,
+        })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.load_quotes()?;
+        self.pick_quote()
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Quote {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let items = QuoteLength::iter()
+            .enumerate()
+            .map(|(i, length)| OptionItem {
+                label: length.to_string(),
+                is_active: self.length == length,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(length) = QuoteLength::iter().nth(index) {
+            self.length = length;
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        QuoteLength::iter().count()
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!(
+                "{}/{}",
+                self.session.typed_words.len(),
+                self.session.target_words.len()
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        match &self.current {
+            Some(QuoteEntry {
+                author,
+                source: Some(source),
+                ..
+            }) => vec![format!("— {}, {}", author, source)],
+            Some(QuoteEntry { author, .. }) => vec![format!("— {}", author)],
+            None => vec![],
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, OptionGroup, Renderer, WordResult, util::TypingSession,
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+/// Follow-up round made of the words missed in the previous test.
+///
+/// This mode has no [`Mode`](super::Mode) variant, it is only started from
+/// the results screen, see [`App::start_retry`](crate::app::App::start_retry).
+pub struct Retry {
+    words: Vec<String>,
+    session: TypingSession,
+}
+
+impl Retry {
+    pub fn new(words: Vec<String>) -> Self {
+        let mut session = TypingSession::new();
+        session.set_target(words.clone());
+
+        Self { words, session }
+    }
+}
+
+impl Handler for Retry {
+    fn initialize(&mut self, _config: &Config) -> Result<()> {
+        self.session.set_target(self.words.clone());
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.session.set_target(self.words.clone());
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Retry {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        format!(
+            "Retry {}/{}",
+            self.session.typed_words.len(),
+            self.words.len()
+        )
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        let fixed = self
+            .session
+            .word_results()
+            .iter()
+            .filter(|result| result.is_correct())
+            .count();
+
+        vec![format!("Fixed {}/{} missed words", fixed, self.words.len())]
+    }
+}
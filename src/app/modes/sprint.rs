@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer, WordResult,
+            util::{Dictionary, REFILL_AHEAD, TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+const DURATIONS: [u64; 3] = [5, 10, 15];
+
+pub struct Sprint {
+    duration: Duration,
+    session: TypingSession,
+    dictionary: Dictionary,
+    text: String,
+    rng: ChaCha8Rng,
+}
+
+impl Sprint {
+    pub fn new(duration: Duration, text: &str) -> Self {
+        Self {
+            duration,
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        self.session.set_target(Vec::new());
+        self.session
+            .refill(&self.dictionary, REFILL_AHEAD, &mut self.rng);
+    }
+}
+
+impl Handler for Sprint {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Sprint { duration, text } = &config.mode("sprint") {
+            self.duration = Duration::from_secs(*duration);
+            self.text = text.clone();
+        }
+
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Sprint {
+            duration: self.duration.as_secs(),
+            text: self.text.clone(),
+        })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        self.session
+            .refill(&self.dictionary, REFILL_AHEAD, &mut self.rng);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session
+            .start
+            .is_some_and(|s| s.elapsed() >= self.duration)
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Sprint {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let current = self.duration.as_secs();
+
+        let items = DURATIONS
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| OptionItem {
+                label: format!("{}s", d),
+                is_active: current == d,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(&duration) = DURATIONS.get(index) {
+            self.duration = Duration::from_secs(duration);
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        DURATIONS.len()
+    }
+
+    fn get_progress(&self) -> String {
+        match self.session.start {
+            Some(start) => {
+                let remaining = self.duration.saturating_sub(start.elapsed());
+                format!("{:.1}", remaining.as_secs_f64())
+            }
+            None => String::new(),
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.duration)
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+}
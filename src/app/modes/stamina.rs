@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer, WordResult,
+            util::{Dictionary, REFILL_AHEAD, TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+/// Available test lengths, in minutes.
+const DURATIONS: [u64; 4] = [10, 20, 30, 60];
+/// Length of a WPM segment.
+const SEGMENT: Duration = Duration::from_secs(60);
+
+pub struct Stamina {
+    duration: Duration,
+    session: TypingSession,
+    dictionary: Dictionary,
+    text: String,
+    rng: ChaCha8Rng,
+}
+
+impl Stamina {
+    pub fn new(minutes: u64, text: &str) -> Self {
+        Self {
+            duration: Duration::from_secs(minutes.max(1) * 60),
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    /// Net WPM per [`SEGMENT`], up to the end of the test.
+    fn segments(&self) -> Vec<f64> {
+        self.session.segment_wpm(SEGMENT, self.duration)
+    }
+
+    fn generate_words(&mut self) {
+        let words = self.dictionary.pick(100, &mut self.rng);
+        self.session.set_target(words);
+    }
+}
+
+impl Handler for Stamina {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Stamina { duration, text } = &config.mode("stamina") {
+            self.duration = Duration::from_secs((*duration).max(1) * 60);
+            self.text = text.clone();
+        }
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Stamina {
+            duration: self.duration.as_secs() / 60,
+            text: self.text.clone(),
+        })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        self.session
+            .refill(&self.dictionary, REFILL_AHEAD, &mut self.rng);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session
+            .start
+            .map(|s| s.elapsed() >= self.duration)
+            .unwrap_or(false)
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Stamina {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let current = self.duration.as_secs() / 60;
+
+        let items = DURATIONS
+            .iter()
+            .enumerate()
+            .map(|(i, &minutes)| OptionItem {
+                label: format!("{}m", minutes),
+                is_active: current == minutes,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(&minutes) = DURATIONS.get(index) {
+            self.duration = Duration::from_secs(minutes * 60);
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        DURATIONS.len()
+    }
+
+    fn get_progress(&self) -> String {
+        match self.session.start {
+            Some(start) => {
+                let remaining = self.duration.saturating_sub(start.elapsed()).as_secs();
+                format!("{}:{:02}", remaining / 60, remaining % 60)
+            }
+            None => String::new(),
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        let elapsed = self.session.elapsed().min(self.duration);
+        self.session.stats(elapsed).with_segments(self.segments())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        let segments = self.segments();
+        if segments.is_empty() {
+            return vec![];
+        }
+
+        let minutes = segments
+            .iter()
+            .map(|wpm| format!("{:.0}", wpm))
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        vec![format!("WPM per minute: {}", minutes)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn fatigue_compares_halves() {
+        let stats = GameStats::new(0.0, 0.0, 0.0).with_segments(vec![80.0, 70.0, 50.0, 60.0, 50.0]);
+        assert_eq!(stats.fatigue(), Some((75.0, 55.0)));
+
+        let stats = GameStats::new(0.0, 0.0, 0.0).with_segments(vec![80.0]);
+        assert_eq!(stats.fatigue(), None);
+    }
+
+    #[test]
+    fn segments_stop_at_the_test_length() {
+        let mut stamina = Stamina::new(10, "english");
+        let start = Instant::now();
+        stamina.session.set_target(vec!["four".to_string(); 20]);
+        stamina.session.typed_words = vec!["four".to_string(); 11];
+        stamina.session.timestamps = (1..=10)
+            .map(|words| (words, start + Duration::from_secs(60 * words as u64 - 30)))
+            .collect();
+        stamina.session.start = Some(start);
+        // Finished a little late, as checked on the next tick
+        stamina.session.end = Some(start + Duration::from_secs(601));
+
+        assert_eq!(stamina.segments(), vec![1.0; 10]);
+    }
+}
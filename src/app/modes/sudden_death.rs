@@ -0,0 +1,213 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, Mode, OptionGroup, Renderer, WordResult,
+            util::{Dictionary, TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+const WORD_COUNT: usize = 100;
+
+pub struct SuddenDeath {
+    session: TypingSession,
+    dictionary: Dictionary,
+    text: String,
+    rng: ChaCha8Rng,
+}
+
+impl SuddenDeath {
+    pub fn new(text: &str) -> Self {
+        Self {
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        let words = self.dictionary.pick(WORD_COUNT, &mut self.rng);
+        self.session.set_target(words);
+    }
+
+    /// Number of words confirmed without any mistake.
+    ///
+    /// The word being typed is only confirmed by a space, or by finishing the
+    /// text.
+    fn survived_words(&self) -> usize {
+        let typed = self.session.typed_words.len();
+        let confirmed = if self.session.is_finished() {
+            typed.min(self.session.target_words.len())
+        } else {
+            typed.saturating_sub(1)
+        };
+        self.session
+            .first_error()
+            .unwrap_or(confirmed)
+            .min(confirmed)
+    }
+}
+
+impl Handler for SuddenDeath {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::SuddenDeath { text } = &config.mode("sudden-death") {
+            self.text = text.clone();
+        }
+
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.first_error().is_some() || self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for SuddenDeath {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{}/{}", self.survived_words(), WORD_COUNT)
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        let Some(idx) = self.session.first_error() else {
+            return vec![format!("Flawless! All {} words typed", WORD_COUNT)];
+        };
+
+        let typed = self
+            .session
+            .typed_words
+            .get(idx)
+            .cloned()
+            .unwrap_or_default();
+        let target = self
+            .session
+            .target_words
+            .get(idx)
+            .cloned()
+            .unwrap_or_default();
+
+        vec![
+            format!("Survived {}/{} words", self.survived_words(), WORD_COUNT),
+            format!("Died on \"{}\" (typed \"{}\")", target, typed),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn type_str(mode: &mut SuddenDeath, s: &str) {
+        for c in s.chars() {
+            mode.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn ends_on_first_mistake() {
+        let mut mode = SuddenDeath::new("english");
+        mode.session
+            .set_target(vec!["hello".to_string(), "world".to_string()]);
+
+        type_str(&mut mode, "hello wor");
+        assert!(!mode.is_complete());
+        assert_eq!(mode.survived_words(), 1);
+
+        type_str(&mut mode, "k");
+        assert!(mode.is_complete());
+        assert_eq!(mode.survived_words(), 1);
+    }
+
+    #[test]
+    fn skipping_a_word_is_a_mistake() {
+        let mut mode = SuddenDeath::new("english");
+        mode.session
+            .set_target(vec!["hello".to_string(), "world".to_string()]);
+
+        type_str(&mut mode, "hel ");
+        assert!(mode.is_complete());
+        assert_eq!(mode.survived_words(), 0);
+    }
+
+    #[test]
+    fn flawless_run_survives_every_word() {
+        let mut mode = SuddenDeath::new("english");
+        mode.session
+            .set_target(vec!["hello".to_string(), "world".to_string()]);
+
+        type_str(&mut mode, "hello world");
+        assert!(mode.is_complete());
+        assert_eq!(mode.survived_words(), 2);
+    }
+}
@@ -0,0 +1,292 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        State,
+        events::Action,
+        modes::{
+            Direction, FooterHint, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer,
+            WordResult,
+            util::{Dictionary, InputOutcome, REFILL_AHEAD, TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+};
+
+const PACES: [u32; 4] = [40, 60, 80, 100];
+/// Time on the clock when the test starts, in seconds.
+const STARTING_TIME: f64 = 10.0;
+/// Time drained by each incorrect character, in seconds.
+const MISTAKE_PENALTY: f64 = 1.0;
+
+/// Time earned by a correct word at the given pace, in seconds.
+///
+/// Words are weighted by length (5 characters per word, trailing space
+/// included), so typing at exactly `pace` WPM keeps the clock steady.
+pub fn word_bonus(word: &str, pace: u32) -> f64 {
+    let words = (word.chars().count() + 1) as f64 / 5.0;
+    words * 60.0 / f64::from(pace.max(1))
+}
+
+pub struct Survival {
+    pace: u32,
+    session: TypingSession,
+    dictionary: Dictionary,
+    text: String,
+    /// Time earned from correct words, in seconds.
+    bonus: f64,
+    /// Time drained by mistakes, in seconds.
+    penalty: f64,
+    /// Amount of words already rewarded, so retyped words aren't counted
+    /// twice. Reopened words give their bonus back, see
+    /// [`Survival::on_unconfirmed`].
+    rewarded: usize,
+    mistakes: usize,
+    rng: ChaCha8Rng,
+}
+
+impl Survival {
+    pub fn new(pace: u32, text: &str) -> Self {
+        Self {
+            pace,
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
+            text: text.to_owned(),
+            bonus: 0.0,
+            penalty: 0.0,
+            rewarded: 0,
+            mistakes: 0,
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        let words = self.dictionary.pick(100, &mut self.rng);
+        self.session.set_target(words);
+        self.bonus = 0.0;
+        self.penalty = 0.0;
+        self.rewarded = 0;
+        self.mistakes = 0;
+    }
+
+    /// Called every time a word is confirmed.
+    fn on_word(&mut self, result: &WordResult) {
+        let confirmed = self.session.typed_words.len() - 1;
+        if confirmed <= self.rewarded {
+            return;
+        }
+
+        self.rewarded = confirmed;
+        if result.is_correct() {
+            self.bonus += word_bonus(&result.target, self.pace);
+        }
+    }
+
+    /// Called when backspace goes back onto a confirmed word, taking back
+    /// its bonus until it is confirmed again.
+    fn on_unconfirmed(&mut self, result: &WordResult) {
+        let confirmed = self.session.typed_words.len().saturating_sub(1);
+        if confirmed >= self.rewarded {
+            return;
+        }
+
+        self.rewarded = confirmed;
+        if result.is_correct() {
+            self.bonus -= word_bonus(&result.target, self.pace);
+        }
+    }
+
+    /// Time left on the clock, in seconds.
+    fn remaining(&self) -> f64 {
+        STARTING_TIME + self.bonus - self.penalty - self.session.elapsed().as_secs_f64()
+    }
+}
+
+impl Handler for Survival {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Survival { pace, text } = &config.mode("survival") {
+            self.pace = *pace;
+            self.text = text.clone();
+        }
+        self.dictionary = Dictionary::load(&self.text, config)?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Survival {
+            pace: self.pace,
+            text: self.text.clone(),
+        })
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        match self.session.handle_input(key) {
+            InputOutcome::Word(result) => self.on_word(&result),
+            InputOutcome::Unconfirmed(result) => self.on_unconfirmed(&result),
+            InputOutcome::Mistake => {
+                self.penalty += MISTAKE_PENALTY;
+                self.mistakes += 1;
+            }
+            InputOutcome::Correct | InputOutcome::None => {}
+        }
+
+        self.session
+            .refill(&self.dictionary, REFILL_AHEAD, &mut self.rng);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.start.is_some() && self.remaining() <= 0.0
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Survival {
+    fn get_options(&self, focused_index: Option<usize>) -> OptionGroup {
+        let items = PACES
+            .iter()
+            .enumerate()
+            .map(|(i, &pace)| OptionItem {
+                label: format!("{} wpm", pace),
+                is_active: self.pace == pace,
+                is_focused: focused_index == Some(i),
+                is_editing: false,
+            })
+            .collect();
+
+        OptionGroup { items }
+    }
+
+    fn select_option(&mut self, index: usize) {
+        if let Some(&pace) = PACES.get(index) {
+            self.pace = pace;
+        }
+    }
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        PACES.len()
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{:.1}s", self.remaining().max(0.0))
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        vec![
+            format!(
+                "Survived {:.1}s at {} wpm",
+                self.session.elapsed().as_secs_f64(),
+                self.pace
+            ),
+            format!(
+                "Earned {:.1}s, lost {:.1}s to {} mistakes",
+                self.bonus, self.penalty, self.mistakes
+            ),
+        ]
+    }
+
+    fn footer_hints(&self) -> Vec<FooterHint> {
+        vec![
+            FooterHint::new("WORD", "+Time", vec![State::Running]),
+            FooterHint::new("TYPO", "-1s", vec![State::Running]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::*;
+    use crate::config::Backspace;
+
+    #[test]
+    fn bonus_matches_pace() {
+        // "four" plus its space is exactly one word, worth a second at 60 wpm
+        assert!((word_bonus("four", 60) - 1.0).abs() < 1e-9);
+        assert!((word_bonus("four", 120) - 0.5).abs() < 1e-9);
+        assert!((word_bonus("hi", 60) - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reopened_words_give_their_bonus_back() {
+        let mut survival = Survival::new(60, "english");
+        survival
+            .session
+            .set_target(vec!["four".to_string(), "five".to_string()]);
+        survival.session.backspace = Backspace::Free;
+
+        for c in "four ".chars() {
+            survival.handle_input(KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert!((survival.bonus - 1.0).abs() < 1e-9);
+
+        survival.handle_input(KeyEvent::from(KeyCode::Backspace));
+        assert!(survival.bonus.abs() < 1e-9);
+
+        survival.handle_input(KeyEvent::from(KeyCode::Char(' ')));
+        assert!((survival.bonus - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn backspace_on_the_first_word_keeps_the_bonus() {
+        let mut survival = Survival::new(60, "english");
+        survival.session.set_target(vec!["four".to_string()]);
+
+        survival.handle_input(KeyEvent::from(KeyCode::Char('f')));
+        for _ in 0..2 {
+            survival.handle_input(KeyEvent::from(KeyCode::Backspace));
+        }
+        assert!(survival.session.typed_words.is_empty());
+        assert_eq!(survival.bonus, 0.0);
+    }
+}
@@ -0,0 +1,204 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::{Rng, SeedableRng, seq::IndexedRandom};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, OptionGroup, Renderer, WordResult,
+            util::{TypingSession, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::Config,
+    resource::store::TextStore,
+};
+
+const GROUP_COUNT: usize = 50;
+/// Maximum amount of tokens joined in a single group.
+const MAX_TOKENS: usize = 3;
+
+/// A symbol token and its relative frequency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolToken {
+    pub token: String,
+    pub weight: u32,
+}
+
+impl SymbolToken {
+    /// Parses a frequency table, one `token<TAB>weight` pair per line.
+    ///
+    /// Lines with a missing or invalid weight are skipped.
+    pub fn parse_all(content: &str) -> Vec<Self> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let (token, weight) = line.split_once('\t')?;
+                let token = token.trim();
+                let weight = weight.trim().parse().ok()?;
+
+                (!token.is_empty()).then(|| Self {
+                    token: token.to_string(),
+                    weight,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Builds `count` groups of 1 to [`MAX_TOKENS`] tokens, picked according to their weight.
+pub fn generate_groups<R: Rng + ?Sized>(
+    table: &[SymbolToken],
+    count: usize,
+    rng: &mut R,
+) -> Vec<String> {
+    (0..count)
+        .map(|_| {
+            (0..rng.random_range(1..=MAX_TOKENS))
+                .filter_map(|_| table.choose_weighted(rng, |t| t.weight).ok())
+                .map(|t| t.token.as_str())
+                .collect()
+        })
+        .filter(|group: &String| !group.is_empty())
+        .collect()
+}
+
+pub struct Symbols {
+    session: TypingSession,
+    table: Vec<SymbolToken>,
+    rng: ChaCha8Rng,
+}
+
+impl Symbols {
+    pub fn new() -> Self {
+        Self {
+            session: TypingSession::new(),
+            table: Vec::new(),
+            rng: ChaCha8Rng::from_os_rng(),
+        }
+    }
+
+    fn generate_words(&mut self) {
+        let rng = &mut self.rng;
+        let words = generate_groups(&self.table, GROUP_COUNT, rng);
+        self.session.set_target(words);
+    }
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Handler for Symbols {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        let content = TextStore::text("symbols")?;
+
+        self.table = SymbolToken::parse_all(&content);
+        if self.table.is_empty() {
+            anyhow::bail!("No symbols found");
+        }
+
+        self.generate_words();
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for Symbols {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{}/{}", self.session.typed_words.len(), GROUP_COUNT)
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_generate() {
+        let table = SymbolToken::parse_all("()\t10\n{}\tnope\n->\t5\n\n");
+        assert_eq!(
+            table,
+            vec![
+                SymbolToken {
+                    token: "()".to_string(),
+                    weight: 10
+                },
+                SymbolToken {
+                    token: "->".to_string(),
+                    weight: 5
+                },
+            ]
+        );
+
+        let groups = generate_groups(&table, 20, &mut rand::rng());
+        assert_eq!(groups.len(), 20);
+        assert!(groups.iter().all(|g| g.chars().all(|c| "()->".contains(c))));
+    }
+}
@@ -2,26 +2,1161 @@
 //!
 //! This module provides shared helper functions used by various game modes.
 
-use crate::app::ui::char::{CharState, StyledChar};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rand::{
+    Rng, SeedableRng,
+    seq::{IndexedRandom, SliceRandom},
+};
+use rand_chacha::ChaCha8Rng;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    app::{
+        modes::{GameStats, KeyResult, WordResult, WordTime},
+        ui::char::{CharState, StyledChar},
+    },
+    config::{Backspace, Behavior, Config, Generator, StopOnError, WordFilter, WordRules},
+    markov::MarkovChain,
+    replay::{Key, Keystroke, Replay},
+    resource::store::TextStore,
+};
+
+/// Grapheme clusters of `text`: the characters as shown, an accented letter
+/// or an emoji being a single one even when made of several `char`s.
+pub fn graphemes(text: &str) -> Vec<&str> {
+    text.graphemes(true).collect()
+}
+
+/// Amount of [`graphemes`] of `text`.
+pub fn grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// `text` in composed form (NFC), so a letter typed with a separate accent
+/// compares equal to the same accented letter of the target.
+pub fn normalize(text: &str) -> String {
+    text.nfc().collect()
+}
+
+/// `text` in decomposed form (NFD), where the start of a grapheme (e.g. a
+/// letter without its accent yet) is a prefix of the whole grapheme.
+fn decompose(text: &str) -> String {
+    text.nfd().collect()
+}
+
+/// Loads a text by name and splits it into its lines (one word per line),
+/// keeping the words accepted by `rules`.
+///
+/// Monkeytype language files are read as their list of words. Texts are only
+/// read and parsed once, see [`TextStore`]. Fails when no word of the text
+/// passes the rules.
+pub fn load_dictionary(name: &str, rules: &WordRules) -> Result<Vec<String>> {
+    let words: Vec<String> = TextStore::words(name)?
+        .iter()
+        .filter(|word| rules.accepts(word))
+        .cloned()
+        .collect();
+
+    if words.is_empty() {
+        bail!("No word of the \"{}\" text matches the word rules", name);
+    }
+    Ok(words)
+}
+
+/// Loads a text like [`load_dictionary`], also keeping only the words
+/// accepted by `filter`.
+///
+/// Fails when no word of the text passes them.
+pub fn load_words(name: &str, filter: &WordFilter, rules: &WordRules) -> Result<Vec<String>> {
+    let words: Vec<String> = load_dictionary(name, rules)?
+        .into_iter()
+        .filter(|word| filter.accepts(word))
+        .collect();
+
+    if words.is_empty() {
+        bail!(
+            "No word of the \"{}\" text matches the word filter and word rules",
+            name
+        );
+    }
+    Ok(words)
+}
+
+/// Where the words of a [`Dictionary`] come from, see [`Generator`].
+#[derive(Debug)]
+enum Source {
+    /// A word list, shuffled.
+    Words(Vec<String>),
+    /// A Markov chain built from the `corpus/` text of the same name.
+    Markov(MarkovChain),
+}
+
+/// The words of word list modes, drawn from a text with the options of the
+/// config.
+///
+/// The text is remembered, so [`Dictionary::reload`] can pick up edits made
+/// while the app is running.
+#[derive(Debug)]
+pub struct Dictionary {
+    name: String,
+    generator: Generator,
+    filter: WordFilter,
+    rules: WordRules,
+    /// The text the words were taken from, to notice when it changes.
+    text: Option<Arc<str>>,
+    source: Source,
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self::from_words(Vec::new())
+    }
+}
+
+impl Dictionary {
+    /// Loads the text `name` with the generator of `config`.
+    ///
+    /// Word lists are restricted by the word filter and word rules, corpora
+    /// are used as is.
+    pub fn load(name: &str, config: &Config) -> Result<Self> {
+        let mut dictionary = Self {
+            name: name.to_string(),
+            generator: config.defaults.generator,
+            filter: config.filter.clone(),
+            rules: config.words.clone(),
+            text: None,
+            source: Source::Words(Vec::new()),
+        };
+        dictionary.reload()?;
+        Ok(dictionary)
+    }
+
+    /// A dictionary of `words`, not tied to a text.
+    pub fn from_words(words: Vec<String>) -> Self {
+        Self {
+            name: String::new(),
+            generator: Generator::Shuffle,
+            filter: WordFilter::default(),
+            rules: WordRules::default(),
+            text: None,
+            source: Source::Words(words),
+        }
+    }
+
+    /// Rebuilds the words if their text changed since they were loaded, e.g.
+    /// a user text edited between two tests.
+    ///
+    /// Texts are cached by [`TextStore`], so this is cheap when nothing changed.
+    pub fn reload(&mut self) -> Result<()> {
+        if self.name.is_empty() {
+            return Ok(());
+        }
+
+        let text = match self.generator {
+            Generator::Shuffle => TextStore::text(&self.name)?,
+            Generator::Markov => TextStore::text(&format!("corpus/{}", self.name))
+                .context(format!("Couldn't find a corpus for \"{}\" text", self.name))?,
+        };
+        if self
+            .text
+            .as_ref()
+            .is_some_and(|loaded| Arc::ptr_eq(loaded, &text))
+        {
+            return Ok(());
+        }
+
+        self.source = match self.generator {
+            Generator::Shuffle => Source::Words(load_words(&self.name, &self.filter, &self.rules)?),
+            Generator::Markov => {
+                let chain = MarkovChain::from_corpus(&text);
+                if chain.is_empty() {
+                    bail!("The \"{}\" corpus doesn't contain any word", self.name);
+                }
+                Source::Markov(chain)
+            }
+        };
+        self.text = Some(text);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match &self.source {
+            Source::Words(words) => words.is_empty(),
+            Source::Markov(chain) => chain.is_empty(),
+        }
+    }
+
+    /// Picks `count` words, with [`pick_words`] or by generating sentences.
+    pub fn pick<R: Rng + ?Sized>(&self, count: usize, rng: &mut R) -> Vec<String> {
+        match &self.source {
+            Source::Words(words) => pick_words(words, count, rng),
+            Source::Markov(chain) => chain.generate(count, rng),
+        }
+    }
+}
+
+/// The random number generator used to pick words, seeded with `seed` so the
+/// same words come up on every run and platform, or from the OS without one.
+pub fn word_rng(seed: Option<u64>) -> ChaCha8Rng {
+    match seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_os_rng(),
+    }
+}
+
+/// Picks `count` words from `dictionary` in random order.
+///
+/// The dictionary is shuffled and cycled through, so every word is used once
+/// before any is repeated. Passing a seeded `rng` makes the result reproducible.
+pub fn pick_words<R: Rng + ?Sized>(
+    dictionary: &[String],
+    count: usize,
+    rng: &mut R,
+) -> Vec<String> {
+    let mut words = dictionary.to_vec();
+    words.shuffle(rng);
+    words.into_iter().cycle().take(count).collect()
+}
+
+/// Turns plain words into sentences: capitalized starts, sentence-ending
+/// marks, commas and the occasional quoted word.
+///
+/// The last word always ends its sentence.
+pub fn punctuate<R: Rng + ?Sized>(words: Vec<String>, rng: &mut R) -> Vec<String> {
+    let count = words.len();
+    let mut sentence_start = true;
+
+    words
+        .into_iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let mut word = if sentence_start {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().collect::<String>() + chars.as_str())
+                    .unwrap_or_default()
+            } else {
+                word
+            };
+            sentence_start = false;
+
+            if i + 1 == count || rng.random_bool(0.12) {
+                word.push(*['.', '.', '.', '!', '?'].choose(rng).unwrap_or(&'.'));
+                sentence_start = true;
+            } else if rng.random_bool(0.1) {
+                word.push(',');
+            } else if rng.random_bool(0.03) {
+                word = format!("\"{}\"", word);
+            }
+
+            word
+        })
+        .collect()
+}
+
+/// The character produced by a key press, if any.
+///
+/// Some terminals report shifted letters as the lowercase letter with the
+/// SHIFT modifier, those are turned into the uppercase letter.
+pub fn typed_char(key: &KeyEvent) -> Option<char> {
+    match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::SHIFT) && c.is_lowercase() => {
+            c.to_uppercase().next()
+        }
+        KeyCode::Char(c) => Some(c),
+        _ => None,
+    }
+}
+
+/// The effect of a key press on a [`TypingSession`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputOutcome {
+    /// Nothing was typed (backspace, ignored keys...).
+    None,
+    /// A character matching the target was typed.
+    Correct,
+    /// A character not matching the target was typed.
+    Mistake,
+    /// A word was confirmed with space.
+    Word(WordResult),
+    /// Backspace went back onto a confirmed word, as it was typed. It is
+    /// confirmed again with space.
+    Unconfirmed(WordResult),
+}
+
+/// Amount of pending words [`TypingSession::refill`] keeps ahead of the cursor.
+pub const REFILL_AHEAD: usize = 50;
+
+/// Typing state shared by the word-based game modes.
+///
+/// Holds the target text, what the user typed so far and the timing data
+/// needed for statistics. Modes own one of these and decide when the test is
+/// over, the session takes care of input handling and bookkeeping.
+#[derive(Default)]
+pub struct TypingSession {
+    pub target_words: Vec<String>,
+    pub typed_words: Vec<String>,
+    /// (amount of typed words, time) pairs recorded each time a word is confirmed.
+    pub timestamps: Vec<(usize, Instant)>,
+    /// Every keystroke typed since the start of the session.
+    pub keystrokes: Vec<Keystroke>,
+    /// Incorrect characters erased with backspace or Ctrl+H.
+    pub corrected: usize,
+    pub start: Option<Instant>,
+    pub end: Option<Instant>,
+    /// Whether mistakes prevent moving on, see [`TypingSession::blocks`].
+    pub stop_on_error: StopOnError,
+    /// Whether backspace can go back into correct words, see
+    /// [`TypingSession::reopen_word`].
+    pub backspace: Backspace,
+}
+
+impl TypingSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the target words and clears any typing progress.
+    pub fn set_target(&mut self, words: Vec<String>) {
+        self.target_words = words.iter().map(|word| normalize(word)).collect();
+        self.clear();
+    }
+
+    /// Appends words from `dictionary` whenever fewer than `ahead` target
+    /// words remain to be typed, so the text never runs out.
+    ///
+    /// Word lists are appended whole, shuffled, so no word repeats before
+    /// every other one was used.
+    pub fn refill<R: Rng + ?Sized>(&mut self, dictionary: &Dictionary, ahead: usize, rng: &mut R) {
+        if dictionary.is_empty() {
+            return;
+        }
+
+        let batch = match &dictionary.source {
+            Source::Words(words) => words.len(),
+            Source::Markov(_) => ahead.max(1),
+        };
+        while self.target_words.len() < self.typed_words.len() + ahead {
+            self.target_words.extend(
+                dictionary
+                    .pick(batch, rng)
+                    .iter()
+                    .map(|word| normalize(word)),
+            );
+        }
+    }
+
+    /// Clears typing progress and timing data, keeping the target words.
+    pub fn clear(&mut self) {
+        self.typed_words.clear();
+        self.timestamps.clear();
+        self.keystrokes.clear();
+        self.corrected = 0;
+        self.start = None;
+        self.end = None;
+    }
+
+    /// Handles typing, word confirmation (space), backspace and word clearing (Ctrl+H).
+    ///
+    /// Keystrokes are recorded in [`TypingSession::keystrokes`]. The returned
+    /// [`InputOutcome`] lets modes react to each typed character or confirmed word.
+    pub fn handle_input(&mut self, key: KeyEvent) -> InputOutcome {
+        let key = match key.code {
+            KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::CONTROL) => Key::ClearWord,
+            KeyCode::Char(_) => match typed_char(&key) {
+                Some(c) => Key::Char(c),
+                None => return InputOutcome::None,
+            },
+            KeyCode::Backspace => Key::Backspace,
+            _ => return InputOutcome::None,
+        };
+
+        if self.start.is_none() && key != Key::Backspace {
+            self.start = Some(Instant::now());
+        }
+
+        if let Some(start) = self.start {
+            self.keystrokes.push(Keystroke {
+                time: start.elapsed().as_secs_f64(),
+                key,
+            });
+        }
+
+        if self.blocks(key) {
+            return InputOutcome::Mistake;
+        }
+
+        let correct = matches!(key, Key::Char(c) if self.is_expected(c));
+        let word_count = self.typed_words.len();
+        self.apply(key);
+
+        match key {
+            Key::Char(' ') if self.typed_words.len() > word_count => {
+                let index = word_count - 1;
+                InputOutcome::Word(WordResult {
+                    target: self.target_words.get(index).cloned().unwrap_or_default(),
+                    typed: self.typed_words[index].clone(),
+                })
+            }
+            Key::Char(' ') => InputOutcome::None,
+            // Only a previous word can be reopened, erasing the first one
+            // just clears it
+            Key::Backspace | Key::ClearWord
+                if word_count >= 2 && self.typed_words.len() < word_count =>
+            {
+                let index = self.typed_words.len() - 1;
+                InputOutcome::Unconfirmed(WordResult {
+                    target: self.target_words.get(index).cloned().unwrap_or_default(),
+                    typed: self.typed_words.get(index).cloned().unwrap_or_default(),
+                })
+            }
+            Key::Char(_) if correct => InputOutcome::Correct,
+            Key::Char(_) => InputOutcome::Mistake,
+            _ => InputOutcome::None,
+        }
+    }
+
+    /// Applies the `[behavior]` settings of the config.
+    pub fn set_behavior(&mut self, behavior: &Behavior) {
+        self.stop_on_error = behavior.stop_on_error;
+        self.backspace = behavior.backspace;
+    }
+
+    /// An empty session with the target and behavior of this one, to replay
+    /// its keystrokes.
+    fn replayer(&self) -> Self {
+        Self {
+            target_words: self.target_words.clone(),
+            stop_on_error: self.stop_on_error,
+            backspace: self.backspace,
+            ..Self::default()
+        }
+    }
+
+    /// Whether `key` is ignored because of [`TypingSession::stop_on_error`]:
+    /// a wrong character (or a space before the end of the word) when stopping
+    /// on letters, a space after a wrong word when stopping on words.
+    pub fn blocks(&self, key: Key) -> bool {
+        let Key::Char(c) = key else {
+            return false;
+        };
+
+        match self.stop_on_error {
+            StopOnError::Off => false,
+            StopOnError::Word => {
+                let word = self.typed_words.len().saturating_sub(1);
+                c == ' '
+                    && self
+                        .typed_words
+                        .last()
+                        .is_some_and(|typed| Some(typed) != self.target_words.get(word))
+            }
+            // Past the end of the word, only a space moves on
+            StopOnError::Letter if self.expected().is_none() => c != ' ',
+            StopOnError::Letter => !self.is_expected(c),
+        }
+    }
+
+    /// Applies a key to the typed words, without recording it.
+    ///
+    /// Keys blocked by [`TypingSession::stop_on_error`] are ignored.
+    pub fn apply(&mut self, key: Key) {
+        if self.blocks(key) {
+            return;
+        }
+
+        match key {
+            Key::ClearWord => {
+                // Clear current word
+                let free = self.backspace == Backspace::Free;
+                if let Some((typed_idx, typed_word)) =
+                    self.typed_words.iter_mut().enumerate().last()
+                    && let Some(target_word) = self.target_words.get(typed_idx)
+                    && (free || typed_word != target_word)
+                {
+                    if typed_word.is_empty() {
+                        self.reopen_word();
+                    } else {
+                        let target = graphemes(target_word);
+                        self.corrected += graphemes(typed_word)
+                            .iter()
+                            .enumerate()
+                            .filter(|&(i, g)| target.get(i) != Some(g))
+                            .count();
+                        typed_word.clear();
+                    }
+                }
+            }
+            Key::Char(' ') => {
+                // Move to next word
+                if let Some(last) = self.typed_words.last()
+                    && !last.is_empty()
+                {
+                    self.timestamps
+                        .push((self.typed_words.len(), Instant::now()));
+                    self.typed_words.push(String::new());
+                }
+            }
+            Key::Char(c) => {
+                if self.typed_words.is_empty() {
+                    self.typed_words.push(String::new());
+                }
+                if let Some(word) = self.typed_words.last_mut() {
+                    word.push(c);
+                    // An accent typed after its letter joins it
+                    if !c.is_ascii() {
+                        *word = normalize(word);
+                    }
+                }
+            }
+            Key::Backspace => {
+                let free = self.backspace == Backspace::Free;
+                if let Some((typed_idx, typed_word)) =
+                    self.typed_words.iter_mut().enumerate().last()
+                    && let Some(target_word) = self.target_words.get(typed_idx)
+                    && (free || typed_word != target_word)
+                {
+                    // The whole character is erased, accents included
+                    match typed_word.grapheme_indices(true).next_back() {
+                        Some((start, grapheme)) => {
+                            let index = grapheme_count(&typed_word[..start]);
+                            if graphemes(target_word).get(index) != Some(&grapheme) {
+                                self.corrected += 1;
+                            }
+                            typed_word.truncate(start);
+                        }
+                        None => self.reopen_word(),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves from the start of the current (empty) word back to the end of
+    /// the previous one, which is no longer confirmed. Correct words can only
+    /// be reopened with [`Backspace::Free`].
+    fn reopen_word(&mut self) {
+        let Some(previous) = self.typed_words.len().checked_sub(2) else {
+            self.typed_words.clear();
+            return;
+        };
+        let correct = self.typed_words.get(previous) == self.target_words.get(previous);
+        if correct && self.backspace != Backspace::Free {
+            return;
+        }
+
+        self.typed_words.pop();
+        // The word's confirmation is recorded again when it is
+        self.timestamps.retain(|&(words, _)| words <= previous);
+    }
+
+    /// Position of the cursor as (word index, character index), characters
+    /// being [`graphemes`].
+    pub fn cursor(&self) -> (usize, usize) {
+        match self.typed_words.last() {
+            Some(word) => (self.typed_words.len() - 1, grapheme_count(word)),
+            None => (0, 0),
+        }
+    }
+
+    /// Replays the keystrokes typed within the first `seconds` of `replay`
+    /// against its target words.
+    pub fn from_replay(replay: &Replay, seconds: f64) -> Self {
+        let mut session = Self {
+            target_words: replay.target_words.iter().map(|w| normalize(w)).collect(),
+            stop_on_error: replay.stop_on_error,
+            backspace: replay.backspace,
+            ..Self::default()
+        };
+
+        for keystroke in replay.keystrokes_until(seconds) {
+            session.apply(keystroke.key);
+        }
+
+        session
+    }
+
+    /// Records the session as a replay of the given mode and text.
+    pub fn to_replay(&self, mode: &str, text: &str) -> Replay {
+        Replay {
+            mode: mode.to_string(),
+            text: text.to_string(),
+            target_words: self.target_words.clone(),
+            keystrokes: self.keystrokes.clone(),
+            duration: self.elapsed().as_secs_f64(),
+            stop_on_error: self.stop_on_error,
+            backspace: self.backspace,
+        }
+    }
+
+    /// Returns true once every target word has been typed.
+    pub fn is_finished(&self) -> bool {
+        self.typed_words.len() == self.target_words.len()
+            && self.typed_words.last().is_some_and(|w| {
+                grapheme_count(w) == self.target_words.last().map_or(5, |w| grapheme_count(w))
+            })
+            || self.typed_words.len() > self.target_words.len()
+    }
+
+    /// Index of the first word containing a mistake, if any.
+    ///
+    /// Confirmed words must match their target exactly, the word being typed
+    /// only needs to be a prefix of its target.
+    pub fn first_error(&self) -> Option<usize> {
+        let current = self.typed_words.len().checked_sub(1)?;
+
+        self.typed_words.iter().enumerate().position(|(i, typed)| {
+            let target = self.target_words.get(i).map(String::as_str).unwrap_or("");
+            if i < current {
+                typed != target
+            } else {
+                // A letter still missing its accent is fine
+                !decompose(target).starts_with(&decompose(typed))
+            }
+        })
+    }
+
+    /// Target/typed pairs of the words typed so far.
+    ///
+    /// The word being typed is only included once it is as long as its
+    /// target, so a test ending mid-word doesn't count it as a mistake.
+    pub fn word_results(&self) -> Vec<WordResult> {
+        let current = self.typed_words.len().saturating_sub(1);
+
+        self.typed_words
+            .iter()
+            .zip(&self.target_words)
+            .enumerate()
+            .filter(|(i, (typed, target))| {
+                *i < current || grapheme_count(typed) >= grapheme_count(target)
+            })
+            .map(|(_, (typed, target))| WordResult {
+                target: target.clone(),
+                typed: typed.clone(),
+            })
+            .collect()
+    }
+
+    /// Characters typed against the target, replayed from the keystrokes.
+    ///
+    /// Spaces and characters typed past the end of a word aren't tied to a
+    /// key of the target and are left out, but still reset the latency.
+    pub fn key_results(&self) -> Vec<KeyResult> {
+        let mut replay = self.replayer();
+        let mut previous: Option<f64> = None;
+        let mut results = Vec::new();
+
+        for keystroke in &self.keystrokes {
+            if let Key::Char(c) = keystroke.key
+                && c != ' '
+                && let Some(expected) = replay.expected()
+                && let Some(key) = normalize(&expected).chars().next()
+            {
+                results.push(KeyResult {
+                    key,
+                    correct: replay.is_expected(c),
+                    latency: previous.map(|time| keystroke.time - time),
+                });
+            }
+
+            replay.apply(keystroke.key);
+            previous = Some(keystroke.time);
+        }
+
+        results
+    }
+
+    /// Seconds into the test at which incorrect characters were typed,
+    /// replayed from the keystrokes.
+    ///
+    /// Characters typed past the end of a word count as errors.
+    pub fn error_times(&self) -> Vec<f64> {
+        let mut replay = self.replayer();
+        let mut times = Vec::new();
+
+        for keystroke in &self.keystrokes {
+            if let Key::Char(c) = keystroke.key
+                && c != ' '
+                && !replay.is_expected(c)
+            {
+                times.push(keystroke.time);
+            }
+            replay.apply(keystroke.key);
+        }
+
+        times
+    }
+
+    /// What is left to type of the target grapheme at the cursor, decomposed
+    /// (see [`decompose`]): the rest of a grapheme only started (e.g. the
+    /// accent of a letter typed without it), or the next grapheme. `None` past
+    /// the end of the word being typed.
+    fn expected(&self) -> Option<String> {
+        let word = self.typed_words.len().saturating_sub(1);
+        let target = graphemes(self.target_words.get(word)?);
+        let typed = self
+            .typed_words
+            .last()
+            .map(|typed| graphemes(typed))
+            .unwrap_or_default();
+
+        if let Some(last) = typed.last()
+            && let Some(current) = target.get(typed.len() - 1)
+        {
+            let (last, current) = (decompose(last), decompose(current));
+            if current.len() > last.len() && current.starts_with(&last) {
+                return Some(current[last.len()..].to_string());
+            }
+        }
+        target.get(typed.len()).map(|next| decompose(next))
+    }
+
+    /// Whether `c` is (or starts) what is [expected](Self::expected) at the
+    /// cursor.
+    fn is_expected(&self, c: char) -> bool {
+        self.expected()
+            .is_some_and(|expected| expected.starts_with(&decompose(c.encode_utf8(&mut [0; 4]))))
+    }
+
+    /// Marks the session as finished, if it wasn't already.
+    pub fn finish(&mut self) {
+        if self.end.is_none() {
+            self.end = Some(Instant::now());
+        }
+    }
+
+    /// Time spent typing, up to [`TypingSession::finish`] if it was called.
+    pub fn elapsed(&self) -> Duration {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => end.duration_since(start),
+            (Some(start), None) => start.elapsed(),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Calculates the statistics of the session over the given duration.
+    pub fn stats(&self, duration: Duration) -> GameStats {
+        GameStats::calculate(duration, &self.typed_words, &self.target_words)
+            .with_peak_burst(self.peak_burst())
+            .with_corrected_errors(self.corrected)
+    }
+
+    /// Speed so far, in WPM, counting the confirmed words only: the word being
+    /// typed doesn't move the figure until it is confirmed with a space.
+    pub fn live_wpm(&self) -> f64 {
+        let confirmed = self
+            .typed_words
+            .len()
+            .saturating_sub(1)
+            .min(self.target_words.len());
+        GameStats::calculate(
+            self.elapsed(),
+            &self.typed_words[..confirmed],
+            &self.target_words[..confirmed],
+        )
+        .wpm()
+    }
+
+    /// Fastest speed reached on a single correctly typed word, in WPM.
+    pub fn peak_burst(&self) -> f64 {
+        self.word_times()
+            .iter()
+            .map(|time| time.wpm)
+            .fold(0.0, f64::max)
+    }
+
+    /// Time taken on each correctly typed and confirmed word, in typing order.
+    ///
+    /// Each word's time runs from the previous word's confirmation (or the
+    /// start of the test) to its own.
+    pub fn word_times(&self) -> Vec<WordTime> {
+        let Some(start) = self.start else {
+            return Vec::new();
+        };
+
+        let mut previous = start;
+        let mut times = Vec::new();
+
+        for &(words, ts) in &self.timestamps {
+            let idx = words.saturating_sub(1);
+            let seconds = ts.duration_since(previous).as_secs_f64();
+            previous = ts;
+
+            if let (Some(typed), Some(target)) =
+                (self.typed_words.get(idx), self.target_words.get(idx))
+                && typed == target
+                && seconds > 0.0
+            {
+                times.push(WordTime {
+                    word: typed.clone(),
+                    seconds,
+                    wpm: ((grapheme_count(typed) + 1) as f64 / 5.0) / (seconds / 60.0),
+                });
+            }
+        }
+
+        times
+    }
+
+    /// Net WPM over each consecutive `segment` of the first `duration` of the
+    /// test, the last one possibly shorter. See [`TypingSession::split_wpm`].
+    pub fn segment_wpm(&self, segment: Duration, duration: Duration) -> Vec<f64> {
+        let elapsed = self.elapsed().min(duration);
+        if segment.is_zero() || elapsed.is_zero() {
+            return Vec::new();
+        }
+
+        let count = elapsed.as_secs_f64() / segment.as_secs_f64();
+        let ends: Vec<Duration> = (1..=count.ceil() as u32)
+            .map(|i| (segment * i).min(elapsed))
+            .collect();
+
+        self.split_wpm(&ends)
+    }
+
+    /// Net WPM over consecutive segments, each ending at the matching time of
+    /// `ends` (in increasing order, relative to the start of the test).
+    ///
+    /// A segment's speed counts the correct words (and their space) confirmed
+    /// within it, words confirmed after the last end are left out.
+    pub fn split_wpm(&self, ends: &[Duration]) -> Vec<f64> {
+        let Some(start) = self.start else {
+            return Vec::new();
+        };
+
+        let mut chars = vec![0usize; ends.len()];
+        let mut previous = 0;
+
+        for &(words, ts) in &self.timestamps {
+            let time = ts.duration_since(start);
+            let Some(index) = ends.iter().position(|&end| time < end) else {
+                break;
+            };
+
+            for i in previous..words {
+                if let (Some(typed), Some(target)) =
+                    (self.typed_words.get(i), self.target_words.get(i))
+                    && typed == target
+                {
+                    chars[index] += grapheme_count(typed) + 1;
+                }
+            }
+            previous = previous.max(words);
+        }
+
+        let mut segment_start = Duration::ZERO;
+        chars
+            .iter()
+            .zip(ends)
+            .map(|(&chars, &end)| {
+                let minutes = end.saturating_sub(segment_start).as_secs_f64() / 60.0;
+                segment_start = end;
+                if minutes > 0.0 {
+                    (chars as f64 / 5.0) / minutes
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    /// WPM data points for the chart, one per confirmed word: (time_seconds, wpm).
+    pub fn wpm_data(&self) -> Vec<(f64, f64)> {
+        let mut data = vec![(0.0, 0.0)];
+
+        if let Some(start) = &self.start {
+            for (words, ts) in &self.timestamps {
+                let duration = ts.duration_since(*start);
+                let typed_words = &self.typed_words[..*words];
+                let target_words = &self.target_words[..*words];
+                let stats = GameStats::calculate(duration, typed_words, target_words);
+                data.push((duration.as_secs_f64(), stats.wpm()));
+            }
+        }
+
+        data
+    }
+
+    /// Raw WPM over each second of the test: (time_seconds, wpm).
+    ///
+    /// Counts every typed character and space, correct or not. The last
+    /// second may be shorter.
+    pub fn raw_wpm_data(&self) -> Vec<(f64, f64)> {
+        let elapsed = self.elapsed().as_secs_f64();
+        let mut chars = vec![0usize; elapsed.ceil() as usize];
+
+        for keystroke in &self.keystrokes {
+            if let Key::Char(_) = keystroke.key
+                && let Some(count) = chars.get_mut(keystroke.time as usize)
+            {
+                *count += 1;
+            }
+        }
+
+        chars
+            .iter()
+            .enumerate()
+            .map(|(second, &chars)| {
+                let end = ((second + 1) as f64).min(elapsed);
+                let seconds = end - second as f64;
+                (end, (chars as f64 / 5.0) / (seconds / 60.0))
+            })
+            .collect()
+    }
+
+    /// Characters to display, see [`build_styled_chars`].
+    pub fn styled_chars(&self) -> Vec<StyledChar> {
+        build_styled_chars(&self.target_words, &self.typed_words, None)
+    }
+}
+
+/// Typing state for modes whose target is a verbatim text, newlines and
+/// indentation included, compared character by character (see [`graphemes`]).
+#[derive(Default)]
+pub struct TextSession {
+    pub target: Vec<String>,
+    pub typed: Vec<String>,
+    /// (amount of typed characters, time) pairs recorded at each word boundary.
+    pub timestamps: Vec<(usize, Instant)>,
+    /// Incorrect characters erased with backspace.
+    pub corrected: usize,
+    pub start: Option<Instant>,
+    pub end: Option<Instant>,
+    /// See [`TypingSession::stop_on_error`].
+    pub stop_on_error: StopOnError,
+    /// See [`TypingSession::backspace`].
+    pub backspace: Backspace,
+}
+
+impl TextSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the `[behavior]` settings of the config.
+    pub fn set_behavior(&mut self, behavior: &Behavior) {
+        self.stop_on_error = behavior.stop_on_error;
+        self.backspace = behavior.backspace;
+    }
+
+    /// Index of the first character of the word containing the character at
+    /// `index`, right after the whitespace before it.
+    fn word_start(&self, index: usize) -> usize {
+        self.typed[..index]
+            .iter()
+            .rposition(|c| c.trim().is_empty())
+            .map_or(0, |i| i + 1)
+    }
+
+    /// Whether the typed character at `index` is the whitespace after a
+    /// correct word, which [`Backspace::Strict`] doesn't erase.
+    fn ends_correct_word(&self, index: usize) -> bool {
+        let start = self.word_start(index);
+        self.typed[index].trim().is_empty()
+            && self.typed[start..=index] == self.target[start..=index]
+    }
+
+    /// Whether `c` is ignored because of [`TextSession::stop_on_error`]: a
+    /// wrong character when stopping on letters, whitespace after a wrong
+    /// word when stopping on words.
+    fn blocks(&self, c: char) -> bool {
+        let index = self.typed.len();
+        let expected = self.target.get(index);
+
+        match self.stop_on_error {
+            StopOnError::Off => false,
+            StopOnError::Word => {
+                let start = self.word_start(index);
+                c.is_whitespace()
+                    && (self.typed[start..] != self.target[start..index]
+                        || expected.is_none_or(|e| !e.trim().is_empty()))
+            }
+            StopOnError::Letter => expected.is_none_or(|e| *e != c.to_string()),
+        }
+    }
+
+    /// Replaces the target text and clears any typing progress.
+    pub fn set_target(&mut self, text: &str) {
+        let text = normalize(&text.replace("\r\n", "\n"));
+        self.target = graphemes(&text).into_iter().map(String::from).collect();
+        self.clear();
+    }
+
+    /// Clears typing progress and timing data, keeping the target text.
+    pub fn clear(&mut self) {
+        self.typed.clear();
+        self.timestamps.clear();
+        self.corrected = 0;
+        self.start = None;
+        self.end = None;
+    }
+
+    /// Handles typing, ENTER (newline), TAB (indentation) and backspace.
+    ///
+    /// TAB types a tab character if one is expected, otherwise it fills the
+    /// expected run of spaces up to the next 4-column tab stop. Keys are
+    /// ignored as set by [`TextSession::stop_on_error`] and
+    /// [`TextSession::backspace`].
+    pub fn handle_input(&mut self, key: KeyEvent) {
+        let typed: Vec<char> = match key.code {
+            KeyCode::Char(_) => typed_char(&key).into_iter().collect(),
+            KeyCode::Enter => vec!['\n'],
+            KeyCode::Tab => {
+                if self.target.get(self.typed.len()).is_some_and(|c| c == "\t") {
+                    vec!['\t']
+                } else {
+                    let spaces = self.target[self.typed.len().min(self.target.len())..]
+                        .iter()
+                        .take(4)
+                        .take_while(|c| *c == " ")
+                        .count();
+                    vec![' '; spaces.max(1)]
+                }
+            }
+            KeyCode::Backspace => {
+                let index = self.typed.len().saturating_sub(1);
+                if self.backspace == Backspace::Strict
+                    && !self.typed.is_empty()
+                    && self.ends_correct_word(index)
+                {
+                    return;
+                }
+                if let Some(c) = self.typed.pop()
+                    && self.target.get(index) != Some(&c)
+                {
+                    self.corrected += 1;
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        if self.start.is_none() {
+            self.start = Some(Instant::now());
+        }
+
+        for c in typed {
+            // An accent typed after its letter joins it
+            if let Some(last) = self.typed.last_mut()
+                && !c.is_ascii()
+                && grapheme_count(&format!("{}{}", last, c)) == 1
+            {
+                *last = normalize(&format!("{}{}", last, c));
+                continue;
+            }
+            if self.typed.len() >= self.target.len() || self.blocks(c) {
+                break;
+            }
+
+            let at_word_end = c.is_whitespace()
+                && self
+                    .typed
+                    .last()
+                    .is_some_and(|last| !last.trim().is_empty());
+            self.typed.push(c.to_string());
+
+            if at_word_end {
+                self.timestamps.push((self.typed.len(), Instant::now()));
+            }
+        }
+    }
+
+    /// Returns true once the whole target has been typed.
+    pub fn is_finished(&self) -> bool {
+        !self.target.is_empty() && self.typed.len() >= self.target.len()
+    }
+
+    /// Marks the session as finished, if it wasn't already.
+    pub fn finish(&mut self) {
+        if self.end.is_none() {
+            self.end = Some(Instant::now());
+        }
+    }
+
+    /// Time spent typing, up to [`TextSession::finish`] if it was called.
+    pub fn elapsed(&self) -> Duration {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => end.duration_since(start),
+            (Some(start), None) => start.elapsed(),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Calculates the statistics of the session over the given duration.
+    pub fn stats(&self, duration: Duration) -> GameStats {
+        GameStats::calculate_chars(duration, &self.typed, &self.target)
+            .with_corrected_errors(self.corrected)
+    }
+
+    /// WPM data points for the chart, one per word boundary: (time_seconds, wpm).
+    pub fn wpm_data(&self) -> Vec<(f64, f64)> {
+        let mut data = vec![(0.0, 0.0)];
+
+        if let Some(start) = &self.start {
+            for (chars, ts) in &self.timestamps {
+                let duration = ts.duration_since(*start);
+                let chars = (*chars).min(self.typed.len());
+                let stats =
+                    GameStats::calculate_chars(duration, &self.typed[..chars], &self.target);
+                data.push((duration.as_secs_f64(), stats.wpm()));
+            }
+        }
+
+        data
+    }
+
+    /// Characters to display, newlines included.
+    pub fn styled_chars(&self) -> Vec<StyledChar> {
+        self.target
+            .iter()
+            .enumerate()
+            .map(|(i, target)| {
+                let state = match self.typed.get(i) {
+                    Some(typed) if typed == target => CharState::Correct,
+                    Some(_) => CharState::Incorrect,
+                    None if i == self.typed.len() => CharState::Cursor,
+                    None => CharState::Pending,
+                };
+                StyledChar::new(target.as_str(), state)
+            })
+            .collect()
+    }
+}
 
 /// Builds styled characters from target and typed words.
 ///
 /// This function compares the user's typed input against the target text and
-/// assigns a state to each character (pending, correct, etc).
-pub fn build_styled_chars(target_words: &[String], typed_words: &[String]) -> Vec<StyledChar> {
+/// assigns a state to each character (pending, correct, etc), characters
+/// being [`graphemes`]. An optional `ghost` position (word index, character
+/// index) is marked as [`CharState::Ghost`] unless the user's cursor is on it.
+pub fn build_styled_chars(
+    target_words: &[String],
+    typed_words: &[String],
+    ghost: Option<(usize, usize)>,
+) -> Vec<StyledChar> {
     let mut chars = Vec::new();
 
     let cursor_pos: (usize, usize) = if typed_words.is_empty() {
         (0, 0)
     } else {
         let last_idx = typed_words.len() - 1;
-        (last_idx, typed_words[last_idx].len())
+        (last_idx, grapheme_count(&typed_words[last_idx]))
     };
 
     for (word_idx, target_word) in target_words.iter().enumerate() {
-        let target_chars: Vec<char> = target_word.chars().collect();
-        let typed_word = typed_words.get(word_idx);
-        let typed_chars: Vec<char> = typed_word.map(|w| w.chars().collect()).unwrap_or_default();
+        let target_chars = graphemes(target_word);
+        let typed_chars = typed_words
+            .get(word_idx)
+            .map(|w| graphemes(w))
+            .unwrap_or_default();
 
         let is_current_word = word_idx == cursor_pos.0;
         let is_past_word = word_idx < cursor_pos.0;
@@ -32,6 +1167,8 @@ pub fn build_styled_chars(target_words: &[String], typed_words: &[String]) -> Ve
 
             let state = if is_cursor_here {
                 CharState::Cursor
+            } else if ghost == Some((word_idx, char_idx)) {
+                CharState::Ghost
             } else if let Some(&typed_char) = typed_chars.get(char_idx) {
                 if typed_char == target_char {
                     CharState::Correct
@@ -65,8 +1202,14 @@ pub fn build_styled_chars(target_words: &[String], typed_words: &[String]) -> Ve
             && cursor_pos.1 >= target_chars.len()
             && cursor_pos.1 >= typed_chars.len();
 
+        let ghost_on_space = ghost.is_some_and(|(ghost_word, ghost_char)| {
+            ghost_word == word_idx && ghost_char >= target_chars.len()
+        });
+
         let state = if cursor_on_space {
             CharState::Cursor
+        } else if ghost_on_space {
+            CharState::Ghost
         } else {
             CharState::Pending
         };
@@ -76,3 +1219,469 @@ pub fn build_styled_chars(target_words: &[String], typed_words: &[String]) -> Ve
 
     chars
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn press(session: &mut TextSession, code: KeyCode) {
+        session.handle_input(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn live_wpm_counts_confirmed_words() {
+        let start = Instant::now();
+        let mut session = TypingSession::new();
+        session.set_target(vec!["four".to_string(); 3]);
+        session.start = Some(start);
+        session.end = Some(start + Duration::from_secs(60));
+
+        session.typed_words = vec!["four".to_string(), "fo".to_string()];
+        assert_eq!(session.live_wpm(), 0.8);
+
+        session.typed_words = vec!["four".to_string(), "four".to_string(), String::new()];
+        assert_eq!(session.live_wpm(), 1.8);
+    }
+
+    #[test]
+    fn segment_wpm_buckets_words() {
+        let start = Instant::now();
+        let mut session = TypingSession::new();
+        session.set_target(vec!["four".to_string(); 3]);
+        session.typed_words = vec!["four".to_string(), "fxur".to_string(), "four".to_string()];
+        session.start = Some(start);
+        session.timestamps = vec![
+            (1, start + Duration::from_secs(10)),
+            (2, start + Duration::from_secs(30)),
+            (3, start + Duration::from_secs(70)),
+        ];
+        session.end = Some(start + Duration::from_secs(90));
+
+        // One correct word in the first minute, one in the following half minute
+        assert_eq!(
+            session.segment_wpm(Duration::from_secs(60), Duration::MAX),
+            vec![1.0, 2.0]
+        );
+        // Nothing past the end of the test
+        assert_eq!(
+            session.segment_wpm(Duration::from_secs(60), Duration::from_secs(60)),
+            vec![1.0]
+        );
+    }
+
+    #[test]
+    fn punctuate_builds_sentences() {
+        let words = vec![
+            "hello".to_string(),
+            "there".to_string(),
+            "friend".to_string(),
+        ];
+        let punctuated = punctuate(words, &mut StdRng::seed_from_u64(7));
+
+        assert_eq!(punctuated.len(), 3);
+        assert!(punctuated[0].trim_start_matches('"').starts_with('H'));
+        assert!(punctuated[2].ends_with(['.', '!', '?']));
+    }
+
+    #[test]
+    fn input_outcomes() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["ab".to_string(), "cd".to_string()]);
+
+        let press = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        assert_eq!(session.handle_input(press('a')), InputOutcome::Correct);
+        assert_eq!(session.handle_input(press('x')), InputOutcome::Mistake);
+        assert_eq!(
+            session.handle_input(press(' ')),
+            InputOutcome::Word(WordResult {
+                target: "ab".to_string(),
+                typed: "ax".to_string(),
+            })
+        );
+        assert_eq!(session.handle_input(press(' ')), InputOutcome::None);
+    }
+
+    #[test]
+    fn stop_on_error_blocks_keys() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["ab".to_string(), "cd".to_string()]);
+        session.stop_on_error = StopOnError::Letter;
+
+        let press = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        assert_eq!(session.handle_input(press('x')), InputOutcome::Mistake);
+        assert_eq!(session.handle_input(press(' ')), InputOutcome::Mistake);
+        assert_eq!(session.handle_input(press('a')), InputOutcome::Correct);
+        assert_eq!(session.typed_words, vec!["a".to_string()]);
+
+        session.stop_on_error = StopOnError::Word;
+        session.handle_input(press('x'));
+        assert_eq!(session.handle_input(press(' ')), InputOutcome::Mistake);
+        assert_eq!(session.typed_words, vec!["ax".to_string()]);
+    }
+
+    #[test]
+    fn free_backspace_erases_correct_words() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["ab".to_string(), "cd".to_string()]);
+        for key in [Key::Char('a'), Key::Char('b'), Key::Char(' ')] {
+            session.apply(key);
+        }
+
+        // The correct word stays confirmed
+        session.apply(Key::Backspace);
+        assert_eq!(session.typed_words, vec!["ab".to_string(), String::new()]);
+
+        session.backspace = Backspace::Free;
+        session.apply(Key::Backspace);
+        session.apply(Key::Backspace);
+        assert_eq!(session.typed_words, vec!["a".to_string()]);
+        session.apply(Key::ClearWord);
+        assert_eq!(session.typed_words, vec![String::new()]);
+    }
+
+    #[test]
+    fn backspace_reopens_mistyped_words() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["ab".to_string(), "cd".to_string()]);
+        let press = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        for c in ['a', 'x', ' ', 'c'] {
+            session.handle_input(press(c));
+        }
+        assert_eq!(session.timestamps.len(), 1);
+
+        let backspace = KeyEvent::from(KeyCode::Backspace);
+        session.handle_input(backspace);
+        assert_eq!(
+            session.handle_input(backspace),
+            InputOutcome::Unconfirmed(WordResult {
+                target: "ab".to_string(),
+                typed: "ax".to_string(),
+            })
+        );
+        assert_eq!(session.typed_words, vec!["ax".to_string()]);
+        assert!(session.timestamps.is_empty());
+
+        // Fixed and confirmed again
+        session.handle_input(backspace);
+        session.handle_input(press('b'));
+        session.handle_input(press(' '));
+        assert_eq!(session.typed_words, vec!["ab".to_string(), String::new()]);
+        assert_eq!(session.timestamps.len(), 1);
+    }
+
+    #[test]
+    fn backspace_on_the_first_word_reopens_nothing() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["ab".to_string(), "cd".to_string()]);
+
+        let backspace = KeyEvent::from(KeyCode::Backspace);
+        session.handle_input(KeyEvent::from(KeyCode::Char('a')));
+        session.handle_input(backspace);
+        assert_eq!(session.handle_input(backspace), InputOutcome::None);
+        assert!(session.typed_words.is_empty());
+    }
+
+    #[test]
+    fn combining_marks_are_one_character() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["e\u{301}t".to_string(), "🇫🇷".to_string()]);
+        for key in [Key::Char('e'), Key::Char('\u{301}')] {
+            session.apply(key);
+        }
+        assert_eq!(session.cursor(), (0, 1));
+
+        let chars = build_styled_chars(&session.target_words, &session.typed_words, None);
+        assert_eq!(chars[0].symbol, "é");
+        assert_eq!(chars[0].state, CharState::Correct);
+        assert_eq!(chars[3].symbol, "🇫🇷");
+
+        // The accent goes with its letter
+        session.apply(Key::Backspace);
+        assert_eq!(session.typed_words, vec![String::new()]);
+    }
+
+    #[test]
+    fn keys_are_checked_per_character() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["e\u{301}t".to_string(), "🇫🇷".to_string()]);
+        session.stop_on_error = StopOnError::Letter;
+
+        let press = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        // The letter, then its accent
+        assert_eq!(session.handle_input(press('e')), InputOutcome::Correct);
+        assert_eq!(session.handle_input(press('t')), InputOutcome::Mistake);
+        assert_eq!(
+            session.handle_input(press('\u{301}')),
+            InputOutcome::Correct
+        );
+        assert_eq!(session.handle_input(press('t')), InputOutcome::Correct);
+        session.handle_input(press(' '));
+        // Both halves of the flag
+        assert_eq!(session.handle_input(press('🇫')), InputOutcome::Correct);
+        assert_eq!(session.handle_input(press('🇷')), InputOutcome::Correct);
+        assert!(session.is_finished());
+
+        let keys: Vec<(char, bool)> = session
+            .key_results()
+            .iter()
+            .map(|result| (result.key, result.correct))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ('é', true),
+                ('\u{301}', false),
+                ('\u{301}', true),
+                ('t', true),
+                ('🇫', true),
+                ('🇷', true),
+            ]
+        );
+        assert_eq!(session.error_times().len(), 1);
+
+        // The accented letter typed at once
+        session.clear();
+        assert_eq!(session.handle_input(press('é')), InputOutcome::Correct);
+        assert_eq!(session.typed_words, vec!["é".to_string()]);
+    }
+
+    #[test]
+    fn text_sessions_join_accents() {
+        let mut session = TextSession::new();
+        session.set_target("cafe\u{301}\r\n");
+        for c in "cafe\u{301}".chars() {
+            press(&mut session, KeyCode::Char(c));
+        }
+        press(&mut session, KeyCode::Enter);
+
+        assert_eq!(session.target.len(), 5);
+        assert_eq!(session.typed, session.target);
+        assert!(session.is_finished());
+    }
+
+    #[test]
+    fn key_results_follow_the_target() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["ab".to_string(), "c".to_string()]);
+        session.keystrokes = [(0.0, 'a'), (0.2, 'x'), (0.5, ' '), (0.6, 'c')]
+            .into_iter()
+            .map(|(time, c)| Keystroke {
+                time,
+                key: Key::Char(c),
+            })
+            .collect();
+
+        let results = session.key_results();
+        let keys: Vec<(char, bool)> = results.iter().map(|r| (r.key, r.correct)).collect();
+        assert_eq!(keys, vec![('a', true), ('b', false), ('c', true)]);
+        assert_eq!(results[0].latency, None);
+        assert!((results[2].latency.unwrap() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn error_times_include_extra_characters() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["ab".to_string(), "c".to_string()]);
+        session.keystrokes = [(0.0, 'a'), (0.2, 'x'), (0.4, 'y'), (0.5, ' '), (0.6, 'c')]
+            .into_iter()
+            .map(|(time, c)| Keystroke {
+                time,
+                key: Key::Char(c),
+            })
+            .collect();
+
+        assert_eq!(session.error_times(), vec![0.2, 0.4]);
+    }
+
+    #[test]
+    fn corrected_errors_count_erased_mistakes() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["abc".to_string(), "de".to_string()]);
+
+        for key in [
+            Key::Char('a'),
+            Key::Char('x'),
+            Key::Backspace,
+            Key::Backspace,
+            Key::Char('a'),
+            Key::Char('b'),
+            Key::Char('c'),
+            Key::Char(' '),
+            Key::Char('x'),
+            Key::Char('y'),
+            Key::ClearWord,
+        ] {
+            session.apply(key);
+        }
+
+        // 'x' in the first word, 'x' and 'y' in the second
+        assert_eq!(session.corrected, 3);
+    }
+
+    #[test]
+    fn raw_wpm_ignores_accuracy() {
+        let mut session = TypingSession::new();
+        session.set_target(vec!["abcd".to_string(), "efgh".to_string()]);
+        session.typed_words = vec!["abcd".to_string(), "efxx".to_string()];
+
+        // 9 typed characters in 6 seconds, 7 of them correct
+        let stats = session.stats(Duration::from_secs(6));
+        assert!((stats.raw_wpm() - 18.0).abs() < 1e-9);
+        assert!((stats.wpm() - 14.0).abs() < 1e-9);
+        assert_eq!(stats.corrected_errors(), Some(0));
+    }
+
+    #[test]
+    fn shifted_letters_are_uppercase() {
+        let shifted = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::SHIFT);
+        let already_upper = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        let symbol = KeyEvent::new(KeyCode::Char('!'), KeyModifiers::SHIFT);
+
+        assert_eq!(typed_char(&shifted), Some('A'));
+        assert_eq!(typed_char(&already_upper), Some('A'));
+        assert_eq!(typed_char(&symbol), Some('!'));
+    }
+
+    #[test]
+    fn seeded_rng_picks_the_same_words() {
+        let dictionary = Dictionary::from_words((0..50).map(|i| i.to_string()).collect());
+        let pick = |seed| dictionary.pick(20, &mut word_rng(seed));
+
+        assert_eq!(pick(Some(42)), pick(Some(42)));
+        assert_ne!(pick(Some(42)), pick(Some(43)));
+    }
+
+    #[test]
+    fn refill_keeps_words_ahead() {
+        let dictionary =
+            Dictionary::from_words(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        let mut session = TypingSession::new();
+
+        session.refill(&dictionary, 10, &mut rand::rng());
+        assert!(session.target_words.len() >= 10);
+
+        session.typed_words = vec!["a".to_string(); 8];
+        session.refill(&dictionary, 10, &mut rand::rng());
+        assert!(session.target_words.len() >= 18);
+    }
+
+    #[test]
+    fn peak_burst_ignores_incorrect_words() {
+        let start = Instant::now();
+        let mut session = TypingSession::new();
+        session.target_words = vec!["abcd".to_string(), "efgh".to_string()];
+        session.typed_words = vec!["abcd".to_string(), "efgx".to_string(), String::new()];
+        session.start = Some(start);
+        session.timestamps = vec![
+            (1, start + Duration::from_secs(1)),
+            (2, start + Duration::from_millis(1100)),
+        ];
+
+        // 5 characters in one second
+        assert!((session.peak_burst() - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn word_times_start_at_the_previous_word() {
+        let start = Instant::now();
+        let mut session = TypingSession::new();
+        session.target_words = vec!["ab".to_string(), "cd".to_string(), "efgh".to_string()];
+        session.typed_words = vec![
+            "ab".to_string(),
+            "cx".to_string(),
+            "efgh".to_string(),
+            String::new(),
+        ];
+        session.start = Some(start);
+        session.timestamps = vec![
+            (1, start + Duration::from_millis(500)),
+            (2, start + Duration::from_secs(1)),
+            (3, start + Duration::from_secs(3)),
+        ];
+
+        let times = session.word_times();
+        assert_eq!(times.len(), 2);
+        assert_eq!(times[0].word, "ab");
+        assert!((times[0].wpm - 72.0).abs() < 1e-9);
+        assert_eq!(times[1].word, "efgh");
+        assert!((times[1].seconds - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn raw_wpm_data_buckets_keystrokes_per_second() {
+        let start = Instant::now();
+        let mut session = TypingSession::new();
+        session.start = Some(start);
+        session.end = Some(start + Duration::from_millis(1500));
+        session.keystrokes = [
+            (0.1, Key::Char('a')),
+            (0.5, Key::Backspace),
+            (1.2, Key::Char(' ')),
+        ]
+        .into_iter()
+        .map(|(time, key)| Keystroke { time, key })
+        .collect();
+
+        // 1 character in the first second, 1 in the last half second
+        assert_eq!(session.raw_wpm_data(), vec![(1.0, 12.0), (1.5, 24.0)]);
+    }
+
+    #[test]
+    fn text_session_types_newlines_and_indentation() {
+        let mut session = TextSession::new();
+        session.set_target("if x {\n        y\n}");
+
+        for c in "if x {".chars() {
+            press(&mut session, KeyCode::Char(c));
+        }
+        press(&mut session, KeyCode::Enter);
+        press(&mut session, KeyCode::Tab);
+        press(&mut session, KeyCode::Tab);
+        press(&mut session, KeyCode::Char('y'));
+        press(&mut session, KeyCode::Enter);
+        press(&mut session, KeyCode::Char('}'));
+
+        assert!(session.is_finished());
+        assert_eq!(session.typed, session.target);
+    }
+
+    #[test]
+    fn text_session_tab_stops_at_expected_spaces() {
+        let mut session = TextSession::new();
+        session.set_target("  x");
+
+        press(&mut session, KeyCode::Tab);
+        assert_eq!(session.typed, vec![" ", " "]);
+    }
+
+    #[test]
+    fn text_session_follows_behavior() {
+        let mut session = TextSession::new();
+        session.set_target("ab cd");
+        for c in "ab c".chars() {
+            press(&mut session, KeyCode::Char(c));
+        }
+
+        // Strict backspace keeps the correct word
+        press(&mut session, KeyCode::Backspace);
+        press(&mut session, KeyCode::Backspace);
+        press(&mut session, KeyCode::Backspace);
+        assert_eq!(session.typed, vec!["a", "b", " "]);
+
+        session.stop_on_error = StopOnError::Letter;
+        press(&mut session, KeyCode::Char('x'));
+        assert_eq!(session.typed, vec!["a", "b", " "]);
+
+        session.stop_on_error = StopOnError::Word;
+        session.set_target("ab cd");
+        for c in "ax ".chars() {
+            press(&mut session, KeyCode::Char(c));
+        }
+        assert_eq!(session.typed, vec!["a", "x"]);
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, OptionGroup, Renderer, default_text,
+            util::{TypingSession, load_dictionary, pick_words},
+        },
+        ui::char::StyledChar,
+    },
+    config::{Config, WordRules},
+};
+
+/// Unscored round typed before the actual test.
+///
+/// Like [`Retry`](super::retry::Retry), this mode has no [`Mode`](super::Mode)
+/// variant, see [`App::start_warmup`](crate::app::App::start_warmup).
+pub struct WarmUp {
+    count: usize,
+    session: TypingSession,
+    dictionary: Vec<String>,
+}
+
+impl WarmUp {
+    /// Creates a warm-up round of `count` words from the default text, kept
+    /// to the words passing `rules`.
+    pub fn new(count: usize, rules: &WordRules) -> Result<Self> {
+        let mut warmup = Self {
+            count,
+            session: TypingSession::new(),
+            dictionary: load_dictionary(&default_text(), rules)?,
+        };
+        warmup.generate_words();
+        Ok(warmup)
+    }
+
+    fn generate_words(&mut self) {
+        let words = pick_words(&self.dictionary, self.count, &mut rand::rng());
+        self.session.set_target(words);
+    }
+}
+
+impl Handler for WarmUp {
+    fn initialize(&mut self, _config: &Config) -> Result<()> {
+        self.generate_words();
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for WarmUp {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        format!("Warm-up {}/{}", self.session.typed_words.len(), self.count)
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_prompt(&self) -> Option<String> {
+        Some("Warming up, this round isn't scored".to_string())
+    }
+}
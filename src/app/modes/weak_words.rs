@@ -0,0 +1,158 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    app::{
+        events::Action,
+        modes::{
+            Direction, GameStats, Handler, OptionGroup, Renderer, WordResult, default_text,
+            util::{TypingSession, load_dictionary, pick_words, word_rng},
+        },
+        ui::char::StyledChar,
+    },
+    config::{Config, WordRules},
+    history::WordStats,
+};
+
+/// Words per practice round.
+const PRACTICE_WORDS: usize = 50;
+/// Amount of weakest words the practice set is built from.
+const WEAK_POOL: usize = 25;
+
+pub struct WeakWords {
+    session: TypingSession,
+    pool: Vec<String>,
+    /// Whether the pool comes from history rather than the default text.
+    from_history: bool,
+    rng: ChaCha8Rng,
+    rules: WordRules,
+}
+
+impl Default for WeakWords {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WeakWords {
+    pub fn new() -> Self {
+        Self {
+            session: TypingSession::new(),
+            pool: Vec::new(),
+            from_history: false,
+            rng: ChaCha8Rng::from_os_rng(),
+            rules: WordRules::default(),
+        }
+    }
+
+    /// Builds the pool from the weakest words in the stats store, falling
+    /// back to the default text when no mistakes were recorded yet.
+    ///
+    /// Words left out by the word rules are never practiced.
+    fn load_pool(&mut self) -> Result<()> {
+        self.pool = WordStats::load()
+            .map(|stats| stats.weakest(WEAK_POOL))
+            .unwrap_or_default();
+        self.pool.retain(|word| self.rules.accepts(word));
+        self.from_history = !self.pool.is_empty();
+
+        if !self.from_history {
+            self.pool = load_dictionary(&default_text(), &self.rules)?;
+        }
+        Ok(())
+    }
+
+    fn generate_words(&mut self) {
+        let words = pick_words(&self.pool, PRACTICE_WORDS, &mut self.rng);
+        self.session.set_target(words);
+    }
+}
+
+impl Handler for WeakWords {
+    fn initialize(&mut self, config: &Config) -> Result<()> {
+        self.rng = word_rng(config.defaults.seed);
+        self.rules = config.words.clone();
+        self.load_pool()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
+        Action::None
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.load_pool()?;
+        self.generate_words();
+        Ok(())
+    }
+
+    fn is_complete(&self) -> bool {
+        self.session.is_finished()
+    }
+
+    fn on_complete(&mut self) {
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
+    }
+}
+
+impl Renderer for WeakWords {
+    fn get_options(&self, _focused_index: Option<usize>) -> OptionGroup {
+        OptionGroup { items: vec![] }
+    }
+
+    fn select_option(&mut self, _index: usize) {}
+
+    fn adjust_option(&mut self, _index: usize, _direction: Direction) {}
+
+    fn is_option_editing(&self) -> bool {
+        false
+    }
+
+    fn option_count(&self) -> usize {
+        0
+    }
+
+    fn get_progress(&self) -> String {
+        if self.session.start.is_some() {
+            format!("{}/{}", self.session.typed_words.len(), PRACTICE_WORDS)
+        } else {
+            String::new()
+        }
+    }
+
+    fn get_characters(&self) -> Vec<StyledChar> {
+        self.session.styled_chars()
+    }
+
+    fn get_stats(&self) -> GameStats {
+        self.session.stats(self.session.elapsed())
+    }
+
+    fn get_wpm_data(&self) -> Vec<(f64, f64)> {
+        self.session.wpm_data()
+    }
+
+    fn get_details(&self) -> Vec<String> {
+        if self.from_history {
+            vec![format!("Practiced your {} weakest words", self.pool.len())]
+        } else {
+            vec!["No mistakes recorded yet, practiced common words".to_string()]
+        }
+    }
+}
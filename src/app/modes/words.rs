@@ -1,16 +1,14 @@
-use std::time::{Duration, Instant};
-
-use anyhow::{Context, Result};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use rand::seq::SliceRandom;
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
 use crate::{
-    Resource,
     app::{
         events::Action,
         modes::{
-            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer,
-            util::build_styled_chars,
+            Direction, GameStats, Handler, Mode, OptionGroup, OptionItem, Renderer, WordResult,
+            util::{Dictionary, TypingSession, word_rng},
         },
         ui::char::StyledChar,
     },
@@ -23,13 +21,10 @@ pub struct Words {
     words: usize,
     custom_words: usize,
     is_editing_custom: bool,
-    start: Option<Instant>,
-    end: Option<Instant>,
-    target_words: Vec<String>,
-    typed_words: Vec<String>,
-    timestamps: Vec<(usize, Instant)>,
-    dictionary: Vec<String>,
+    session: TypingSession,
+    dictionary: Dictionary,
     text: String,
+    rng: ChaCha8Rng,
 }
 
 impl Words {
@@ -44,46 +39,23 @@ impl Words {
             words,
             custom_words,
             is_editing_custom: false,
-            start: None,
-            end: None,
-            target_words: Vec::new(),
-            typed_words: Vec::new(),
-            timestamps: Vec::new(),
-            dictionary: Vec::new(),
+            session: TypingSession::new(),
+            dictionary: Dictionary::default(),
             text: text.to_owned(),
+            rng: ChaCha8Rng::from_os_rng(),
         }
     }
 
     fn generate_words(&mut self) {
-        let mut rng = rand::rng();
-        self.dictionary.shuffle(&mut rng);
-
-        self.target_words = self
-            .dictionary
-            .iter()
-            .cycle()
-            .take(self.words)
-            .map(ToString::to_string)
-            .collect();
-    }
-
-    fn check_complete(&self) -> bool {
-        self.typed_words.len() == self.target_words.len()
-            && self
-                .typed_words
-                .last()
-                .is_some_and(|w| w.len() == self.target_words.last().map_or(5, |w| w.len()))
-            || self.typed_words.len() > self.target_words.len()
+        let words = self.dictionary.pick(self.words, &mut self.rng);
+        self.session.set_target(words);
     }
 }
 
 impl Handler for Words {
     fn initialize(&mut self, config: &Config) -> Result<()> {
-        self.start = None;
-        self.end = None;
-        self.typed_words.clear();
-
-        if let Mode::Words { count, text } = &config.defaults.mode {
+        self.rng = word_rng(config.defaults.seed);
+        if let Mode::Words { count, text } = &config.mode("words") {
             self.words = *count;
             if !WORD_COUNTS.contains(count) {
                 self.custom_words = *count;
@@ -91,87 +63,48 @@ impl Handler for Words {
             self.text = text.clone();
         }
 
-        let bytes = Resource::get_text(&self.text)
-            .context(format!("Couldn't find \"{}\" text", &self.text))?;
-
-        self.dictionary = std::str::from_utf8(&bytes)
-            .context("Text contains non-utf8 characters")?
-            .lines()
-            .map(ToString::to_string)
-            .collect();
+        self.dictionary = Dictionary::load(&self.text, config)?;
 
         self.generate_words();
         Ok(())
     }
 
-    fn handle_input(&mut self, key: KeyEvent) -> Action {
-        match key.code {
-            KeyCode::Char(c) => {
-                if self.start.is_none() {
-                    self.start = Some(Instant::now());
-                }
-
-                if c == 'h' && key.modifiers.contains(KeyModifiers::CONTROL) {
-                    // Clear current word
-                    if let Some((typed_idx, typed_word)) =
-                        self.typed_words.iter_mut().enumerate().last()
-                        && let Some(target_word) = self.target_words.get(typed_idx)
-                        && typed_word != target_word
-                    {
-                        if typed_word.is_empty() {
-                            self.typed_words.pop();
-                        } else {
-                            typed_word.clear();
-                        }
-                    }
-                } else if c == ' ' {
-                    // Move to next word
-                    if let Some(last) = self.typed_words.last()
-                        && !last.is_empty()
-                    {
-                        self.timestamps
-                            .push((self.typed_words.len(), Instant::now()));
-                        self.typed_words.push(String::new());
-                    }
-                } else if let Some(word) = self.typed_words.last_mut() {
-                    word.push(c);
-                } else {
-                    self.typed_words.push(c.to_string());
-                }
-            }
-            KeyCode::Backspace => {
-                if let Some((typed_idx, typed_word)) =
-                    self.typed_words.iter_mut().enumerate().last()
-                    && let Some(target_word) = self.target_words.get(typed_idx)
-                    && typed_word != target_word
-                    && typed_word.pop().is_none()
-                {
-                    self.typed_words.pop();
-                }
-            }
-            _ => {}
-        }
+    fn config(&self) -> Option<Mode> {
+        Some(Mode::Words {
+            count: self.words,
+            text: self.text.clone(),
+        })
+    }
 
+    fn handle_input(&mut self, key: KeyEvent) -> Action {
+        self.session.handle_input(key);
         Action::None
     }
 
     fn reset(&mut self) -> Result<()> {
+        self.dictionary.reload()?;
         self.generate_words();
-        self.start = None;
-        self.end = None;
-        self.typed_words.clear();
-        self.timestamps.clear();
         Ok(())
     }
 
     fn is_complete(&self) -> bool {
-        self.check_complete()
+        self.session.is_finished()
     }
 
     fn on_complete(&mut self) {
-        if self.end.is_none() {
-            self.end = Some(Instant::now());
-        }
+        self.session.finish();
+    }
+
+    fn word_results(&self) -> Vec<WordResult> {
+        self.session.word_results()
+    }
+
+    fn session(&self) -> Option<&TypingSession> {
+        Some(&self.session)
+    }
+
+    fn session_mut(&mut self) -> Option<&mut TypingSession> {
+        Some(&mut self.session)
     }
 }
 
@@ -239,40 +172,34 @@ impl Renderer for Words {
     }
 
     fn get_progress(&self) -> String {
-        if self.start.is_some() {
-            format!("{}/{}", self.typed_words.len(), self.words)
+        if self.session.start.is_some() {
+            format!("{}/{}", self.session.typed_words.len(), self.words)
         } else {
             String::new()
         }
     }
 
+    fn progress_ratio(&self) -> Option<f64> {
+        self.session.start?;
+        let completed = self.session.typed_words.len().saturating_sub(1);
+        Some((completed as f64 / self.words.max(1) as f64).min(1.0))
+    }
+
     fn get_characters(&self) -> Vec<StyledChar> {
-        build_styled_chars(&self.target_words, &self.typed_words)
+        self.session.styled_chars()
     }
 
     fn get_stats(&self) -> GameStats {
-        let duration = if let (Some(start), Some(end)) = (self.start, self.end) {
-            end.duration_since(start)
+        let duration = if self.session.end.is_some() {
+            self.session.elapsed()
         } else {
-            Duration::from_secs(0)
+            std::time::Duration::from_secs(0)
         };
 
-        GameStats::calculate(duration, &self.typed_words, &self.target_words)
+        self.session.stats(duration)
     }
 
     fn get_wpm_data(&self) -> Vec<(f64, f64)> {
-        let mut data = vec![(0.0, 0.0)];
-
-        if let Some(start) = &self.start {
-            for (words, ts) in &self.timestamps {
-                let duration = ts.duration_since(*start);
-                let typed_words = &self.typed_words[..*words];
-                let target_words = &self.target_words[..*words];
-                let stats = GameStats::calculate(duration, typed_words, target_words);
-                data.push((duration.as_secs_f64(), stats.wpm()));
-            }
-        }
-
-        data
+        self.session.wpm_data()
     }
 }
@@ -108,6 +108,10 @@ impl Handler for Zen {
             self.end = Some(Instant::now());
         }
     }
+
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        key.code == KeyCode::Enter
+    }
 }
 
 impl Renderer for Zen {
@@ -0,0 +1,78 @@
+//! # Results Screen Module
+//!
+//! This module holds the state of the results screen shown after a test: the
+//! selected tab, and how far the per-word table is scrolled.
+
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::app::{history_screen::cycle, modes::Direction};
+
+/// Tabs of the results screen.
+#[derive(Default, Clone, Copy, PartialEq, Debug, Display, EnumIter)]
+pub enum ResultsTab {
+    /// Speed, accuracy and the other stats of the test.
+    #[default]
+    Summary,
+    /// WPM over the test.
+    Chart,
+    /// Mistyped words and per-key accuracy.
+    Errors,
+    /// Time taken on every word.
+    #[strum(serialize = "Raw Data")]
+    RawData,
+}
+
+/// Selected tab of the results screen.
+#[derive(Default)]
+pub struct ResultsScreen {
+    pub tab: ResultsTab,
+    /// First row shown in the per-word table.
+    pub scroll: usize,
+}
+
+impl ResultsScreen {
+    /// Tabs shown, the chart tab only if `chart` is enabled.
+    pub fn tabs(chart: bool) -> Vec<ResultsTab> {
+        ResultsTab::iter()
+            .filter(|tab| chart || *tab != ResultsTab::Chart)
+            .collect()
+    }
+
+    /// Cycles through the tabs, see [`ResultsScreen::tabs`].
+    pub fn cycle_tab(&mut self, direction: Direction, chart: bool) {
+        let tabs = Self::tabs(chart);
+        let index = tabs.iter().position(|tab| *tab == self.tab).unwrap_or(0);
+        self.tab = tabs[cycle(index, tabs.len(), direction)];
+        self.scroll = 0;
+    }
+
+    /// Scrolls the per-word table of `rows` rows, `Left` for up.
+    pub fn scroll(&mut self, direction: Direction, rows: usize) {
+        self.scroll = match direction {
+            Direction::Left => self.scroll.saturating_sub(1),
+            Direction::Right => (self.scroll + 1).min(rows.saturating_sub(1)),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_through_tabs() {
+        let mut screen = ResultsScreen::default();
+
+        screen.cycle_tab(Direction::Left, true);
+        assert_eq!(screen.tab, ResultsTab::RawData);
+
+        screen.scroll(Direction::Right, 10);
+        screen.cycle_tab(Direction::Right, true);
+        assert_eq!(screen.tab, ResultsTab::Summary);
+        assert_eq!(screen.scroll, 0);
+
+        // Without the chart
+        screen.cycle_tab(Direction::Right, false);
+        assert_eq!(screen.tab, ResultsTab::Errors);
+    }
+}
@@ -4,7 +4,7 @@
 //! characters and their states in the typing area.
 
 /// State of a character in the typing area.
-#[derive(Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum CharState {
     #[default]
     Default,
@@ -14,17 +14,26 @@ pub enum CharState {
     Skipped,
     Extra,
     Cursor,
+    /// Position of a secondary caret, such as the ghost of a previous run.
+    Ghost,
+    /// Position of the pace caret, moving at a target speed.
+    Pace,
 }
 
 /// A single character and its state.
 #[derive(Clone)]
 pub struct StyledChar {
-    pub char: char,
+    /// The character as shown: a grapheme cluster, possibly made of several
+    /// `char`s (e.g. a letter and its combining accent).
+    pub symbol: String,
     pub state: CharState,
 }
 
 impl StyledChar {
-    pub fn new(char: char, state: CharState) -> Self {
-        Self { char, state }
+    pub fn new(symbol: impl Into<String>, state: CharState) -> Self {
+        Self {
+            symbol: symbol.into(),
+            state,
+        }
     }
 }
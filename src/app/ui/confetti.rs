@@ -0,0 +1,124 @@
+//! # Confetti Module
+//!
+//! This module renders the confetti falling over the results screen after a
+//! new personal best.
+
+use std::time::Duration;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::{Color, Style},
+    widgets::Widget,
+};
+
+/// How long the confetti falls for.
+pub const DURATION: Duration = Duration::from_millis(2000);
+
+/// Pieces of confetti per column of the area.
+const DENSITY: f64 = 0.4;
+
+const SYMBOLS: [char; 5] = ['*', '+', 'o', '~', '\''];
+const COLORS: [Color; 6] = [
+    Color::Yellow,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Green,
+    Color::Red,
+    Color::Blue,
+];
+
+/// Confetti `elapsed` into its fall. Pieces only land on empty cells, leaving
+/// the text under them readable, and are gone after [`DURATION`].
+pub struct Confetti {
+    pub elapsed: Duration,
+}
+
+impl Confetti {
+    /// Position of the `index`th piece in `area`, if it is inside.
+    ///
+    /// Pieces start above the area at different heights and fall at different
+    /// speeds, all derived from their index so every frame agrees.
+    fn position(&self, index: usize, area: Rect) -> Option<Position> {
+        let seed = hash(index);
+        let x = seed % u64::from(area.width);
+        let start = (seed >> 16) % u64::from(area.height);
+        // Rows per second, enough to cross the area before the end
+        let speed = f64::from(area.height) * (1.0 + (seed >> 32) as f64 % 100.0 / 100.0);
+
+        let fallen = self.elapsed.as_secs_f64() * speed;
+        let y = fallen - start as f64;
+        (y >= 0.0 && y < f64::from(area.height))
+            .then(|| Position::new(area.x + x as u16, area.y + y as u16))
+    }
+}
+
+impl Widget for Confetti {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.elapsed >= DURATION || area.is_empty() {
+            return;
+        }
+
+        let count = (f64::from(area.width) * DENSITY).ceil() as usize;
+        for index in 0..count {
+            let Some(position) = self.position(index, area) else {
+                continue;
+            };
+            let Some(cell) = buf.cell_mut(position) else {
+                continue;
+            };
+            if cell.symbol() == " " {
+                cell.set_char(SYMBOLS[index % SYMBOLS.len()])
+                    .set_style(Style::new().fg(COLORS[index % COLORS.len()]));
+            }
+        }
+    }
+}
+
+/// Spreads the bits of `index`, see [`Confetti::position`].
+fn hash(index: usize) -> u64 {
+    // SplitMix64
+    let mut z = (index as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pieces(buf: &Buffer) -> usize {
+        buf.content()
+            .iter()
+            .filter(|cell| SYMBOLS.iter().any(|c| cell.symbol() == c.to_string()))
+            .count()
+    }
+
+    #[test]
+    fn confetti_falls_on_empty_cells_then_stops() {
+        let area = Rect::new(0, 0, 40, 10);
+
+        let mut buf = Buffer::empty(area);
+        Confetti {
+            elapsed: Duration::from_millis(500),
+        }
+        .render(area, &mut buf);
+        assert!(pieces(&buf) > 0);
+
+        // Text isn't covered
+        let mut buf = Buffer::empty(area);
+        for y in 0..10 {
+            buf.set_string(0, y, "#".repeat(40), Style::new());
+        }
+        Confetti {
+            elapsed: Duration::from_millis(500),
+        }
+        .render(area, &mut buf);
+        assert_eq!(pieces(&buf), 0);
+
+        let mut buf = Buffer::empty(area);
+        Confetti { elapsed: DURATION }.render(area, &mut buf);
+        assert_eq!(pieces(&buf), 0);
+    }
+}
@@ -0,0 +1,138 @@
+//! # Heatmap Module
+//!
+//! This module renders per-key accuracy and speed on a keyboard layout.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::{
+    app::ui::theme::Theme,
+    history::{KeyStat, KeyStats},
+};
+
+/// Keys of a QWERTY keyboard, row by row.
+pub const KEYBOARD_ROWS: [&str; 4] = ["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"];
+
+/// Amount of keys underlined as the slowest.
+const SLOWEST: usize = 3;
+
+/// Color band of a key, from its accuracy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Band {
+    /// 98% of presses or more were correct.
+    Good,
+    /// 90% of presses or more were correct.
+    Fair,
+    Poor,
+}
+
+impl Band {
+    pub fn of(stat: &KeyStat) -> Self {
+        match stat.accuracy() {
+            a if a >= 98.0 => Band::Good,
+            a if a >= 90.0 => Band::Fair,
+            _ => Band::Poor,
+        }
+    }
+
+    fn style(self) -> Style {
+        match self {
+            Band::Good => Style::new().bg(Color::Green).fg(Color::Black),
+            Band::Fair => Style::new().bg(Color::Yellow).fg(Color::Black),
+            Band::Poor => Style::new().bg(Color::Red).fg(Color::White),
+        }
+    }
+}
+
+/// Keyboard colored by per-key accuracy, with the slowest keys underlined.
+pub struct KeyboardHeatmap<'a> {
+    pub stats: &'a KeyStats,
+    pub theme: &'a Theme,
+}
+
+impl KeyboardHeatmap<'_> {
+    /// Rows taken by the widget: the keyboard and its legend.
+    pub const HEIGHT: u16 = KEYBOARD_ROWS.len() as u16 + 1;
+}
+
+impl Widget for KeyboardHeatmap<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let slowest = self.stats.slowest(SLOWEST);
+
+        let lines: Vec<Line> = KEYBOARD_ROWS
+            .iter()
+            .enumerate()
+            .map(|(row, keys)| {
+                // Stagger the rows like a physical keyboard
+                let mut spans = vec![Span::raw(" ".repeat(row * 2))];
+                for key in keys.chars() {
+                    let style = match self.stats.get(key) {
+                        Some(stat) if slowest.contains(&key) => Band::of(stat)
+                            .style()
+                            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                        Some(stat) => Band::of(stat).style(),
+                        None => self.theme.pending,
+                    };
+                    spans.push(Span::styled(format!(" {} ", key), style));
+                    spans.push(Span::raw(" "));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        // Center the keyboard as a block, so the stagger is kept
+        let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        let keyboard_area = Rect {
+            x: area.x + area.width.saturating_sub(width) / 2,
+            width: width.min(area.width),
+            height: area.height.min(KEYBOARD_ROWS.len() as u16),
+            ..area
+        };
+        Paragraph::new(lines).render(keyboard_area, buf);
+
+        let legend_area = Rect {
+            y: area.y + KEYBOARD_ROWS.len() as u16,
+            height: 1,
+            ..area
+        };
+        if legend_area.bottom() > area.bottom() {
+            return;
+        }
+
+        Paragraph::new(
+            Line::from(vec![
+                Span::styled(" ≥98% ", Band::Good.style()),
+                Span::raw(" "),
+                Span::styled(" ≥90% ", Band::Fair.style()),
+                Span::raw(" "),
+                Span::styled(" <90% ", Band::Poor.style()),
+                Span::styled("  underlined: slowest", self.theme.pending),
+            ])
+            .centered(),
+        )
+        .render(legend_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bands_follow_accuracy() {
+        let stat = |hits, misses| KeyStat {
+            hits,
+            misses,
+            ..KeyStat::default()
+        };
+
+        assert_eq!(Band::of(&stat(99, 1)), Band::Good);
+        assert_eq!(Band::of(&stat(95, 5)), Band::Fair);
+        assert_eq!(Band::of(&stat(8, 2)), Band::Poor);
+    }
+}
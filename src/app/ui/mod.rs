@@ -4,22 +4,77 @@
 //! It defines the global layout, theme/styles, and the main rendering entry point.
 
 pub mod char;
+pub mod confetti;
+pub mod heatmap;
 pub mod theme;
+pub mod timer;
+pub mod typing;
 
+use std::time::Duration;
+
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     Frame,
     buffer::Buffer,
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Flex, Layout, Position, Rect},
     style::{Modifier, Style, Stylize},
     symbols,
     text::{Line, Span},
     widgets::{
-        Axis, Block, BorderType, Borders, Chart, Dataset, GraphType, Padding, Paragraph, Widget,
-        Wrap,
+        Axis, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, LineGauge, Padding,
+        Paragraph, Sparkline, Widget, Wrap,
     },
 };
+use strum::VariantNames;
+
+use crate::{
+    app::{
+        App, State,
+        events::MouseTarget,
+        history_screen::HistoryView,
+        modes::{Mode, WordResult},
+        results_screen::{ResultsScreen, ResultsTab},
+        ui::{
+            char::CharState, confetti::Confetti, heatmap::KeyboardHeatmap, timer::Timer,
+            typing::TypingLayout,
+        },
+    },
+    history::TestRecord,
+    profile, stats,
+};
+
+/// Logo of the startup screen.
+const LOGO: [&str; 5] = [
+    r" _____ _____ _____ ",
+    r"|_   _|_   _|_   _|",
+    r"  | |   | |   | |  ",
+    r"  | |   | |   | |  ",
+    r"  |_|   |_|   |_|  ",
+];
+
+/// Seconds of WPM drawn by the live sparkline, one per column.
+const SPARKLINE_SECONDS: usize = 30;
+
+/// Amount of words in the slowest words table of the results screen.
+const SLOWEST_WORDS: usize = 3;
+
+/// Amount of lines of the typing area shown at once.
+const TYPING_LINES: usize = 3;
+
+/// Smallest terminal the UI is drawn in, in columns and rows.
+const MIN_WIDTH: u16 = 50;
+const MIN_HEIGHT: u16 = 20;
+
+/// Time the blinking cursor stays shown, then hidden.
+const CURSOR_BLINK: Duration = Duration::from_millis(500);
 
-use crate::app::{App, State};
+/// Warning shown instead of the UI when `area` is smaller than
+/// [`MIN_WIDTH`] x [`MIN_HEIGHT`].
+fn size_warning(area: Rect) -> Option<String> {
+    (area.width < MIN_WIDTH || area.height < MIN_HEIGHT)
+        .then(|| format!("Terminal too small (need {}x{})", MIN_WIDTH, MIN_HEIGHT))
+}
 
 /// Renders the application UI with a two-section vertical layout.
 ///
@@ -29,11 +84,28 @@ use crate::app::{App, State};
 ///
 /// Game mode data is retrieved via the [`Renderer`](super::modes::Renderer) trait
 /// and styled using the application's [`Theme`](super::Theme).
-pub fn draw(frame: &mut Frame, app: &App) {
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let mut targets = Vec::new();
+
     // Set global background
     let bg_block = Block::default().style(Style::default().bg(app.theme.background));
     frame.render_widget(bg_block, frame.area());
 
+    if let Some(warning) = size_warning(frame.area()) {
+        let [warning_area] = Layout::vertical([Constraint::Length(2)])
+            .flex(Flex::Center)
+            .areas(frame.area());
+        frame.render_widget(
+            Paragraph::new(warning)
+                .style(app.theme.incorrect)
+                .centered()
+                .wrap(Wrap { trim: true }),
+            warning_area,
+        );
+        app.mouse_targets = targets;
+        return;
+    }
+
     let layout = Layout::vertical([Constraint::Min(10), Constraint::Length(3)]).split(frame.area());
 
     let body_block = Block::new()
@@ -72,41 +144,216 @@ pub fn draw(frame: &mut Frame, app: &App) {
     // Render content based on state
     match app.state {
         State::Home | State::Running => {
-            render_game_body(body_area, frame.buffer_mut(), app);
+            let body_area = if app.ui.status_bar {
+                let [body_area, status_area] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(body_area);
+                render_status_bar(status_area, frame.buffer_mut(), app);
+                body_area
+            } else {
+                body_area
+            };
+
+            let cursor = render_game_body(body_area, frame.buffer_mut(), app, &mut targets);
+            if let Some(cursor) = cursor
+                && app.ui.cursor.uses_terminal_cursor()
+            {
+                frame.set_cursor_position(cursor);
+            }
+        }
+        State::Splash => {
+            render_splash(body_area, frame.buffer_mut(), app);
         }
         State::Complete => {
             render_complete_body(body_area, frame.buffer_mut(), app);
+            if app.new_pb && app.ui.celebration {
+                Confetti {
+                    elapsed: app.completed_at.elapsed(),
+                }
+                .render(body_area, frame.buffer_mut());
+            }
+        }
+        State::History => {
+            render_history_body(body_area, frame.buffer_mut(), app);
         }
     }
 
-    render_footer(footer_area, frame.buffer_mut(), app);
+    render_footer(footer_area, frame.buffer_mut(), app, &mut targets);
 
     frame.render_widget(body_block, layout[0]);
     frame.render_widget(footer_block, layout[1]);
+
+    // The overlay covers the screen, nothing under it can be clicked
+    if app.show_help {
+        render_help(frame.area(), frame.buffer_mut(), app);
+        targets.clear();
+    }
+    app.mouse_targets = targets;
+}
+
+/// Renders the startup screen: the [`LOGO`], and how to get started.
+fn render_splash(area: Rect, buf: &mut Buffer, app: &App) {
+    let mut lines: Vec<Line> = LOGO
+        .iter()
+        .map(|line| Line::styled(*line, app.theme.highlighted.add_modifier(Modifier::BOLD)))
+        .collect();
+    lines.push(Line::styled("Terminal Typing Test", app.theme.default));
+    lines.push(Line::default());
+    lines.extend(
+        [
+            "Press any key to start",
+            "Start typing on the Home screen to begin a test",
+            "ENTER on the mode selector lists every mode",
+            "? lists the keys of each screen",
+        ]
+        .map(|hint| Line::styled(hint, app.theme.pending)),
+    );
+
+    let [area] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+        .flex(Flex::Center)
+        .areas(area);
+    Paragraph::new(lines).centered().render(area, buf);
 }
 
 /// Renders the main game area: options bar, progress, and typing area.
-fn render_game_body(area: Rect, buf: &mut Buffer, app: &App) {
-    let layout = Layout::vertical([
+///
+/// Returns the position of the cursor in the typing area, if it is visible.
+fn render_game_body(
+    area: Rect,
+    buf: &mut Buffer,
+    app: &App,
+    targets: &mut Vec<(Rect, MouseTarget)>,
+) -> Option<Position> {
+    let [options_area, progress_area, typing_area] = Layout::vertical([
         Constraint::Length(3), // Options bar
         Constraint::Length(1), // Progress
         Constraint::Min(5),    // Typing area
     ])
-    .split(area);
+    .areas(area);
+    // The progress stays aligned with the text
+    let progress_area = centered_width(progress_area, app.ui.max_width);
+    let typing_area = centered_width(typing_area, app.ui.max_width);
 
     if app.state == State::Home {
-        render_options_bar(layout[0], buf, app);
+        render_options_bar(options_area, buf, app, targets);
     }
 
     if app.state == State::Running {
-        render_progress(layout[1], buf, app);
+        render_progress(progress_area, buf, app);
+    }
+
+    if app.state == State::Home
+        && let Some(notice) = &app.notice
+    {
+        Paragraph::new(notice.as_str())
+            .style(app.theme.incorrect)
+            .centered()
+            .render(progress_area, buf);
+    }
+
+    if let Some(selected) = app.editing_mode.as_deref()
+        && app.state == State::Home
+    {
+        render_mode_menu(typing_area, buf, app, selected);
+        return None;
+    }
+
+    let prompt = app.mode.get_prompt();
+    let [prompt_area, text_area, _, bar_area] = Layout::vertical([
+        Constraint::Length(if prompt.is_some() { 2 } else { 0 }), // Prompt
+        Constraint::Length(TYPING_LINES as u16),                  // Typing area
+        Constraint::Length(1),
+        Constraint::Length(1), // Progress bar
+    ])
+    .areas(typing_area);
+
+    if let Some(prompt) = prompt {
+        Paragraph::new(prompt)
+            .style(app.theme.highlighted.add_modifier(Modifier::BOLD))
+            .render(prompt_area, buf);
     }
 
-    render_typing_area(layout[2], buf, app);
+    if app.state == State::Running
+        && app.ui.progress_bar
+        && let Some(ratio) = app.mode.progress_ratio()
+    {
+        LineGauge::default()
+            .ratio(ratio.clamp(0.0, 1.0))
+            .label("")
+            .filled_style(app.theme.highlighted)
+            .unfilled_style(app.theme.pending)
+            .render(bar_area, buf);
+    }
+
+    render_typing_area(text_area, buf, app)
+}
+
+/// Narrows `area` to at most `max_width` columns, centered. A `max_width` of 0
+/// keeps the full width.
+fn centered_width(area: Rect, max_width: u16) -> Rect {
+    if max_width == 0 || area.width <= max_width {
+        return area;
+    }
+    let [centered] = Layout::horizontal([Constraint::Length(max_width)])
+        .flex(Flex::Center)
+        .areas(area);
+    centered
+}
+
+/// Renders the mode menu: every mode with its description and settings,
+/// scrolled to keep `selected` in view.
+fn render_mode_menu(area: Rect, buf: &mut Buffer, app: &App, selected: &str) {
+    let current = app.current_config();
+    let name_width = Mode::VARIANTS
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or(0);
+
+    let lines: Vec<Line> = app
+        .modes
+        .iter()
+        .map(|(mode, description)| {
+            // The current mode shows the options changed on the Home screen
+            let mode = if mode.name() == current.name() {
+                &current
+            } else {
+                mode
+            };
+            let (marker, style) = if mode.name() == selected {
+                ("›", app.theme.highlighted.add_modifier(Modifier::BOLD))
+            } else {
+                (" ", app.theme.default)
+            };
+
+            Line::from(vec![
+                Span::styled(
+                    format!("{} {:<name_width$}  ", marker, capitalize(mode.name())),
+                    style,
+                ),
+                Span::styled(format!("{}  ", description), app.theme.default),
+                Span::styled(mode.parameters(), app.theme.pending),
+            ])
+        })
+        .collect();
+
+    let index = Mode::VARIANTS
+        .iter()
+        .position(|name| *name == selected)
+        .unwrap_or(0);
+    let scroll = index.saturating_sub(usize::from(area.height) / 2);
+    Paragraph::new(lines)
+        .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0))
+        .render(area, buf);
 }
 
 /// Renders the mode selector and mode-specific options.
-fn render_options_bar(area: Rect, buf: &mut Buffer, app: &App) {
+fn render_options_bar(
+    area: Rect,
+    buf: &mut Buffer,
+    app: &App,
+    targets: &mut Vec<(Rect, MouseTarget)>,
+) {
+    // Every span, with the option it selects when clicked
     let mut spans = vec![];
 
     // Mode selector (index 0)
@@ -123,9 +370,12 @@ fn render_options_bar(area: Rect, buf: &mut Buffer, app: &App) {
         app.theme.highlighted
     };
 
-    spans.push(Span::styled(capitalize(mode_name), mode_style));
+    spans.push((
+        Span::styled(capitalize(mode_name), mode_style),
+        Some(MouseTarget::Option(0)),
+    ));
     if app.mode.option_count() > 0 {
-        spans.push(Span::from(" | "));
+        spans.push((Span::from(" | "), None));
     }
 
     // We pass None when mode selector is focused, otherwise pass the mode option index
@@ -152,86 +402,726 @@ fn render_options_bar(area: Rect, buf: &mut Buffer, app: &App) {
             app.theme.default
         };
 
-        spans.push(Span::styled(&item.label, style));
+        spans.push((
+            Span::styled(&item.label, style),
+            Some(MouseTarget::Option(i + 1)),
+        ));
 
         if i < options.items.len() - 1 {
-            spans.push(Span::from(" | "));
+            spans.push((Span::from(" | "), None));
         }
     }
 
-    Paragraph::new(Line::from(spans))
+    let mut details = Vec::new();
+    if let Some(best) = app.personal_bests.get(&app.current_config()) {
+        details.push(format!("PB: {:.1} WPM", best));
+    }
+    let today = Utc::now().date_naive();
+    match app.activity.streak(today) {
+        0 => {}
+        1 => details.push("Streak: 1 day".to_string()),
+        streak => details.push(format!("Streak: {} days", streak)),
+    }
+    if let Some(progress) = stats::goal_progress(&app.goal, &app.activity.day(today)) {
+        details.push(format!("Goal: {}", progress));
+    }
+
+    // The line is centered
+    let width: usize = spans.iter().map(|(span, _)| span.width()).sum();
+    let mut x = area.x + area.width.saturating_sub(width as u16) / 2;
+    for (span, target) in &spans {
+        let span_width = span.width() as u16;
+        if let Some(target) = target {
+            targets.push((Rect::new(x, area.y, span_width, 1), *target));
+        }
+        x += span_width;
+    }
+
+    let line: Line = spans.into_iter().map(|(span, _)| span).collect();
+    let mut lines = vec![line];
+    if !details.is_empty() {
+        lines.push(Line::styled(details.join(" · "), app.theme.default).dim());
+    }
+
+    Paragraph::new(lines).centered().render(area, buf);
+}
+
+/// Renders the status line of the Home and game screens, see [`status_line`].
+fn render_status_bar(area: Rect, buf: &mut Buffer, app: &App) {
+    let mode = app.current_config();
+    let line = status_line(
+        &mode,
+        profile::active().unwrap_or(profile::DEFAULT),
+        app.personal_bests.get(&mode),
+    );
+    Paragraph::new(line)
+        .style(app.theme.default)
+        .dim()
         .centered()
         .render(area, buf);
 }
 
-/// Renders the progress indicator (timer, word count, etc).
+/// Status line text: the mode with its settings (text included), the profile
+/// and the personal best of these settings.
+fn status_line(mode: &Mode, profile: &str, best: Option<f64>) -> String {
+    let mut parts = vec![capitalize(mode.name())];
+    let parameters = mode.parameters();
+    if !parameters.is_empty() {
+        parts.push(parameters);
+    }
+    parts.push(format!("profile {}", profile));
+    parts.push(match best {
+        Some(best) => format!("PB {:.1} WPM", best),
+        None => "no PB yet".to_string(),
+    });
+    parts.join(" · ")
+}
+
+/// Renders the progress indicator (timer, word count, etc), and the live stats
+/// enabled in the config.
 fn render_progress(area: Rect, buf: &mut Buffer, app: &App) {
     let progress = app.mode.get_progress();
-    Paragraph::new(progress)
+    let mut parts = Vec::new();
+
+    // Right after the warm-up, the test waits for the first keystroke
+    if progress.is_empty() && app.warmed_up {
+        parts.push("Warm-up done, the test starts when you type".to_string());
+    } else if app.ui.live_timer && !progress.is_empty() {
+        parts.push(progress);
+    }
+    if let Some(session) = app.mode.session()
+        && session.start.is_some()
+    {
+        if app.ui.live_wpm {
+            parts.push(format!("{:.0} WPM", session.live_wpm()));
+        }
+        if app.ui.live_accuracy {
+            parts.push(format!(
+                "{:.0}%",
+                session.stats(session.elapsed()).accuracy()
+            ));
+        }
+    }
+
+    if app.confirming_restart {
+        parts = vec![format!(
+            "Press {} again to restart, any other key to go on",
+            app.behavior.restart_key.label()
+        )];
+    }
+
+    // The bar or arc of the clock mode, before its seconds
+    let timer = app
+        .mode
+        .get_timer()
+        .filter(|_| app.ui.live_timer && !app.confirming_restart)
+        .map(|(remaining, total)| Timer::new(remaining, total, app.ui.timer).style(app.theme.timer))
+        .filter(|timer| timer.width() > 0);
+    let timer_width = timer.as_ref().map_or(0, |timer| timer.width() + 1);
+    let [timer_area, text_area] =
+        Layout::horizontal([Constraint::Length(timer_width), Constraint::Fill(1)]).areas(area);
+    if let Some(timer) = timer {
+        timer.render(timer_area, buf);
+    }
+
+    let text = parts.join("   ");
+    let width = timer_width + Line::from(text.as_str()).width() as u16;
+    Paragraph::new(text)
         .style(app.theme.highlighted)
-        .render(area, buf);
+        .render(text_area, buf);
+
+    if app.ui.sparkline
+        && !app.confirming_restart
+        && let Some(session) = app.mode.session()
+        && session.start.is_some()
+    {
+        let [_, sparkline_area] = Layout::horizontal([
+            Constraint::Length(width + 3),
+            Constraint::Length(SPARKLINE_SECONDS as u16),
+        ])
+        .areas(area);
+        let data = sparkline_data(&app.mode.get_wpm_data(), session.elapsed().as_secs_f64());
+        Sparkline::default()
+            .data(&data)
+            .style(app.theme.highlighted)
+            .render(sparkline_area, buf);
+    }
 }
 
-/// Renders styled characters from the game mode using theme colors.
-fn render_typing_area(area: Rect, buf: &mut Buffer, app: &App) {
-    let chars = app.mode.get_characters();
-    let spans: Vec<Span> = chars
-        .iter()
-        .map(|sc| {
-            let style = app.theme.style_for(sc.state);
-            Span::styled(sc.char.to_string(), style)
-        })
-        .collect();
+/// WPM every second of the last [`SPARKLINE_SECONDS`] of a test that has run
+/// for `elapsed` seconds, read on the line going through `data` (see
+/// [`Handler::get_wpm_data`](super::modes::Handler::get_wpm_data)).
+fn sparkline_data(data: &[(f64, f64)], elapsed: f64) -> Vec<u64> {
+    let last = elapsed.floor() as usize;
+    (last.saturating_sub(SPARKLINE_SECONDS - 1)..=last)
+        .map(|second| interpolate(data, second as f64).round() as u64)
+        .collect()
+}
 
-    Paragraph::new(Line::from(spans))
-        .wrap(Wrap { trim: false })
-        .render(area, buf);
+/// Renders styled characters from the game mode using theme colors, see
+/// [`TypingLayout`].
+///
+/// The pace caret is marked once the test has started, see
+/// [`UiOptions::pace_caret`](crate::config::UiOptions::pace_caret).
+///
+/// Only [`TYPING_LINES`] lines are shown, scrolling as the cursor moves down.
+/// Returns the position of the cursor, if it is visible. When the terminal
+/// cursor is used, or the blinking cursor is hidden, the character under it
+/// keeps its pending style.
+fn render_typing_area(area: Rect, buf: &mut Buffer, app: &App) -> Option<Position> {
+    let mut chars = app.mode.get_characters();
+    if let Some(session) = app.mode.session()
+        && session.start.is_some()
+        && let Some(wpm) = app.ui.pace_caret.wpm(
+            app.ui.pace_wpm,
+            app.personal_bests.get(&app.current_config()),
+        )
+    {
+        typing::mark_pace(&mut chars, wpm, session.elapsed());
+    }
+    // The terminal cursor is drawn over the character, or the cursor blinked out
+    let plain_cursor = app.ui.cursor.uses_terminal_cursor()
+        || !cursor_shown(app.ui.cursor_blink, app.last_key.elapsed());
+    let mut layout = TypingLayout::new(&chars, area.width.into(), |state| {
+        if state == CharState::Cursor && plain_cursor {
+            app.theme.pending
+        } else {
+            app.theme.style_for(state)
+        }
+    });
+
+    if app.ui.dim_typed {
+        layout.dim_typed(&chars, app.theme.typed);
+    }
+    layout.scroll(TYPING_LINES);
+    Paragraph::new(layout.lines).render(area, buf);
+
+    let (column, line) = layout.cursor?;
+    let position = Position::new(
+        area.x + u16::try_from(column).ok()?,
+        area.y + u16::try_from(line).ok()?,
+    );
+    area.contains(position).then_some(position)
 }
 
-/// Renders the completion screen with stats and WPM chart.
+/// Whether the styled cursor is shown `since_key` after the last key press,
+/// blinking every [`CURSOR_BLINK`] if `blink` is set.
+fn cursor_shown(blink: bool, since_key: Duration) -> bool {
+    !blink || (since_key.as_millis() / CURSOR_BLINK.as_millis()).is_multiple_of(2)
+}
+
+/// Renders the completion screen: its tabs, and the selected one.
 fn render_complete_body(area: Rect, buf: &mut Buffer, app: &App) {
-    let layout = Layout::vertical([
-        Constraint::Length(6), // Stats
-        Constraint::Min(10),   // WPM Chart
+    let [tabs_area, _, body_area] = Layout::vertical([
+        Constraint::Length(1), // Tabs
+        Constraint::Length(1),
+        Constraint::Min(0), // Selected tab
     ])
-    .split(area);
+    .areas(area);
 
-    // Stats
+    let tabs = ResultsScreen::tabs(app.ui.results_chart);
+    let mut spans = Vec::new();
+    for (i, tab) in tabs.iter().enumerate() {
+        let style = if *tab == app.results.tab {
+            app.theme.highlighted.add_modifier(Modifier::BOLD)
+        } else {
+            app.theme.default
+        };
+        spans.push(Span::styled(tab.to_string(), style));
+
+        if i < tabs.len() - 1 {
+            spans.push(Span::from(" | "));
+        }
+    }
+    Paragraph::new(Line::from(spans))
+        .centered()
+        .render(tabs_area, buf);
+
+    match app.results.tab {
+        ResultsTab::Summary => render_results_summary(body_area, buf, app),
+        ResultsTab::Chart => render_results_chart(body_area, buf, app),
+        ResultsTab::Errors => render_results_errors(body_area, buf, app),
+        ResultsTab::RawData => render_results_words(body_area, buf, app),
+    }
+}
+
+/// Renders the stats of the completed test, and its slowest words.
+fn render_results_summary(area: Rect, buf: &mut Buffer, app: &App) {
     let stats = app.mode.get_stats();
-    let stats_lines = vec![
-        Line::from(""),
+
+    // Stats
+    let mut stats_lines = vec![
         Line::from("Test Complete!")
             .centered()
             .green()
             .add_modifier(Modifier::BOLD),
-        Line::from(""),
-        Line::from(format!("Average WPM: {:.1}", stats.wpm()))
+        if app.behavior.is_invalid(stats.accuracy()) {
+            Line::from(format!(
+                "Invalid: accuracy below {}%",
+                app.behavior.min_accuracy
+            ))
             .centered()
-            .cyan(),
-        Line::from(format!("Accuracy: {:.1}%", stats.accuracy()))
-            .centered()
-            .yellow(),
+            .red()
+            .add_modifier(Modifier::BOLD)
+        } else if app.new_pb {
+            Line::from("New PB!")
+                .centered()
+                .style(app.theme.personal_best)
+        } else {
+            Line::from("")
+        },
+        Line::from(format!(
+            "Raw / Net WPM: {:.1} / {:.1}",
+            stats.raw_wpm(),
+            stats.wpm()
+        ))
+        .centered()
+        .cyan(),
+        Line::from(match stats.corrected_errors() {
+            Some(corrected) => format!(
+                "Accuracy: {:.1}% ({} corrected)",
+                stats.accuracy(),
+                corrected
+            ),
+            None => format!("Accuracy: {:.1}%", stats.accuracy()),
+        })
+        .centered()
+        .yellow(),
         Line::from(format!("Time: {:.1}s", stats.duration()))
             .centered()
             .magenta(),
     ];
-    Paragraph::new(stats_lines).render(layout[0], buf);
+    if let Some(burst) = stats.peak_burst() {
+        stats_lines.push(
+            Line::from(format!("Peak Burst: {:.1} WPM", burst))
+                .centered()
+                .blue(),
+        );
+    }
+    if let Some(first_key) = app.last_stats.as_ref().and_then(|stats| stats.first_key()) {
+        stats_lines.push(
+            Line::from(format!("First Key: {:.2}s", first_key))
+                .centered()
+                .style(app.theme.default),
+        );
+    }
+    if let Some((first, second)) = stats.fatigue() {
+        let change = if first > 0.0 {
+            (second - first) / first * 100.0
+        } else {
+            0.0
+        };
+        stats_lines.push(
+            Line::from(format!(
+                "Fatigue: {:.1} → {:.1} WPM ({:+.1}%)",
+                first, second, change
+            ))
+            .centered()
+            .red(),
+        );
+    }
+    stats_lines.extend(
+        app.mode
+            .get_details()
+            .into_iter()
+            .map(|line| Line::from(line).centered().style(app.theme.default)),
+    );
+
+    let slowest = app.slowest_words(SLOWEST_WORDS);
+    if !slowest.is_empty() {
+        let width = slowest
+            .iter()
+            .map(|time| time.word.chars().count())
+            .max()
+            .unwrap_or(0);
+        stats_lines.push(Line::from(""));
+        stats_lines.push(
+            Line::from("Slowest Words")
+                .centered()
+                .style(app.theme.highlighted.add_modifier(Modifier::BOLD)),
+        );
+        stats_lines.extend(slowest.into_iter().map(|time| {
+            Line::from(format!(
+                "{:<width$}  {:>5.2}s  {:>5.1} WPM",
+                time.word, time.seconds, time.wpm
+            ))
+            .centered()
+            .style(app.theme.default)
+        }));
+    }
+
+    Paragraph::new(stats_lines).render(area, buf);
+}
+
+/// Renders the WPM chart of the completed test, with its errors.
+fn render_results_chart(area: Rect, buf: &mut Buffer, app: &App) {
+    let stats = app.mode.get_stats();
 
     // WPM Chart
     let data = app.mode.get_wpm_data();
-    let max_wpm = data.iter().map(|(_, wpm)| *wpm).fold(0.0, f64::max);
-
-    let y_max = max_wpm.max(10.0);
+    let average = rolling_average(&data, app.chart.smoothing);
+    let raw = match app.mode.session() {
+        Some(session) if app.chart.raw => session.raw_wpm_data(),
+        _ => Vec::new(),
+    };
+    // Errors are marked on the WPM line
+    let errors: Vec<(f64, f64)> = app
+        .mode
+        .error_times()
+        .into_iter()
+        .map(|time| (time, interpolate(&data, time)))
+        .collect();
     let x_max = stats.duration().max(1.0);
-
     let x_labels = [
         "0.0".to_string(),
         format!("{:.1}", x_max / 2.0),
         format!("{:.1}", x_max),
     ];
 
+    render_wpm_chart(
+        area,
+        buf,
+        app,
+        &[
+            ChartLine::new("Raw", &raw, app.theme.pending),
+            ChartLine::new("WPM", &data, app.theme.highlighted),
+            ChartLine::new("Average", &average, app.theme.correct),
+            ChartLine::new("Errors", &errors, app.theme.incorrect).scatter(),
+        ],
+        "Time",
+        x_max,
+        x_labels,
+    );
+}
+
+/// Renders the per-key heatmap of the completed test, and its mistyped words.
+fn render_results_errors(area: Rect, buf: &mut Buffer, app: &App) {
+    let heatmap_height = if app.key_stats.keys.is_empty() {
+        0
+    } else {
+        KeyboardHeatmap::HEIGHT + 1
+    };
+    let [heatmap_area, words_area] = Layout::vertical([
+        Constraint::Length(heatmap_height), // Key heatmap
+        Constraint::Min(0),                 // Mistyped words
+    ])
+    .areas(area);
+
+    if heatmap_height > 0 {
+        KeyboardHeatmap {
+            stats: &app.key_stats,
+            theme: &app.theme,
+        }
+        .render(heatmap_area, buf);
+    }
+
+    let [title_area, words_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(words_area);
+    Paragraph::new(
+        Line::from("Mistyped Words")
+            .centered()
+            .style(app.theme.highlighted.add_modifier(Modifier::BOLD)),
+    )
+    .render(title_area, buf);
+
+    let missed = app.mistyped_words();
+    if missed.is_empty() {
+        Paragraph::new(Line::styled("No mistyped words.", app.theme.pending).centered())
+            .render(words_area, buf);
+        return;
+    }
+
+    let width = missed
+        .iter()
+        .map(|result| result.target.chars().count())
+        .max()
+        .unwrap_or(0);
+    let lines: Vec<Line> = missed
+        .iter()
+        .map(|result| mistyped_word_line(result, width, app))
+        .collect();
+
+    Paragraph::new(lines)
+        .scroll((u16::try_from(app.results.scroll).unwrap_or(u16::MAX), 0))
+        .render(words_area, buf);
+}
+
+/// A mistyped word: the target padded to `width`, then what was typed with
+/// each character styled as in the typing area.
+fn mistyped_word_line<'a>(result: &WordResult, width: usize, app: &'a App) -> Line<'a> {
+    let mut spans = vec![Span::styled(
+        format!("{:<width$}  ", result.target),
+        app.theme.default,
+    )];
+    spans.extend(
+        result
+            .styled_chars()
+            .into_iter()
+            .map(|c| Span::styled(c.symbol, app.theme.style_for(c.state))),
+    );
+    Line::from(spans).centered()
+}
+
+/// Renders the time taken on every correctly typed word, scrolled to
+/// [`ResultsScreen::scroll`].
+fn render_results_words(area: Rect, buf: &mut Buffer, app: &App) {
+    if app.word_times.is_empty() {
+        Paragraph::new(
+            Line::styled("No word timings for this test.", app.theme.pending).centered(),
+        )
+        .render(area, buf);
+        return;
+    }
+
+    let width = app
+        .word_times
+        .iter()
+        .map(|time| time.word.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let [header_area, rows_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+    Paragraph::new(
+        Line::from(format!(
+            "{:>4}  {:<width$}  {:>6}  {:>9}",
+            "#", "Word", "Time", "Speed"
+        ))
+        .centered()
+        .style(app.theme.highlighted.add_modifier(Modifier::BOLD)),
+    )
+    .render(header_area, buf);
+
+    let rows: Vec<Line> = app
+        .word_times
+        .iter()
+        .enumerate()
+        .map(|(i, time)| {
+            Line::from(format!(
+                "{:>4}  {:<width$}  {:>5.2}s  {:>5.1} WPM",
+                i + 1,
+                time.word,
+                time.seconds,
+                time.wpm
+            ))
+            .centered()
+            .style(app.theme.default)
+        })
+        .collect();
+    Paragraph::new(rows)
+        .scroll((u16::try_from(app.results.scroll).unwrap_or(u16::MAX), 0))
+        .render(rows_area, buf);
+}
+
+/// Renders the History screen in its current view.
+fn render_history_body(area: Rect, buf: &mut Buffer, app: &App) {
+    match app.history.view {
+        HistoryView::Chart => render_history_chart(area, buf, app),
+        HistoryView::Missed => render_missed_words(area, buf, app),
+    }
+}
+
+/// Renders the words mistyped the most often across every test.
+fn render_missed_words(area: Rect, buf: &mut Buffer, app: &App) {
+    let mut lines = vec![
+        Line::from("Most Missed Words")
+            .centered()
+            .add_modifier(Modifier::BOLD),
+        Line::from(""),
+    ];
+
+    if app.history.missed.is_empty() {
+        lines.push(Line::styled("No mistyped words recorded yet.", app.theme.pending).centered());
+    } else {
+        let width = app
+            .history
+            .missed
+            .iter()
+            .map(|(word, _)| word.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        lines.extend(app.history.missed.iter().map(|(word, stat)| {
+            Line::from(vec![
+                Span::styled(format!("{:<width$}  ", word), app.theme.highlighted),
+                Span::styled(
+                    format!("{:>3} errors in {:>3} attempts", stat.errors, stat.attempts),
+                    app.theme.default,
+                ),
+            ])
+            .centered()
+        }));
+        lines.push(Line::from(""));
+        lines.push(
+            Line::styled(
+                "Press P to practice them in weak-words mode",
+                app.theme.pending,
+            )
+            .centered(),
+        );
+    }
+
+    Paragraph::new(lines).render(area, buf);
+}
+
+/// Renders the WPM chart of past tests, with its filters and a summary.
+fn render_history_chart(area: Rect, buf: &mut Buffer, app: &App) {
+    let now = Utc::now();
+    let records = app.history.filtered(now);
+
+    let layout = Layout::vertical([
+        Constraint::Length(2), // Filters
+        Constraint::Length(2), // Summary
+        Constraint::Min(10),   // WPM Chart
+    ])
+    .split(area);
+
+    Paragraph::new(Line::from(vec![
+        Span::styled("Mode: ", app.theme.default),
+        Span::styled(
+            format!("‹ {} ›", app.history.mode_label()),
+            app.theme.highlighted,
+        ),
+        Span::styled("   Range: ", app.theme.default),
+        Span::styled(
+            format!("‹ {} ›", app.history.range_label()),
+            app.theme.highlighted,
+        ),
+        Span::styled("   Tag: ", app.theme.default),
+        Span::styled(app.history.tag_label().to_string(), app.theme.highlighted),
+    ]))
+    .centered()
+    .render(layout[0], buf);
+
+    let (Some(first), Some(last)) = (records.first(), records.last()) else {
+        Paragraph::new(Line::from("No tests recorded for these filters.").centered())
+            .style(app.theme.pending)
+            .render(layout[1], buf);
+        return;
+    };
+
+    let average = records.iter().map(|r| r.wpm).sum::<f64>() / records.len() as f64;
+    let best = records
+        .iter()
+        .filter(|r| !r.invalid)
+        .map(|r| r.wpm)
+        .fold(0.0, f64::max);
+    Paragraph::new(
+        Line::from(format!(
+            "Tests: {}   Average: {:.1} WPM   Best: {:.1} WPM",
+            records.len(),
+            average,
+            best
+        ))
+        .centered()
+        .cyan(),
+    )
+    .render(layout[1], buf);
+
+    // Days since the first test shown
+    let days =
+        |record: &TestRecord| (record.timestamp - first.timestamp).num_seconds() as f64 / 86400.0;
+    let data: Vec<(f64, f64)> = records.iter().map(|r| (days(r), r.wpm)).collect();
+    let x_max = days(last).max(1.0);
+    let date = |days: f64| {
+        (first.timestamp + chrono::Duration::seconds((days * 86400.0) as i64))
+            .format("%Y-%m-%d")
+            .to_string()
+    };
+    let x_labels = [date(0.0), date(x_max / 2.0), date(x_max)];
+    let average = rolling_average(&data, app.chart.smoothing);
+
+    render_wpm_chart(
+        layout[2],
+        buf,
+        app,
+        &[
+            ChartLine::new("WPM", &data, app.theme.highlighted),
+            ChartLine::new("Average", &average, app.theme.correct),
+        ],
+        "Date",
+        x_max,
+        x_labels,
+    );
+}
+
+/// A line of a WPM chart.
+struct ChartLine<'a> {
+    name: &'static str,
+    data: &'a [(f64, f64)],
+    style: Style,
+    /// Whether the points are drawn as unconnected markers.
+    scatter: bool,
+}
+
+impl<'a> ChartLine<'a> {
+    fn new(name: &'static str, data: &'a [(f64, f64)], style: Style) -> Self {
+        Self {
+            name,
+            data,
+            style,
+            scatter: false,
+        }
+    }
+
+    fn scatter(mut self) -> Self {
+        self.scatter = true;
+        self
+    }
+}
+
+/// The value of the line going through `data` (sorted by x) at `x`.
+fn interpolate(data: &[(f64, f64)], x: f64) -> f64 {
+    let next = data.iter().position(|&(px, _)| px >= x);
+    match next {
+        Some(0) => data[0].1,
+        Some(i) => {
+            let ((x0, y0), (x1, y1)) = (data[i - 1], data[i]);
+            y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+        }
+        None => data.last().map_or(0.0, |&(_, y)| y),
+    }
+}
+
+/// Average of each point and the `window - 1` points before it.
+///
+/// Returns nothing when `window` is below 2, as there's nothing to smooth.
+fn rolling_average(data: &[(f64, f64)], window: usize) -> Vec<(f64, f64)> {
+    if window < 2 {
+        return Vec::new();
+    }
+
+    data.iter()
+        .enumerate()
+        .map(|(i, &(x, _))| {
+            let points = &data[(i + 1).saturating_sub(window)..=i];
+            let sum: f64 = points.iter().map(|(_, y)| y).sum();
+            (x, sum / points.len() as f64)
+        })
+        .collect()
+}
+
+/// Renders WPM lines on a chart starting at 0 on both axes, lines without
+/// data are left out.
+fn render_wpm_chart(
+    area: Rect,
+    buf: &mut Buffer,
+    app: &App,
+    lines: &[ChartLine],
+    x_title: &str,
+    x_max: f64,
+    x_labels: [String; 3],
+) {
+    let max_wpm = lines
+        .iter()
+        .flat_map(|line| line.data)
+        .map(|(_, wpm)| *wpm)
+        .fold(0.0, f64::max);
+    let y_max = max_wpm.max(10.0);
+
     let x_axis = Axis::default()
-        .title("Time".red())
+        .title(x_title.red())
         .style(app.theme.default)
         .bounds([0.0, x_max])
         .labels(x_labels);
@@ -248,26 +1138,79 @@ fn render_complete_body(area: Rect, buf: &mut Buffer, app: &App) {
         .bounds([0.0, y_max])
         .labels(y_labels);
 
-    let dataset = Dataset::default()
-        .name("WPM")
-        .marker(symbols::Marker::Braille)
-        .graph_type(GraphType::Line)
-        .style(app.theme.highlighted)
-        .data(&data);
+    let datasets = lines
+        .iter()
+        .filter(|line| !line.data.is_empty())
+        .map(|line| {
+            let (marker, graph_type) = if line.scatter {
+                (symbols::Marker::Dot, GraphType::Scatter)
+            } else {
+                (symbols::Marker::Braille, GraphType::Line)
+            };
+            Dataset::default()
+                .name(line.name)
+                .marker(marker)
+                .graph_type(graph_type)
+                .style(line.style)
+                .data(line.data)
+        })
+        .collect();
 
-    Chart::new(vec![dataset])
+    Chart::new(datasets)
         .x_axis(x_axis)
         .y_axis(y_axis)
-        .render(layout[1], buf);
+        .render(area, buf);
 }
 
-/// Renders key hints (global + mode-specific) in the footer.
-fn render_footer(area: Rect, buf: &mut Buffer, app: &App) {
+/// Keys of the current screen with what they do, mode-specific ones included.
+fn key_hints(app: &App) -> Vec<(&str, &str)> {
     let mut hints: Vec<(&str, &str)> = match app.state {
-        State::Home => vec![("ESC", "Quit"), ("← →", "Navigate"), ("ENTER", "Select")],
-        State::Running | State::Complete => vec![("TAB", "Restart"), ("ESC", "Quit")],
+        State::Splash => vec![("ANY KEY", "Start")],
+        State::Home if app.editing_mode.is_some() => {
+            vec![("ESC", "Close"), ("↑ ↓", "Mode"), ("ENTER", "Select")]
+        }
+        State::Home => vec![
+            ("ESC", "Quit"),
+            ("← →", "Navigate"),
+            ("ENTER", "Select"),
+            ("^S", "History"),
+        ],
+        State::History if app.history.view == HistoryView::Missed => vec![
+            ("TAB", "Back"),
+            ("ESC", "Quit"),
+            ("ENTER", "WPM chart"),
+            ("P", "Practice"),
+        ],
+        State::History => vec![
+            ("TAB", "Back"),
+            ("ESC", "Quit"),
+            ("← →", "Mode"),
+            ("↑ ↓", "Range"),
+            ("T", "Tag"),
+            ("ENTER", "Missed words"),
+            ("R", "Replay last"),
+        ],
+        State::Running if app.mode.captures_key(&app.behavior.restart_key.event()) => {
+            vec![("ESC", "Quit")]
+        }
+        State::Running | State::Complete => {
+            vec![
+                (app.behavior.restart_key.label(), "Restart"),
+                ("ESC", "Quit"),
+            ]
+        }
     };
 
+    if app.state == State::Complete {
+        hints.push(("← →", "Tabs"));
+        if matches!(app.results.tab, ResultsTab::Errors | ResultsTab::RawData) {
+            hints.push(("↑ ↓", "Scroll"));
+        }
+        if !app.missed_words().is_empty() {
+            hints.push(("R", "Retype missed"));
+        }
+    }
+
     // Add mode-specific hints
     hints.extend(
         app.mode
@@ -277,18 +1220,132 @@ fn render_footer(area: Rect, buf: &mut Buffer, app: &App) {
             .map(|hint| (hint.key, hint.description))
             .collect::<Vec<(&str, &str)>>(),
     );
+    hints
+}
+
+/// Every key of the current screen, the footer hints and the keys left out of
+/// the footer.
+fn help_bindings(app: &App) -> Vec<(&str, &str)> {
+    let mut bindings = key_hints(app);
+    if app.state == State::Home && app.editing_mode.is_none() {
+        bindings.extend([
+            ("↑ ↓", "Change option"),
+            ("SPACE", "Select"),
+            ("ANY KEY", "Start typing"),
+        ]);
+    }
+    bindings.push(("?", "Close help"));
+    bindings
+}
 
-    let spans: Vec<Span> = hints
+/// Renders the help overlay listing [`help_bindings`] over the screen.
+fn render_help(area: Rect, buf: &mut Buffer, app: &App) {
+    let bindings = help_bindings(app);
+    let key_width = bindings
         .iter()
-        .flat_map(|(key, desc)| {
-            vec![
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let lines: Vec<Line> = bindings
+        .iter()
+        .map(|(key, description)| {
+            Line::from(vec![
+                Span::styled(format!("{:>key_width$}  ", key), app.theme.highlighted),
+                Span::styled(*description, app.theme.default),
+            ])
+        })
+        .collect();
+
+    let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 6;
+    let height = lines.len() as u16 + 4;
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let block = Block::bordered()
+        .border_type(app.theme.border_type)
+        .border_style(app.theme.border_style)
+        .padding(Padding::symmetric(2, 1))
+        .title(Line::from(" Help ").centered())
+        .style(Style::default().bg(app.theme.background));
+
+    Clear.render(area, buf);
+    Paragraph::new(lines).block(block).render(area, buf);
+}
+
+/// Renders the footer with the key hints of the current screen.
+fn render_footer(area: Rect, buf: &mut Buffer, app: &App, targets: &mut Vec<(Rect, MouseTarget)>) {
+    let mut hints = key_hints(app);
+    if !matches!(app.state, State::Splash | State::Running) {
+        hints.push(("?", "Help"));
+    }
+
+    let buttons: Vec<Line> = hints
+        .iter()
+        .map(|(key, desc)| {
+            Line::from(vec![
                 Span::from(format!(" {} ", desc)),
                 Span::styled(format!("({})", key), app.theme.highlighted),
-            ]
+            ])
         })
         .collect();
 
-    Paragraph::new(Line::from(spans)).render(area, buf);
+    // Only the keys are listed when the descriptions don't fit
+    let buttons = if buttons.iter().map(Line::width).sum::<usize>() > area.width.into() {
+        hints
+            .iter()
+            .map(|(key, _)| Line::styled(format!(" {}", key), app.theme.highlighted))
+            .collect()
+    } else {
+        buttons
+    };
+
+    // Hints are buttons pressing their key
+    let mut x = area.x;
+    for ((key, _), button) in hints.iter().zip(&buttons) {
+        let width = button.width() as u16;
+        if let Some(key) = hint_key(key) {
+            targets.push((Rect::new(x, area.y, width, 1), MouseTarget::Key(key)));
+        }
+        x += width;
+    }
+
+    let line: Line = buttons
+        .into_iter()
+        .flat_map(|button| button.spans)
+        .collect();
+    Paragraph::new(line).render(area, buf);
+}
+
+/// Key of a footer hint, `None` for hints naming several keys (e.g. "← →").
+fn hint_key(label: &str) -> Option<KeyEvent> {
+    let code = match label {
+        "ESC" => KeyCode::Esc,
+        "TAB" => KeyCode::Tab,
+        "ENTER" => KeyCode::Enter,
+        "SPACE" => KeyCode::Char(' '),
+        "SHIFT+TAB" => KeyCode::BackTab,
+        "F5" => KeyCode::F(5),
+        _ => {
+            let (modifiers, key) = match label.strip_prefix('^') {
+                Some(key) => (KeyModifiers::CONTROL, key),
+                None => (KeyModifiers::NONE, label),
+            };
+            let mut chars = key.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_graphic() => Some(KeyEvent::new(
+                    KeyCode::Char(c.to_ascii_lowercase()),
+                    modifiers,
+                )),
+                _ => None,
+            };
+        }
+    };
+    Some(KeyEvent::new(code, KeyModifiers::NONE))
 }
 
 /// Capitalizes the first character of a string.
@@ -299,3 +1356,99 @@ fn capitalize(s: &str) -> String {
         Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_blinks_unless_disabled() {
+        assert!(cursor_shown(true, Duration::from_millis(200)));
+        assert!(!cursor_shown(true, Duration::from_millis(700)));
+        assert!(cursor_shown(true, Duration::from_millis(1200)));
+        assert!(cursor_shown(false, Duration::from_millis(700)));
+    }
+
+    #[test]
+    fn typing_area_is_narrowed_and_centered() {
+        let area = Rect::new(2, 1, 120, 10);
+
+        assert_eq!(centered_width(area, 80), Rect::new(22, 1, 80, 10));
+        assert_eq!(centered_width(area, 0), area);
+        assert_eq!(centered_width(area, 200), area);
+    }
+
+    #[test]
+    fn small_terminals_are_reported() {
+        assert_eq!(size_warning(Rect::new(0, 0, 80, 24)), None);
+        assert_eq!(
+            size_warning(Rect::new(0, 0, 40, 24)).as_deref(),
+            Some("Terminal too small (need 50x20)")
+        );
+        assert!(size_warning(Rect::new(0, 0, 80, 10)).is_some());
+    }
+
+    #[test]
+    fn status_line_names_mode_profile_and_best() {
+        let mode = Mode::Words {
+            count: 25,
+            text: "english".to_string(),
+        };
+
+        assert_eq!(
+            status_line(&mode, "default", Some(72.34)),
+            "Words · count 25 · text english · profile default · PB 72.3 WPM"
+        );
+        assert_eq!(
+            status_line(&Mode::Zen, "work", None),
+            "Zen · profile work · no PB yet"
+        );
+    }
+
+    #[test]
+    fn footer_hints_press_their_key() {
+        assert_eq!(hint_key("ESC"), Some(KeyEvent::from(KeyCode::Esc)));
+        assert_eq!(hint_key("R"), Some(KeyEvent::from(KeyCode::Char('r'))));
+        assert_eq!(
+            hint_key("^S"),
+            Some(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(hint_key("?"), Some(KeyEvent::from(KeyCode::Char('?'))));
+        assert_eq!(hint_key("← →"), None);
+        assert_eq!(hint_key("RELAX"), None);
+    }
+
+    #[test]
+    fn interpolate_follows_the_line() {
+        let data = [(0.0, 0.0), (2.0, 40.0), (4.0, 20.0)];
+
+        assert_eq!(interpolate(&data, 0.0), 0.0);
+        assert_eq!(interpolate(&data, 1.0), 20.0);
+        assert_eq!(interpolate(&data, 3.0), 30.0);
+        assert_eq!(interpolate(&data, 5.0), 20.0);
+        assert_eq!(interpolate(&[], 1.0), 0.0);
+    }
+
+    #[test]
+    fn sparkline_keeps_the_last_seconds() {
+        let data = [(0.0, 0.0), (10.0, 50.0), (40.0, 80.0)];
+
+        assert_eq!(sparkline_data(&data, 2.5), vec![0, 5, 10]);
+
+        let data = sparkline_data(&data, 40.0);
+        assert_eq!(data.len(), SPARKLINE_SECONDS);
+        assert_eq!(data.first(), Some(&51));
+        assert_eq!(data.last(), Some(&80));
+    }
+
+    #[test]
+    fn rolling_average_smooths_over_the_window() {
+        let data = [(0.0, 0.0), (1.0, 30.0), (2.0, 60.0), (3.0, 30.0)];
+
+        assert!(rolling_average(&data, 1).is_empty());
+        assert_eq!(
+            rolling_average(&data, 2),
+            vec![(0.0, 0.0), (1.0, 15.0), (2.0, 45.0), (3.0, 45.0)]
+        );
+    }
+}
@@ -3,17 +3,30 @@
 //! This module defines the application's theming system, allowing for
 //! customizable colors and styles across the UI.
 
+use std::{borrow::Cow, fmt, fs, path::PathBuf};
+
+use anyhow::{Context, Result, anyhow};
 use ratatui::{
     style::{Color, Modifier, Style},
     widgets::BorderType,
 };
-use serde::{Deserialize, Serialize};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, MapAccess, Visitor, value::MapAccessDeserializer},
+};
 
-use crate::app::ui::char::CharState;
+use crate::{Resource, app::ui::char::CharState, config_dir};
+
+/// Name of the built-in default theme.
+pub const DEFAULT_THEME: &str = "default";
 
 /// Theme configuration for consistent styling across the application.
+///
+/// Deserialized from a table of styles, or from the name of a built-in theme
+/// or user theme (e.g. `theme = "dracula"`), see [`Theme::load`].
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
-#[serde(default)]
+#[serde(default, remote = "Self")]
 pub struct Theme {
     #[serde(with = "serde_border")]
     pub border_type: BorderType,
@@ -38,6 +51,16 @@ pub struct Theme {
     #[serde(with = "serde_style")]
     pub extra: Style,
     #[serde(with = "serde_style")]
+    pub ghost: Style,
+    #[serde(with = "serde_style")]
+    pub pace: Style,
+    #[serde(with = "serde_style")]
+    pub typed: Style,
+    #[serde(with = "serde_style")]
+    pub timer: Style,
+    #[serde(with = "serde_style")]
+    pub personal_best: Style,
+    #[serde(with = "serde_style")]
     pub highlighted: Style,
     #[serde(with = "serde_style")]
     pub selected: Style,
@@ -62,6 +85,13 @@ impl Default for Theme {
                 .underline_color(Color::Red),
             extra: Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
             cursor: Style::new().bg(Color::White).fg(Color::DarkGray),
+            ghost: Style::new().bg(Color::DarkGray).fg(Color::White),
+            pace: Style::new()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::UNDERLINED),
+            typed: Style::new().add_modifier(Modifier::DIM),
+            timer: Style::new().fg(Color::Magenta),
+            personal_best: Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
             highlighted: Style::new().fg(Color::Magenta).add_modifier(Modifier::BOLD),
             selected: Style::new()
                 .fg(Color::Yellow)
@@ -71,7 +101,171 @@ impl Default for Theme {
     }
 }
 
+impl Serialize for Theme {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Theme::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Theme {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ThemeVisitor;
+
+        impl<'de> Visitor<'de> for ThemeVisitor {
+            type Value = Theme;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a theme name or a table of styles")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<Theme, E> {
+                Theme::load(name).map_err(|error| E::custom(format!("{:#}", error)))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Theme, A::Error> {
+                Theme::deserialize(MapAccessDeserializer::new(map))
+            }
+        }
+
+        deserializer.deserialize_any(ThemeVisitor)
+    }
+}
+
+impl JsonSchema for Theme {
+    fn schema_name() -> Cow<'static, str> {
+        "Theme".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        // The keys of a theme table, taken from the default theme
+        let defaults = serde_json::to_value(Theme::default()).unwrap_or_default();
+        let properties: serde_json::Map<String, serde_json::Value> = defaults
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(key, default)| {
+                let schema = match key.as_str() {
+                    "border_type" => json_schema!({
+                        "enum": ["plain", "rounded", "double", "thick"],
+                    }),
+                    "background" => json_schema!({
+                        "type": "string",
+                        "description": "A color: a name (e.g. `dark_gray`), `#RRGGBB` or 0-255",
+                    }),
+                    _ => json_schema!({
+                        "type": "string",
+                        "description": "Space-separated style properties, e.g. `fg:red bold`",
+                    }),
+                };
+                let mut schema = schema.to_value();
+                schema["default"] = default.clone();
+                (key.clone(), schema)
+            })
+            .collect();
+
+        json_schema!({
+            "description": "A theme name, or a table of styles",
+            "anyOf": [
+                {
+                    "type": "string",
+                    "description": "Name of a built-in theme or of a file of the themes directory",
+                    "examples": Theme::names(),
+                },
+                {
+                    "type": "object",
+                    "properties": properties,
+                    "additionalProperties": false,
+                },
+            ],
+        })
+    }
+}
+
+/// Where a theme is loaded from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThemeSource {
+    /// Embedded under `res/themes/`.
+    BuiltIn,
+    /// A file of the user's `themes/` directory.
+    User,
+}
+
+/// Returns the directory user themes are stored in.
+pub fn themes_dir() -> Option<PathBuf> {
+    Some(config_dir()?.join("themes"))
+}
+
+/// Returns the path of the user theme `name`, if there is one.
+fn user_theme_path(name: &str) -> Option<PathBuf> {
+    // Names can't point outside of the themes directory
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return None;
+    }
+    Some(themes_dir()?.join(format!("{}.toml", name))).filter(|path| path.is_file())
+}
+
 impl Theme {
+    /// Names of the available themes with their source, sorted. A user theme
+    /// replaces the built-in theme of the same name.
+    pub fn list() -> Vec<(String, ThemeSource)> {
+        let mut themes: Vec<(String, ThemeSource)> = Resource::iter()
+            .filter_map(|path| {
+                let name = path.strip_prefix("themes/")?.strip_suffix(".toml")?;
+                Some((name.to_string(), ThemeSource::BuiltIn))
+            })
+            .collect();
+        themes.push((DEFAULT_THEME.to_string(), ThemeSource::BuiltIn));
+
+        if let Some(dir) = themes_dir()
+            && let Ok(entries) = fs::read_dir(dir)
+        {
+            for entry in entries.flatten() {
+                let file_name = entry.file_name();
+                if let Some(name) = file_name
+                    .to_str()
+                    .and_then(|name| name.strip_suffix(".toml"))
+                    && user_theme_path(name).is_some()
+                {
+                    themes.retain(|(other, _)| other != name);
+                    themes.push((name.to_string(), ThemeSource::User));
+                }
+            }
+        }
+
+        themes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        themes
+    }
+
+    /// Names of the available themes, sorted.
+    pub fn names() -> Vec<String> {
+        Self::list().into_iter().map(|(name, _)| name).collect()
+    }
+
+    /// Loads the theme `name`: `themes/<name>.toml` from the config directory
+    /// if it exists, or the built-in theme embedded under `res/themes/`.
+    pub fn load(name: &str) -> Result<Self> {
+        if let Some(path) = user_theme_path(name) {
+            let content = fs::read_to_string(&path)
+                .context(format!("Couldn't read theme {}", path.display()))?;
+            return toml::from_str(&content)
+                .context(format!("Invalid theme file {}", path.display()));
+        }
+
+        if name == DEFAULT_THEME {
+            return Ok(Self::default());
+        }
+
+        let file = Resource::get(&format!("themes/{}.toml", name)).ok_or_else(|| {
+            anyhow!(
+                "Unknown theme \"{}\", expected one of: {}",
+                name,
+                Self::names().join(", ")
+            )
+        })?;
+        let content = std::str::from_utf8(&file.data)?;
+        Ok(toml::from_str(content)?)
+    }
+
     /// Convert a [`CharState`] to its corresponding Style.
     pub fn style_for(&self, state: CharState) -> Style {
         match state {
@@ -82,6 +276,8 @@ impl Theme {
             CharState::Skipped => self.skipped,
             CharState::Extra => self.extra,
             CharState::Cursor => self.cursor,
+            CharState::Ghost => self.ghost,
+            CharState::Pace => self.pace,
         }
     }
 }
@@ -370,4 +566,42 @@ mod tests {
             toml::from_str(toml_str).expect("Failed to deserialize background color");
         assert_eq!(theme.background, Color::Blue);
     }
+
+    #[test]
+    fn test_built_in_themes() {
+        let presets = Theme::names();
+        for name in [
+            "catppuccin",
+            "colorblind",
+            "default",
+            "dracula",
+            "gruvbox",
+            "high-contrast",
+            "nord",
+            "solarized",
+        ] {
+            assert!(presets.contains(&name.to_string()));
+        }
+
+        // Mistakes aren't told apart by color only
+        for name in ["colorblind", "high-contrast"] {
+            let theme = Theme::load(name).unwrap();
+            assert!(theme.incorrect.add_modifier.contains(Modifier::UNDERLINED));
+            assert!(theme.extra.add_modifier.contains(Modifier::ITALIC));
+        }
+
+        for name in &presets {
+            let theme = Theme::load(name).expect("Failed to load theme");
+            let named: Theme = toml::Value::String(name.clone())
+                .try_into()
+                .expect("Failed to deserialize theme name");
+            assert_eq!(named, theme);
+        }
+        assert_eq!(
+            Theme::load("dracula").unwrap().background,
+            Color::Rgb(0x28, 0x2a, 0x36)
+        );
+        assert!(Theme::load("unknown").is_err());
+        assert!(Theme::load("../config").is_err());
+    }
 }
@@ -0,0 +1,135 @@
+//! # Timer Module
+//!
+//! This module draws the time left of a timed test as a shrinking bar or an
+//! emptying braille arc, see [`TimerStyle`].
+
+use std::time::Duration;
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+use crate::config::TimerStyle;
+
+/// Cells of the [`TimerStyle::Bar`] timer.
+const BAR_WIDTH: usize = 20;
+/// Dots of a braille cell going clockwise from the top left, for the
+/// [`TimerStyle::Arc`] timer.
+const ARC_DOTS: [u32; 8] = [0x01, 0x08, 0x10, 0x20, 0x80, 0x40, 0x04, 0x02];
+
+/// Time left of a test, drawn in the `timer` style of the theme. The numeric
+/// timer draws nothing, the seconds are part of the progress line.
+pub struct Timer {
+    remaining: Duration,
+    total: Duration,
+    kind: TimerStyle,
+    style: Style,
+}
+
+impl Timer {
+    pub fn new(remaining: Duration, total: Duration, kind: TimerStyle) -> Self {
+        Self {
+            remaining,
+            total,
+            kind,
+            style: Style::default(),
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Cells taken by the timer.
+    pub fn width(&self) -> u16 {
+        self.symbols().chars().count() as u16
+    }
+
+    /// Share of the time left, from 1 at the start to 0.
+    fn ratio(&self) -> f64 {
+        if self.total.is_zero() {
+            return 0.0;
+        }
+        (self.remaining.as_secs_f64() / self.total.as_secs_f64()).clamp(0.0, 1.0)
+    }
+
+    fn symbols(&self) -> String {
+        match self.kind {
+            TimerStyle::Numeric => String::new(),
+            TimerStyle::Bar => {
+                // Eighths of a cell, for a smooth shrink
+                let eighths = (self.ratio() * (BAR_WIDTH * 8) as f64).ceil() as usize;
+                let full = eighths / 8;
+                let partial = match eighths % 8 {
+                    0 => String::new(),
+                    rest => ['▏', '▎', '▍', '▌', '▋', '▊', '▉'][rest - 1].to_string(),
+                };
+                let empty = BAR_WIDTH - full - partial.chars().count();
+                format!("{}{}{}", "█".repeat(full), partial, " ".repeat(empty))
+            }
+            TimerStyle::Arc => {
+                // The dots go out clockwise from the top
+                let lit = (self.ratio() * ARC_DOTS.len() as f64).ceil() as usize;
+                let bits: u32 = ARC_DOTS[ARC_DOTS.len() - lit..].iter().sum();
+                char::from_u32(0x2800 + bits).unwrap_or(' ').to_string()
+            }
+        }
+    }
+}
+
+impl Widget for Timer {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_stringn(
+            area.x,
+            area.y,
+            self.symbols(),
+            area.width as usize,
+            self.style,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timer_styles_show_the_time_left() {
+        let timer = |remaining, kind| {
+            Timer::new(
+                Duration::from_secs(remaining),
+                Duration::from_secs(30),
+                kind,
+            )
+            .symbols()
+        };
+
+        assert_eq!(timer(15, TimerStyle::Numeric), "");
+        assert_eq!(
+            timer(15, TimerStyle::Bar),
+            format!("{}{}", "█".repeat(10), " ".repeat(10))
+        );
+        assert_eq!(timer(30, TimerStyle::Bar), "█".repeat(20));
+        assert_eq!(timer(0, TimerStyle::Bar), " ".repeat(20));
+        assert_eq!(timer(30, TimerStyle::Arc), "⣿");
+        assert_eq!(timer(15, TimerStyle::Arc), "⣆");
+        assert_eq!(timer(0, TimerStyle::Arc), "⠀");
+    }
+
+    #[test]
+    fn renders_in_its_style() {
+        let area = Rect::new(0, 0, 25, 1);
+        let mut buf = Buffer::empty(area);
+        let style = Style::new().fg(ratatui::style::Color::Cyan);
+        let timer = Timer::new(
+            Duration::from_secs(15),
+            Duration::from_secs(30),
+            TimerStyle::Bar,
+        )
+        .style(style);
+        assert_eq!(timer.width(), 20);
+
+        timer.render(area, &mut buf);
+        assert_eq!(buf[(0, 0)].symbol(), "█");
+        assert_eq!(buf[(0, 0)].fg, ratatui::style::Color::Cyan);
+    }
+}
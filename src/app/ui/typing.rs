@@ -0,0 +1,227 @@
+//! # Typing Area Module
+//!
+//! This module lays out the characters of the typing area in lines, wrapping
+//! between words, and keeps track of where the cursor ends up.
+
+use std::time::Duration;
+
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use crate::app::ui::char::{CharState, StyledChar};
+
+/// Columns a tab is expanded to.
+const TAB_WIDTH: usize = 4;
+
+/// Lines of the typing area, and the position of the cursor in them.
+#[derive(Debug, Default)]
+pub struct TypingLayout {
+    pub lines: Vec<Line<'static>>,
+    /// Column and line of the cursor, if the text has one.
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl TypingLayout {
+    /// Lays out `chars` in lines of at most `width` columns, styled by `style`.
+    ///
+    /// Lines are wrapped before the word that doesn't fit, words longer than a
+    /// line are split. Newlines are shown as `↵` and break the line, tabs are
+    /// expanded to [`TAB_WIDTH`] columns.
+    pub fn new(chars: &[StyledChar], width: usize, style: impl Fn(CharState) -> Style) -> Self {
+        let width = width.max(1);
+        let mut layout = Self {
+            lines: vec![Line::default()],
+            cursor: None,
+        };
+        let mut column = 0;
+
+        for (i, sc) in chars.iter().enumerate() {
+            let (text, columns) = match sc.symbol.as_str() {
+                "\n" => ("↵".to_string(), 1),
+                "\t" => (" ".repeat(TAB_WIDTH), TAB_WIDTH),
+                symbol => (symbol.to_string(), Span::raw(symbol).width()),
+            };
+
+            // Wrap before a word that doesn't fit, or a character of a word too
+            // long to fit on its own line
+            let starts_word = !is_separator(&sc.symbol)
+                && chars[..i]
+                    .last()
+                    .is_none_or(|previous| is_separator(&previous.symbol));
+            let needed = if starts_word {
+                chars[i..]
+                    .iter()
+                    .take_while(|sc| !is_separator(&sc.symbol))
+                    .map(|sc| Span::raw(sc.symbol.as_str()).width())
+                    .sum::<usize>()
+                    .min(width)
+            } else {
+                columns
+            };
+            if column > 0 && column + needed > width {
+                layout.lines.push(Line::default());
+                column = 0;
+            }
+
+            if sc.state == CharState::Cursor {
+                layout.cursor = Some((column, layout.lines.len() - 1));
+            }
+            let line = layout.lines.last_mut().expect("lines is never empty");
+            line.push_span(Span::styled(text, style(sc.state)));
+            column += columns;
+
+            if sc.symbol == "\n" {
+                layout.lines.push(Line::default());
+                column = 0;
+            }
+        }
+
+        layout
+    }
+
+    /// Patches the characters of the words before the cursor's with `style`,
+    /// so the eye stays on the words to type.
+    pub fn dim_typed(&mut self, chars: &[StyledChar], style: Style) {
+        let Some(cursor) = chars.iter().position(|sc| sc.state == CharState::Cursor) else {
+            return;
+        };
+        let typed = chars[..cursor]
+            .iter()
+            .rposition(|sc| is_separator(&sc.symbol))
+            .map_or(0, |separator| separator + 1);
+
+        // Every character is a span of its own, in order
+        for span in self
+            .lines
+            .iter_mut()
+            .flat_map(|line| line.spans.iter_mut())
+            .take(typed)
+        {
+            span.style = span.style.patch(style);
+        }
+    }
+
+    /// Keeps the `visible` lines around the cursor, which stays on the middle
+    /// line once it's past the first lines. Without a cursor, the first lines
+    /// are kept.
+    pub fn scroll(&mut self, visible: usize) {
+        let first = self.cursor.map_or(0, |(_, line)| {
+            line.saturating_sub(visible.saturating_sub(1) / 2)
+        });
+
+        self.lines = self.lines.drain(..).skip(first).take(visible).collect();
+        if let Some((_, line)) = &mut self.cursor {
+            *line -= first;
+        }
+    }
+}
+
+/// Marks the character reached after `elapsed` at `wpm` as the pace caret.
+///
+/// Extra characters aren't part of the text and aren't counted, the caret
+/// isn't drawn over the cursor or the ghost caret.
+pub fn mark_pace(chars: &mut [StyledChar], wpm: f64, elapsed: Duration) {
+    let position = (wpm * 5.0 * elapsed.as_secs_f64() / 60.0) as usize;
+
+    if let Some(sc) = chars
+        .iter_mut()
+        .filter(|sc| sc.state != CharState::Extra)
+        .nth(position)
+        && !matches!(sc.state, CharState::Cursor | CharState::Ghost)
+    {
+        sc.state = CharState::Pace;
+    }
+}
+
+fn is_separator(symbol: &str) -> bool {
+    matches!(symbol, " " | "\t" | "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Stylize;
+
+    use super::*;
+
+    fn chars(text: &str, cursor: usize) -> Vec<StyledChar> {
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let state = if i == cursor {
+                    CharState::Cursor
+                } else {
+                    CharState::Pending
+                };
+                StyledChar::new(c, state)
+            })
+            .collect()
+    }
+
+    fn text(layout: &TypingLayout) -> Vec<String> {
+        layout.lines.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn wraps_between_words() {
+        let layout = TypingLayout::new(&chars("the quick brown fox", 10), 10, |_| Style::new());
+
+        assert_eq!(text(&layout), ["the quick ", "brown fox"]);
+        assert_eq!(layout.cursor, Some((0, 1)));
+    }
+
+    #[test]
+    fn scrolls_to_keep_the_cursor_on_the_middle_line() {
+        let words = "aa bb cc dd ee";
+
+        let mut layout = TypingLayout::new(&chars(words, 0), 3, |_| Style::new());
+        layout.scroll(3);
+        assert_eq!(text(&layout), ["aa ", "bb ", "cc "]);
+        assert_eq!(layout.cursor, Some((0, 0)));
+
+        let mut layout = TypingLayout::new(&chars(words, 9), 3, |_| Style::new());
+        layout.scroll(3);
+        assert_eq!(text(&layout), ["cc ", "dd ", "ee"]);
+        assert_eq!(layout.cursor, Some((0, 1)));
+    }
+
+    #[test]
+    fn dims_the_words_before_the_cursor() {
+        let words = chars("ab cd ef", 4);
+        let mut layout = TypingLayout::new(&words, 4, |_| Style::new());
+        layout.dim_typed(&words, Style::new().dim());
+
+        let dimmed: Vec<bool> = layout
+            .lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .map(|span| span.style == Style::new().dim())
+            .collect();
+        assert_eq!(
+            dimmed,
+            [true, true, true, false, false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn pace_caret_skips_extra_characters() {
+        let mut chars = chars("ab cd", 0);
+        chars.insert(1, StyledChar::new('x', CharState::Extra));
+
+        // 60 WPM is a character every 200ms
+        mark_pace(&mut chars, 60.0, Duration::from_millis(700));
+        assert!(chars[4].state == CharState::Pace);
+
+        mark_pace(&mut chars, 60.0, Duration::ZERO);
+        assert!(chars[0].state == CharState::Cursor);
+    }
+
+    #[test]
+    fn splits_long_words_and_breaks_lines() {
+        let layout = TypingLayout::new(&chars("a abcdefgh\nb", 11), 4, |_| Style::new());
+
+        assert_eq!(text(&layout), ["a ", "abcd", "efgh", "↵", "b"]);
+        assert_eq!(layout.cursor, Some((0, 4)));
+    }
+}
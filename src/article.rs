@@ -0,0 +1,176 @@
+//! # Article Module
+//!
+//! This module downloads web pages (e.g. Wikipedia articles) and turns their
+//! HTML into plain paragraphs, typed in the `copy` mode. Downloads need the
+//! `articles` feature.
+
+#[cfg(feature = "articles")]
+use anyhow::Context;
+use anyhow::{Result, bail};
+
+/// Articles are cut after the paragraph reaching this many characters.
+#[cfg(feature = "articles")]
+const MAX_LENGTH: usize = 1200;
+
+/// Paragraphs with fewer words are left out (captions, navigation...).
+#[cfg(feature = "articles")]
+const MIN_PARAGRAPH_WORDS: usize = 8;
+
+/// Elements whose content is never typed (navigation, tables, footnote
+/// references...), hidden when rendering the page.
+#[cfg(feature = "articles")]
+const HIDDEN: &str = "nav, footer, table, sup, figure { display: none; }";
+
+/// Width the page is rendered at, wide enough for paragraphs not to wrap.
+#[cfg(feature = "articles")]
+const RENDER_WIDTH: usize = 100_000;
+
+/// A downloaded article.
+#[derive(Debug, PartialEq)]
+pub struct Article {
+    /// Title of the page, if it has one.
+    pub title: Option<String>,
+    /// Paragraphs of the article, separated by blank lines.
+    pub text: String,
+}
+
+/// Downloads the page at `url` and extracts its article.
+#[cfg(feature = "articles")]
+pub fn fetch(url: &str) -> Result<Article> {
+    let html = ureq::get(url)
+        .header("User-Agent", concat!("ttt/", env!("CARGO_PKG_VERSION")))
+        .call()
+        .context(format!("Couldn't download \"{}\"", url))?
+        .body_mut()
+        .read_to_string()
+        .context(format!("Couldn't read \"{}\"", url))?;
+
+    let article = parse(&html).context(format!("Couldn't read \"{}\"", url))?;
+    if article.text.is_empty() {
+        bail!("Couldn't find any article text at \"{}\"", url);
+    }
+    Ok(article)
+}
+
+/// Downloading articles needs the `articles` feature.
+#[cfg(not(feature = "articles"))]
+pub fn fetch(url: &str) -> Result<Article> {
+    bail!(
+        "Couldn't download \"{}\", ttt was built without the \"articles\" feature",
+        url
+    )
+}
+
+/// Extracts the title and paragraphs of an HTML page.
+///
+/// Markup, scripts, tables and footnote references (`[1]`) are removed, and
+/// typographic characters replaced by their keyboard equivalent. Text is cut
+/// after [`MAX_LENGTH`] characters, at the end of a paragraph.
+#[cfg(feature = "articles")]
+pub fn parse(html: &str) -> Result<Article> {
+    let render = |html: &str| {
+        html2text::config::plain_no_decorate()
+            .add_css(HIDDEN)?
+            .string_from_read(html.as_bytes(), RENDER_WIDTH)
+    };
+
+    let title = match html.find("<title").zip(html.find("</title>")) {
+        Some((start, end)) if start < end => {
+            let title = &html[start..end];
+            let title = title.find('>').map_or("", |open| &title[open + 1..]);
+            Some(clean(&render(&format!("<p>{}</p>", title))?))
+        }
+        _ => None,
+    };
+
+    let mut text = String::new();
+    for paragraph in render(html)?.split("\n\n").map(clean) {
+        if paragraph.split_whitespace().count() < MIN_PARAGRAPH_WORDS {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        text.push_str(&paragraph);
+        if text.chars().count() >= MAX_LENGTH {
+            break;
+        }
+    }
+
+    Ok(Article {
+        title: title.filter(|title| !title.is_empty()),
+        text,
+    })
+}
+
+/// Removes footnote references, replaces typographic characters and
+/// collapses whitespace.
+#[cfg(feature = "articles")]
+fn clean(text: &str) -> String {
+    let mut cleaned = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        cleaned.push_str(&rest[..start]);
+        match rest[start..].find(']') {
+            // Footnotes ([1], [citation needed]...) are short
+            Some(end) if end <= 20 => rest = &rest[start + end + 1..],
+            _ => {
+                cleaned.push('[');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    cleaned.push_str(rest);
+
+    cleaned
+        .chars()
+        .map(|c| match c {
+            '‘' | '’' => "'".to_string(),
+            '“' | '”' => "\"".to_string(),
+            '–' | '—' => "-".to_string(),
+            '…' => "...".to_string(),
+            c => c.to_string(),
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+#[cfg(all(test, feature = "articles"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_paragraphs() {
+        let html = r#"<html><head><title>Typing &amp; Keyboards</title>
+            <style>p { color: red; }</style></head>
+            <body><nav>Main page Contents Random article</nav>
+            <p>The <b>QWERTY</b> layout was designed for typewriters<sup>[1]</sup> in the
+            1870s, and is still the most common layout today.[citation needed]</p>
+            <table><tr><td>Not typed at all, even though it is long enough</td></tr></table>
+            <p>Short caption.</p>
+            <p>Touch typists don&#39;t look at the keyboard &#x2014; they rely on
+            muscle memory... and “practice”.</p></body></html>"#;
+
+        let article = parse(html).unwrap();
+
+        assert_eq!(article.title.as_deref(), Some("Typing & Keyboards"));
+        assert_eq!(
+            article.text,
+            "The QWERTY layout was designed for typewriters in the 1870s, and is still the \
+             most common layout today.\n\nTouch typists don't look at the keyboard - they rely \
+             on muscle memory... and \"practice\"."
+        );
+    }
+
+    #[test]
+    fn cuts_long_articles() {
+        let paragraph = format!("<p>{}</p>", "word ".repeat(100));
+        let article = parse(&paragraph.repeat(10)).unwrap();
+
+        let paragraphs = article.text.split("\n\n").count();
+        assert_eq!(paragraphs, 3);
+        assert_eq!(article.title, None);
+    }
+}
@@ -5,74 +5,627 @@
 
 use std::path::PathBuf;
 
-use clap::Parser;
-use directories::ProjectDirs;
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use clap::{
+    ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand,
+    builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+    parser::ValueSource,
+};
+use clap_complete::Shell;
+use strum::VariantNames;
 
-use crate::{app::modes::Mode, config::Config};
+use crate::{
+    Resource,
+    app::{modes::Mode, ui::theme::Theme},
+    config::{Config, Generator},
+    export::ExportFormat,
+    profile,
+};
 
 #[derive(Parser)]
 #[command(version, about = "A simple Terminal Typing Test utility.", long_about = None)]
 pub struct Args {
-    /// The game mode to use
+    /// A command, `run` when omitted
     #[command(subcommand)]
-    mode: Option<Mode>,
+    command: Option<Command>,
 
     /// Read config from file
     #[arg(short, long)]
     config: Option<PathBuf>,
 
-    /// Save config, applies overrides provided by other arguments
-    #[arg(short, long, default_value_t = false)]
+    /// Keep the config, texts, themes and history in this directory
+    #[arg(long, global = true, env = "TTT_CONFIG_DIR", value_name = "PATH")]
+    config_dir: Option<PathBuf>,
+
+    /// Ignore unknown config keys, and use default settings if the config file is invalid
+    #[arg(long, default_value_t = false)]
+    lenient_config: bool,
+
+    /// Tag the tests of this session, or only include tagged tests in `stats`
+    #[arg(long, global = true)]
+    tag: Option<String>,
+
+    /// Use the config and history of a profile
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Deprecated, use `ttt config save`
+    #[arg(short, long, default_value_t = false, hide = true)]
     save_config: bool,
 
+    /// Options of the typing test started without a command
+    #[command(flatten, next_help_heading = "Run Options")]
+    run: RunOptions,
+
+    /// Options of the game mode given on the command line, as opposed to
+    /// their default values, see [`Args::parse_args`].
+    #[arg(skip)]
+    given_options: Vec<String>,
+}
+
+/// Options of a typing test, overriding the settings of the config.
+#[derive(clap::Args, Default)]
+pub struct RunOptions {
     /// Use default settings
     #[arg(long, default_value_t = false)]
     defaults: bool,
+
+    /// Type an unscored warm-up round of this many words before the first test
+    #[arg(short, long, value_name = "WORDS")]
+    warmup: Option<usize>,
+
+    /// Only type words of at least this many characters
+    #[arg(long, value_name = "LENGTH")]
+    min_word_len: Option<usize>,
+
+    /// Only type words of at most this many characters
+    #[arg(long, value_name = "LENGTH")]
+    max_word_len: Option<usize>,
+
+    /// Leave out words with non-ASCII characters, such as accents
+    #[arg(long, default_value_t = false)]
+    ascii_only: bool,
+
+    /// How words are generated in word list modes: shuffle or markov
+    #[arg(long, hide_possible_values = true)]
+    generator: Option<Generator>,
+
+    /// Use a theme by name: a built-in one (dracula, nord...) or one of the themes directory
+    #[arg(long, hide_possible_values = true, value_parser = PossibleValuesParser::new(Theme::names()))]
+    theme: Option<String>,
+
+    /// Seed the random words, to get the same words on every run
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Start on the Home screen, without the logo
+    #[arg(long, default_value_t = false)]
+    no_splash: bool,
+
+    /// Type a line in the terminal instead of using the UI, also used when the output isn't a terminal
+    #[arg(long, default_value_t = false)]
+    plain: bool,
+
+    /// Print the stats of the last test as JSON on exit
+    #[arg(long, default_value_t = false)]
+    json: bool,
+}
+
+impl RunOptions {
+    /// Applies the options on top of `config`.
+    fn apply(&self, config: &mut Config) -> Result<()> {
+        if let Some(warmup) = self.warmup {
+            config.defaults.warmup = warmup;
+        }
+
+        if let Some(generator) = self.generator {
+            config.defaults.generator = generator;
+        }
+
+        if let Some(theme) = &self.theme {
+            config.theme = Theme::load(theme)?;
+        }
+
+        if let Some(seed) = self.seed {
+            config.defaults.seed = Some(seed);
+        }
+
+        if let Some(min) = self.min_word_len {
+            config.filter.min_word_len = Some(min);
+        }
+        if let Some(max) = self.max_word_len {
+            config.filter.max_word_len = Some(max);
+        }
+        if self.ascii_only {
+            config.filter.ascii_only = true;
+        }
+        if self.no_splash {
+            config.ui.splash = false;
+        }
+
+        Ok(())
+    }
+}
+
+/// A typing test: its options, and the game mode to start with.
+#[derive(clap::Args, Default)]
+#[command(subcommand_value_name = "MODE", subcommand_help_heading = "Modes")]
+pub struct Run {
+    #[command(flatten)]
+    options: RunOptions,
+
+    /// The game mode to start with, the one of the config when omitted
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+/// Subcommands: a typing test, or a command running without the UI.
+#[derive(Subcommand)]
+pub enum Command {
+    /// Start a typing test, in a game mode
+    Run(Run),
+    /// Print statistics from the history of completed tests
+    Stats {
+        /// Show per-key accuracy and latency
+        #[arg(long, default_value_t = false, conflicts_with = "errors")]
+        keys: bool,
+
+        /// Show the words mistyped the most often
+        #[arg(long, default_value_t = false)]
+        errors: bool,
+    },
+    /// Print the history of completed tests, one row per test
+    Export {
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+
+        /// Only export tests completed on or after this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        since: Option<NaiveDate>,
+    },
+    /// Import results from another typing tool into the history
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+    /// Play back a recorded test
+    Replay {
+        /// Replay file, as saved under the replays directory
+        file: PathBuf,
+    },
+    /// Manage and inspect word lists
+    Texts {
+        #[command(subcommand)]
+        action: TextsAction,
+    },
+    /// List the themes
+    Theme {
+        #[command(subcommand)]
+        action: ThemeAction,
+    },
+    /// Show, edit or save the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Manage configuration profiles, each with its own config and history
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+    /// Print a shell completion script
+    Completions {
+        /// Shell to complete commands in
+        shell: Shell,
+    },
+    /// Game modes given without `run`, as before it existed
+    #[command(flatten)]
+    Legacy(LegacyMode),
+}
+
+/// A game mode given as a command of its own (`ttt clock -d 60`), hidden and
+/// forwarded to `run`, see [`Args::parse_args`].
+pub struct LegacyMode(Mode);
+
+impl FromArgMatches for LegacyMode {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        Mode::from_arg_matches(matches).map(Self)
+    }
+
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+        self.0.update_from_arg_matches(matches)
+    }
+}
+
+impl Subcommand for LegacyMode {
+    fn augment_subcommands(cmd: clap::Command) -> clap::Command {
+        Mode::VARIANTS
+            .iter()
+            .fold(Mode::augment_subcommands(cmd), |cmd, name| {
+                cmd.mut_subcommand(name, |mode| mode.hide(true))
+            })
+    }
+
+    fn augment_subcommands_for_update(cmd: clap::Command) -> clap::Command {
+        Self::augment_subcommands(cmd)
+    }
+
+    fn has_subcommand(name: &str) -> bool {
+        Mode::has_subcommand(name)
+    }
+}
+
+/// Options of the game mode of `matches` (given to `run`, `config save`...)
+/// that were set on the command line.
+fn given_options(matches: &ArgMatches) -> Vec<String> {
+    match matches.subcommand() {
+        Some((name, mode)) if Mode::has_subcommand(name) => mode
+            .ids()
+            .filter(|id| mode.value_source(id.as_str()) == Some(ValueSource::CommandLine))
+            .map(ToString::to_string)
+            .collect(),
+        Some((_, command)) => given_options(command),
+        None => Vec::new(),
+    }
+}
+
+/// Parses `--text` values, offering the available word lists to shell
+/// completions.
+///
+/// Any name is accepted, texts are looked up when the mode starts.
+#[derive(Clone)]
+pub struct TextParser;
+
+impl TypedValueParser for TextParser {
+    type Value = String;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<String, clap::Error> {
+        clap::builder::StringValueParser::new().parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            Resource::word_lists().into_iter().map(PossibleValue::new),
+        ))
+    }
+}
+
+/// Changes to the user texts directory.
+#[derive(Subcommand)]
+pub enum TextsAction {
+    /// Copy a word list into the texts directory, one word per line
+    Add {
+        /// Path of the word list, or prose to split into words
+        file: PathBuf,
+
+        /// Name to use the text with, defaults to the file name without extension
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Replace an existing text of the same name
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+    },
+    /// List the word lists, with their language and recommended modes
+    List,
+    /// Print words generated from a text with the current options (generator,
+    /// word filter, seed...)
+    Preview {
+        /// Name of the text
+        #[arg(value_parser = TextParser)]
+        name: String,
+
+        /// Amount of words to print
+        #[arg(short, long, default_value_t = 50)]
+        count: usize,
+    },
+    /// Delete a text from the texts directory
+    Remove {
+        /// Name of the text
+        name: String,
+    },
+}
+
+/// Commands about themes.
+#[derive(Subcommand)]
+pub enum ThemeAction {
+    /// List the built-in themes and the themes of the themes directory
+    List,
+}
+
+/// Commands about the config file.
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the settings in use, overrides applied
+    Show,
+    /// Open the config file in $VISUAL or $EDITOR, and check it once closed
+    Edit,
+    /// Print the path of the config file
+    Path,
+    /// Save the settings in use, with the options and game mode of a run
+    Save(Run),
+    /// Print a JSON Schema of the config file, for editor completion and validation
+    Schema,
+}
+
+/// Changes to the configuration profiles.
+#[derive(Subcommand)]
+pub enum ProfileAction {
+    /// List the profiles, marking the active one
+    List,
+    /// Create a profile from the current settings
+    Create {
+        /// Name of the profile
+        name: String,
+
+        /// Replace an existing profile of the same name
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+    },
+    /// Use a profile by default, when `--profile` isn't given
+    Switch {
+        /// Name of the profile, `default` for the default one
+        name: String,
+    },
+}
+
+/// Typing tools results can be imported from.
+#[derive(Subcommand)]
+pub enum ImportSource {
+    /// Results exported from a Monkeytype account (JSON)
+    Monkeytype {
+        /// Path of the exported results
+        file: PathBuf,
+    },
 }
 
 impl Args {
+    /// Parses the command line like [`Parser::parse`], forwarding the forms of
+    /// older versions (`ttt clock -d 60`, `--save-config`) to `run` and
+    /// `config save`.
+    pub fn parse_args() -> Self {
+        let matches = <Self as CommandFactory>::command().get_matches();
+        let mut args = Self::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+        args.given_options = given_options(&matches);
+        args.forward_legacy()
+    }
+
+    /// See [`Args::parse_args`].
+    fn forward_legacy(mut self) -> Self {
+        if let Some(Command::Legacy(LegacyMode(mode))) = self
+            .command
+            .take_if(|command| matches!(command, Command::Legacy(_)))
+        {
+            eprintln!(
+                "Note: `ttt {0}` is deprecated, use `ttt run {0}`",
+                mode.name()
+            );
+            self.command = Some(Command::Run(Run {
+                options: RunOptions::default(),
+                mode: Some(mode),
+            }));
+        }
+
+        if self.save_config {
+            eprintln!("Note: `--save-config` is deprecated, use `ttt config save`");
+            let run = match self.command.take() {
+                Some(Command::Run(run)) => run,
+                None => Run::default(),
+                other => {
+                    self.command = other;
+                    return self;
+                }
+            };
+            self.command = Some(Command::Config {
+                action: ConfigAction::Save(run),
+            });
+        }
+        self
+    }
+
     /// Resolves the final application configuration.
     ///
     /// It loads configuration from a provided path, the default user config
-    /// directory, or falls back to system defaults. CLI arguments are then
-    /// applied as overrides.
-    pub fn get_config(&self) -> Config {
-        let mut config: Config = match &self.config {
+    /// directory, or falls back to system defaults. `TTT_*` environment
+    /// variables (see [`Config::apply_env`]), then CLI arguments are applied
+    /// as overrides.
+    ///
+    /// Invalid config files and unknown keys are errors, unless
+    /// `--lenient-config` is given: the default config is used instead of an
+    /// invalid file, and unknown keys are ignored.
+    pub fn get_config(&self) -> Result<Config> {
+        let path = self.config_path().filter(|path| path.exists());
+
+        let mut config = match path {
             Some(path) => {
-                let content = std::fs::read_to_string(path).expect("Couldn't read config content");
-                toml::from_str(&content).unwrap_or_default()
-            }
-            _ => {
-                if let Some(config_dir) = self.config_dir()
-                    && let Ok(config_str) = std::fs::read_to_string(config_dir.join("config.toml"))
-                {
-                    toml::from_str(&config_str).unwrap_or_default()
-                } else {
-                    Config::default()
+                let content = std::fs::read_to_string(&path)
+                    .context(format!("Couldn't read config file {}", path.display()))?;
+                match Config::load(&content, !self.lenient_config) {
+                    Ok((config, notes)) => {
+                        for note in notes {
+                            eprintln!(
+                                "Note: {} in {}, use `ttt config save` to update the file",
+                                note,
+                                path.display()
+                            );
+                        }
+                        config
+                    }
+                    Err(_) if self.lenient_config => Config::default(),
+                    Err(error) => bail!(
+                        "Invalid config file {}:\n{}\n\nFix it, or use --lenient-config to ignore errors",
+                        path.display(),
+                        error.to_string().trim_end()
+                    ),
                 }
             }
+            None => Config::default(),
         };
 
-        if let Some(mode) = &self.mode {
+        let mode = self.run().and_then(|run| run.mode.as_ref());
+        if let Some(mode) = mode {
+            config.modes.remove(mode.name());
             config.defaults.mode = mode.clone();
         }
 
-        config
+        // The mode and options given on the command line take precedence
+        let mut given: Vec<&str> = self.given_options.iter().map(String::as_str).collect();
+        if mode.is_some() {
+            given.push("mode");
+        }
+        // Variables that aren't valid Unicode can't be settings, they are skipped
+        config.apply_env(
+            std::env::vars_os().filter_map(|(key, value)| {
+                Some((key.into_string().ok()?, value.into_string().ok()?))
+            }),
+            &given,
+        )?;
+
+        for options in self.run_options() {
+            options.apply(&mut config)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Returns the path of the config file: the one given with `--config`, or
+    /// the one of the active profile.
+    pub fn config_path(&self) -> Option<PathBuf> {
+        match &self.config {
+            Some(path) => Some(path.clone()),
+            None => self
+                .config_dir()
+                .map(|dir| dir.join(profile::config_file(profile::active()))),
+        }
+    }
+
+    /// Returns the requested command, if it isn't `run`.
+    pub fn command(&self) -> Option<&Command> {
+        self.command
+            .as_ref()
+            .filter(|command| !matches!(command, Command::Run(_) | Command::Legacy(_)))
+    }
+
+    /// Returns the typing test given with `run` or `config save`, if any.
+    fn run(&self) -> Option<&Run> {
+        match &self.command {
+            Some(Command::Run(run))
+            | Some(Command::Config {
+                action: ConfigAction::Save(run),
+            }) => Some(run),
+            _ => None,
+        }
+    }
+
+    /// Returns the options given before the command, then those given to
+    /// `run` (or `config save`), which take precedence.
+    fn run_options(&self) -> impl Iterator<Item = &RunOptions> {
+        std::iter::once(&self.run).chain(self.run().map(|run| &run.options))
+    }
+
+    /// Returns true if the user requested the stats of the last test as JSON.
+    pub fn json(&self) -> bool {
+        self.run_options().any(|options| options.json)
+    }
+
+    /// Returns true if the test should be typed as a line, without the UI.
+    pub fn plain(&self) -> bool {
+        self.run_options().any(|options| options.plain)
+    }
+
+    /// Returns the tag of this session, if any.
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
     }
 
-    /// Returns true if the user requested to save the current configuration.
-    pub fn should_save(&self) -> bool {
-        self.save_config
+    /// Returns the profile of this session: the one given with `--profile`,
+    /// or the one selected with `ttt profile switch`.
+    pub fn profile(&self) -> Option<String> {
+        self.profile.clone().or_else(profile::selected)
+    }
+
+    /// Returns true if the command can run with a profile that doesn't exist
+    /// yet: saving its settings creates it, and profiles are managed by name.
+    pub fn creates_profile(&self) -> bool {
+        matches!(
+            self.command,
+            Some(Command::Config {
+                action: ConfigAction::Save(_)
+            }) | Some(Command::Profile { .. })
+        )
     }
 
     /// Returns true if the user requested to ignore config files and use defaults.
     pub fn use_defaults(&self) -> bool {
-        self.defaults
+        self.run_options().any(|options| options.defaults)
+    }
+
+    /// Returns the directory given with `--config-dir`, if any.
+    pub fn base_dir(&self) -> Option<&PathBuf> {
+        self.config_dir.as_ref()
     }
 
-    /// Returns the platform-specific configuration directory for TTT.
+    /// Returns the configuration directory for TTT, see [`crate::config_dir`].
     pub fn config_dir(&self) -> Option<PathBuf> {
-        let project_dir = ProjectDirs::from("com", "semanavasco", "ttt")?;
-        Some(project_dir.config_dir().to_path_buf())
+        crate::config_dir()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn arguments_do_not_clash() {
+        <Args as CommandFactory>::command().debug_assert();
+    }
+
+    #[test]
+    fn run_options_are_read_before_and_after_run() {
+        let args = Args::parse_from(["ttt", "--seed", "1", "run", "--warmup", "5", "clock"]);
+
+        assert!(args.command().is_none());
+        assert_eq!(args.run.seed, Some(1));
+        assert_eq!(args.run().map(|run| run.options.warmup), Some(Some(5)));
+        assert!(matches!(
+            args.run().and_then(|run| run.mode.as_ref()),
+            Some(Mode::Clock { .. })
+        ));
+    }
+
+    #[test]
+    fn legacy_forms_are_forwarded() {
+        let args = Args::parse_from(["ttt", "clock", "-d", "60"]).forward_legacy();
+        assert!(args.command().is_none());
+        assert!(matches!(
+            args.run().and_then(|run| run.mode.as_ref()),
+            Some(Mode::Clock { duration: 60, .. })
+        ));
+
+        let args = Args::parse_from(["ttt", "-s", "words", "-c", "10"]).forward_legacy();
+        assert!(matches!(
+            args.command(),
+            Some(Command::Config {
+                action: ConfigAction::Save(Run {
+                    mode: Some(Mode::Words { count: 10, .. }),
+                    ..
+                })
+            })
+        ));
+
+        // Not listed in the help
+        let help = <Args as CommandFactory>::command()
+            .render_help()
+            .to_string();
+        assert!(!help.contains("clock"));
     }
 }
@@ -3,26 +3,1101 @@
 //! This module defines the application's configuration schema, handling
 //! serialization and deserialization of user preferences.
 
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Subcommand, ValueEnum};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use strum::VariantNames;
+use toml::de::{DeTable, DeValue};
+use toml_edit::{DocumentMut, Item};
+
+use crate::{
+    app::{modes::Mode, ui::theme::Theme},
+    texts::TextMeta,
+};
+
+/// Current version of the config schema, stored in the `version` key.
+pub const CONFIG_VERSION: u32 = 1;
 
-use crate::app::{modes::Mode, ui::theme::Theme};
+/// Upgrades of older config files, the migration at index `i` upgrades
+/// version `i` to `i + 1`. Each returns notes on what it changed.
+const MIGRATIONS: [fn(&mut toml::Table) -> Vec<String>; 1] = [move_word_count];
+
+/// Prefix of the environment variables overriding settings, see
+/// [`Config::apply_env`].
+const ENV_PREFIX: &str = "TTT_";
+
+/// Environment variables that aren't mode options, `TTT_CONFIG_DIR` being read
+/// by the command line parser.
+const ENV_SETTINGS: [&str; 6] = ["mode", "theme", "seed", "warmup", "generator", "config_dir"];
 
 /// The root configuration object.
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct Config {
+    /// Version of the config schema, see [`CONFIG_VERSION`].
+    #[serde(default = "current_version")]
+    pub version: u32,
+
     #[serde(default)]
     pub defaults: Defaults,
 
     #[serde(default)]
     pub theme: Theme,
+
+    #[serde(default)]
+    pub goal: Goal,
+
+    #[serde(default)]
+    pub chart: ChartOptions,
+
+    #[serde(default)]
+    pub filter: WordFilter,
+
+    #[serde(default)]
+    pub words: WordRules,
+
+    #[serde(default)]
+    pub ui: UiOptions,
+
+    #[serde(default)]
+    pub behavior: Behavior,
+
+    /// Settings of each mode, e.g. `[modes.clock]`, used when switching to the
+    /// mode.
+    #[serde(
+        default,
+        with = "mode_tables",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    #[schemars(schema_with = "mode_tables::schema")]
+    pub modes: BTreeMap<String, Mode>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            defaults: Defaults::default(),
+            theme: Theme::default(),
+            goal: Goal::default(),
+            chart: ChartOptions::default(),
+            filter: WordFilter::default(),
+            words: WordRules::default(),
+            ui: UiOptions::default(),
+            behavior: Behavior::default(),
+            modes: BTreeMap::new(),
+        }
+    }
+}
+
+fn current_version() -> u32 {
+    CONFIG_VERSION
+}
+
+impl Config {
+    /// Upgrades the content of a config file written for an older version to
+    /// the current schema. Returns the upgraded content, and notes on what
+    /// changed.
+    ///
+    /// Content that isn't valid TOML, or that doesn't need any change, is
+    /// returned unchanged.
+    pub fn migrate(content: &str) -> (Cow<'_, str>, Vec<String>) {
+        let Ok(mut table) = content.parse::<toml::Table>() else {
+            return (Cow::Borrowed(content), vec![]);
+        };
+
+        // Files without a version predate versioning
+        let version = match table.get("version") {
+            Some(version) => match version.as_integer().map(usize::try_from) {
+                Some(Ok(version)) => version,
+                _ => return (Cow::Borrowed(content), vec![]),
+            },
+            None => 0,
+        };
+        if version >= MIGRATIONS.len() {
+            return (Cow::Borrowed(content), vec![]);
+        }
+
+        let notes: Vec<String> = MIGRATIONS[version..]
+            .iter()
+            .flat_map(|migration| migration(&mut table))
+            .collect();
+        // Kept as written, so errors point at the user's lines
+        if notes.is_empty() {
+            return (Cow::Borrowed(content), notes);
+        }
+        table.insert(
+            "version".to_string(),
+            toml::Value::Integer(CONFIG_VERSION.into()),
+        );
+
+        match toml::to_string(&table) {
+            Ok(migrated) => (Cow::Owned(migrated), notes),
+            Err(_) => (Cow::Borrowed(content), vec![]),
+        }
+    }
+
+    /// Parses the content of a config file.
+    ///
+    /// When `strict`, keys TTT doesn't use (typos, options of another mode...)
+    /// are errors too, reported with their line.
+    pub fn parse(content: &str, strict: bool) -> Result<Self> {
+        let config: Config = toml::from_str(content)?;
+        if strict {
+            config.check(content, |_| true)?;
+        }
+        Ok(config)
+    }
+
+    /// Parses the content of a config file like [`Config::parse`], upgrading
+    /// it first if it was written for an older version (see
+    /// [`Config::migrate`]). Returns notes on what the upgrade changed.
+    ///
+    /// Errors point at the lines of `content` as written. An upgrade that
+    /// doesn't give a valid config is left out, and the file is parsed as is.
+    pub fn load(content: &str, strict: bool) -> Result<(Self, Vec<String>)> {
+        let (migrated, notes) = Self::migrate(content);
+        let upgraded = match notes.is_empty() {
+            true => None,
+            false => toml::from_str::<Config>(&migrated)
+                .ok()
+                .zip(migrated.parse::<toml::Table>().ok()),
+        };
+
+        let Some((config, table)) = upgraded else {
+            let config = Self::parse(content, false)?.with_text_mode(content);
+            if strict {
+                config.check(content, |_| true)?;
+            }
+            return Ok((config, vec![]));
+        };
+        let config = config.with_text_mode(&migrated);
+        if strict {
+            // Keys moved by the upgrade aren't in it anymore
+            config.check(content, |key| has_key(&table, key))?;
+        }
+        Ok((config, notes))
+    }
+
+    /// Starts a `[defaults]` table of `content` setting a `text` without a
+    /// `mode` in the mode recommended by the metadata of the text, see
+    /// [`text_mode`].
+    fn with_text_mode(mut self, content: &str) -> Self {
+        let Some(defaults) = content
+            .parse::<toml::Table>()
+            .ok()
+            .and_then(|table| table.get("defaults")?.as_table().cloned())
+            .filter(|defaults| !defaults.contains_key("mode"))
+        else {
+            return self;
+        };
+
+        // A text that can't be found is reported when the mode starts
+        let meta = defaults
+            .get("text")
+            .and_then(toml::Value::as_str)
+            .and_then(|text| TextMeta::load(text).ok())
+            .unwrap_or_default();
+        if let Some(mode) = text_mode(defaults, &meta) {
+            self.defaults.mode = mode;
+        }
+        self
+    }
+
+    /// Fails when the config is for a newer version, or when `content` (that
+    /// the config was parsed from) has keys TTT doesn't use among those
+    /// accepted by `reported`.
+    fn check(&self, content: &str, reported: impl Fn(&str) -> bool) -> Result<()> {
+        if self.version > CONFIG_VERSION {
+            bail!(
+                "config version {} is newer than the supported version {}, update TTT",
+                self.version,
+                CONFIG_VERSION
+            );
+        }
+
+        let keys: Vec<String> = unknown_keys(content, self)?
+            .iter()
+            .filter(|(key, _)| reported(key))
+            .map(|(key, line)| format!("unknown key `{}` at line {}", key, line))
+            .collect();
+        if !keys.is_empty() {
+            bail!("{}", keys.join("\n"));
+        }
+        Ok(())
+    }
+
+    /// Settings of the mode `name`: its `[modes.<name>]` table, the mode of
+    /// `[defaults]` if it is the same one, or the mode's defaults otherwise.
+    pub fn mode(&self, name: &str) -> Mode {
+        match self.modes.get(name) {
+            Some(mode) => mode.clone(),
+            None if self.defaults.mode.name() == name => self.defaults.mode.clone(),
+            None => Mode::default_for(name),
+        }
+    }
+
+    /// Settings of the mode to start with, see [`Config::mode`].
+    pub fn start_mode(&self) -> Mode {
+        self.mode(self.defaults.mode.name())
+    }
+
+    /// Saves the config to `path`.
+    ///
+    /// An existing file keeps its comments, formatting and unknown keys, only
+    /// the settings are updated, see [`merge_into`]. The file is replaced in
+    /// one step, so it is never left half written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string(self).context("Couldn't serialize config")?;
+        let content = match fs::read_to_string(path) {
+            Ok(existing) => merge_into(&existing, &content).unwrap_or(content),
+            Err(_) => content,
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Couldn't create config directory")?;
+        }
+        write_atomic(path, &content).context("Couldn't save config")
+    }
+
+    /// JSON Schema of the config file, for editors to complete and check it.
+    pub fn schema() -> serde_json::Value {
+        schemars::schema_for!(Config).to_value()
+    }
+
+    /// Applies the `TTT_*` variables of `vars` (the environment) on top of
+    /// the config.
+    ///
+    /// `TTT_MODE` picks the mode to start with, and the options of that mode
+    /// are set by their name (`TTT_DURATION`, `TTT_TEXT`...). `TTT_THEME`,
+    /// `TTT_SEED`, `TTT_WARMUP` and `TTT_GENERATOR` set the matching settings.
+    /// Options of other modes are ignored, names that are neither options nor
+    /// settings are errors.
+    ///
+    /// Variables named in `given` (e.g. `mode`, `duration`) were set on the
+    /// command line, which takes precedence, they are skipped.
+    pub fn apply_env(
+        &mut self,
+        vars: impl IntoIterator<Item = (String, String)>,
+        given: &[&str],
+    ) -> Result<()> {
+        let vars: BTreeMap<String, String> = vars
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.strip_prefix(ENV_PREFIX)?.to_lowercase(), value)))
+            .filter(|(key, _)| !given.contains(&key.as_str()))
+            .collect();
+
+        let modes = Mode::augment_subcommands(clap::Command::new("modes"));
+        let options = |name: &str| -> Vec<String> {
+            modes
+                .find_subcommand(name)
+                .into_iter()
+                .flat_map(clap::Command::get_arguments)
+                .map(|arg| arg.get_id().to_string())
+                .collect()
+        };
+        let all_options: Vec<String> = Mode::VARIANTS
+            .iter()
+            .flat_map(|name| options(name))
+            .collect();
+        if let Some(key) = vars
+            .keys()
+            .find(|key| !ENV_SETTINGS.contains(&key.as_str()) && !all_options.contains(key))
+        {
+            bail!("Unknown variable {}{}", ENV_PREFIX, key.to_uppercase());
+        }
+
+        let mode = match vars.get("mode") {
+            Some(name) if Mode::VARIANTS.contains(&name.as_str()) => self.mode(name),
+            Some(name) => bail!(
+                "Invalid TTT_MODE \"{}\", expected one of: {}",
+                name,
+                Mode::VARIANTS.join(", ")
+            ),
+            None => self.start_mode(),
+        };
+
+        let mut table = toml::Table::try_from(&mode).context("Couldn't serialize mode")?;
+        let mut changed = vars.contains_key("mode");
+        for key in options(mode.name()) {
+            let Some(value) = vars.get(&key) else {
+                continue;
+            };
+            // Unset options (`None`) aren't serialized, their type is unknown
+            let parsed = match table.get(&key) {
+                Some(current) => env_value(current, value),
+                None => Some(env_value_of_unset(value)),
+            };
+            let parsed = parsed.ok_or_else(|| {
+                anyhow!("Invalid {}{} \"{}\"", ENV_PREFIX, key.to_uppercase(), value)
+            })?;
+            table.insert(key, parsed);
+            changed = true;
+        }
+        if changed {
+            let mode = Mode::deserialize(toml::Value::Table(table))
+                .map_err(|error| anyhow!("Invalid TTT_ variable: {}", error))?;
+            self.modes.remove(mode.name());
+            self.defaults.mode = mode;
+        }
+
+        if let Some(theme) = vars.get("theme") {
+            self.theme = Theme::load(theme)?;
+        }
+        if let Some(seed) = vars.get("seed") {
+            self.defaults.seed = Some(seed.parse().context("Invalid TTT_SEED")?);
+        }
+        if let Some(warmup) = vars.get("warmup") {
+            self.defaults.warmup = warmup.parse().context("Invalid TTT_WARMUP")?;
+        }
+        if let Some(generator) = vars.get("generator") {
+            self.defaults.generator = Generator::from_str(generator, true)
+                .map_err(|error| anyhow!("Invalid TTT_GENERATOR: {}", error))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses an environment variable into a value of the type of `current`.
+fn env_value(current: &toml::Value, value: &str) -> Option<toml::Value> {
+    match current {
+        toml::Value::String(_) => Some(toml::Value::String(value.to_string())),
+        toml::Value::Integer(_) => value.parse().ok().map(toml::Value::Integer),
+        toml::Value::Float(_) => value.parse().ok().map(toml::Value::Float),
+        toml::Value::Boolean(_) => value.parse().ok().map(toml::Value::Boolean),
+        // Arrays and tables are written as TOML, e.g. `[40, 60, 80]`
+        _ => toml::from_str::<toml::Table>(&format!("value = {}", value))
+            .ok()?
+            .remove("value"),
+    }
+}
+
+/// Parses an environment variable setting an option without a value: as TOML
+/// if it is valid TOML (a number, an array...), as a string otherwise.
+fn env_value_of_unset(value: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", value))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+/// Writes the settings of `new` (a serialized config) into the config file
+/// `existing`, keeping its comments and formatting. Returns `None` if either
+/// isn't valid TOML.
+///
+/// Only settings that changed are written, the others are kept as written
+/// (e.g. `theme = "nord"`) or left out. Keys of `existing` that `new` doesn't
+/// have are removed when they are settings (e.g. the table of a mode whose
+/// settings were moved to `[defaults]`), and kept when the config doesn't
+/// know them.
+fn merge_into(existing: &str, new: &str) -> Option<String> {
+    let (existing, _) = Config::migrate(existing);
+    // Serialized like `new`, so unchanged settings compare equal
+    let known = Config::parse(&existing, false)
+        .ok()
+        .and_then(|config| toml::to_string(&config).ok())
+        .and_then(|known| known.parse::<DocumentMut>().ok());
+
+    // Comments heading the file (e.g. a `#:schema` directive) stay on top
+    let header_length: usize = existing
+        .split_inclusive('\n')
+        .take_while(|line| line.trim().is_empty() || line.trim_start().starts_with('#'))
+        .map(str::len)
+        .sum();
+    let (header, body) = existing.split_at(header_length);
+
+    let mut document = body.parse::<DocumentMut>().ok()?;
+    let new = new.parse::<DocumentMut>().ok()?;
+    merge_tables(
+        document.as_table_mut(),
+        new.as_table(),
+        known.as_ref().map(DocumentMut::as_table),
+    );
+    Some(format!("{}{}", header, document))
+}
+
+/// Writes the items of `new` into `existing`, see [`merge_into`]. `known` is
+/// the matching table of the settings read from `existing`.
+fn merge_tables(
+    existing: &mut toml_edit::Table,
+    new: &toml_edit::Table,
+    known: Option<&toml_edit::Table>,
+) {
+    let removed: Vec<String> = existing
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !new.contains_key(key) && known.is_some_and(|known| known.contains_key(key)))
+        .collect();
+    for key in removed {
+        existing.remove(&key);
+    }
+
+    for (key, item) in new.iter() {
+        let known = known.and_then(|known| known.get(key));
+        match (existing.get_mut(key), item) {
+            (Some(Item::Table(table)), Item::Table(new_table)) => {
+                merge_tables(table, new_table, known.and_then(Item::as_table));
+            }
+            _ if known.is_some_and(|known| known.to_string() == item.to_string()) => {}
+            (Some(Item::Value(value)), Item::Value(new_value)) => {
+                let decor = value.decor().clone();
+                *value = new_value.clone();
+                *value.decor_mut() = decor;
+            }
+            _ => {
+                existing.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+/// Writes `content` to a temporary file next to `path`, then moves it in
+/// place.
+///
+/// A symlinked `path` (e.g. by a dotfile manager) stays a link, the file it
+/// points to is replaced.
+fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    let path = &fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+
+    let result = fs::File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Whether `table` has the dotted `key`, e.g. `defaults.count`.
+fn has_key(table: &toml::Table, key: &str) -> bool {
+    let mut value = None;
+    let mut table = Some(table);
+    for part in key.split('.') {
+        value = table.and_then(|table| table.get(part));
+        table = value.and_then(toml::Value::as_table);
+    }
+    value.is_some()
+}
+
+/// Version 0 stored the word count of the words mode in `defaults.words`,
+/// moved to `count` (or `[modes.words]` when another mode is the default).
+fn move_word_count(table: &mut toml::Table) -> Vec<String> {
+    let Some(defaults) = table
+        .get_mut("defaults")
+        .and_then(toml::Value::as_table_mut)
+    else {
+        return vec![];
+    };
+    let Some(count) = defaults.remove("words") else {
+        return vec![];
+    };
+
+    let mode = match defaults.get("mode") {
+        Some(mode) => mode.as_str(),
+        None => Some(Mode::default().name()),
+    };
+    if mode == Some("words") {
+        if defaults.contains_key("count") {
+            return vec!["removed `defaults.words`, replaced by `defaults.count`".to_string()];
+        }
+        defaults.insert("count".to_string(), count);
+        return vec!["moved `defaults.words` to `defaults.count`".to_string()];
+    }
+
+    let modes = table
+        .entry("modes")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    let Some(words) = modes
+        .as_table_mut()
+        .map(|modes| {
+            modes
+                .entry("words")
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        })
+        .and_then(toml::Value::as_table_mut)
+    else {
+        return vec![];
+    };
+    if words.contains_key("count") {
+        return vec!["removed `defaults.words`, replaced by `modes.words.count`".to_string()];
+    }
+    words.insert("count".to_string(), count);
+    vec!["moved `defaults.words` to `modes.words.count`".to_string()]
+}
+
+/// [`Mode`]s serializer/deserializer, as tables named after the mode without
+/// their `mode` key.
+mod mode_tables {
+    use std::collections::BTreeMap;
+
+    use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+    use serde::{Deserialize, Deserializer, Serializer, de, ser};
+    use serde_json::Value;
+
+    use crate::app::modes::Mode;
+
+    pub fn serialize<S>(modes: &BTreeMap<String, Mode>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tables = BTreeMap::new();
+        for (name, mode) in modes {
+            let mut table = toml::Table::try_from(mode).map_err(ser::Error::custom)?;
+            table.remove("mode");
+            tables.insert(name, table);
+        }
+        serializer.collect_map(tables)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BTreeMap<String, Mode>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let tables = BTreeMap::<String, toml::Table>::deserialize(deserializer)?;
+
+        let mut modes = BTreeMap::new();
+        for (name, mut table) in tables {
+            table.insert("mode".to_string(), toml::Value::String(name.clone()));
+            let mode = Mode::deserialize(toml::Value::Table(table))
+                .map_err(|error| de::Error::custom(format!("[modes.{}]: {}", name, error)))?;
+            modes.insert(name, mode);
+        }
+        Ok(modes)
+    }
+
+    /// Schema of the `[modes]` tables: the settings of each mode, without
+    /// the `mode` key.
+    pub fn schema(generator: &mut SchemaGenerator) -> Schema {
+        let mode = Mode::json_schema(generator);
+        let mut properties = serde_json::Map::new();
+
+        for variant in mode
+            .get("oneOf")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let mut variant = variant.clone();
+            let Some(name) = variant.pointer_mut("/properties/mode").and_then(|tag| {
+                tag.get("const")
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string)
+            }) else {
+                continue;
+            };
+            if let Some(properties) = variant.get_mut("properties").and_then(Value::as_object_mut) {
+                properties.remove("mode");
+            }
+            if let Some(object) = variant.as_object_mut() {
+                object.remove("required");
+            }
+            properties.insert(name, variant);
+        }
+
+        json_schema!({
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": false,
+        })
+    }
+}
+
+/// [`Mode`] deserializer for `[defaults]`, falling back to the default mode
+/// when the `mode` key is left out (e.g. a table only setting `seed`).
+mod default_mode {
+    use schemars::{JsonSchema, Schema, SchemaGenerator};
+    use serde::{Deserialize, Deserializer, de};
+    use serde_json::Value;
+
+    use crate::app::modes::Mode;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Mode, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut table = toml::Table::deserialize(deserializer)?;
+        table
+            .entry("mode")
+            .or_insert_with(|| toml::Value::String(Mode::default().name().to_string()));
+        Mode::deserialize(toml::Value::Table(table)).map_err(de::Error::custom)
+    }
+
+    /// Schema of the modes, where the default mode's `mode` key is optional.
+    pub fn schema(generator: &mut SchemaGenerator) -> Schema {
+        let mut schema = Mode::json_schema(generator);
+        let default = Mode::default();
+
+        for variant in schema
+            .get_mut("oneOf")
+            .and_then(Value::as_array_mut)
+            .into_iter()
+            .flatten()
+        {
+            if variant
+                .pointer("/properties/mode/const")
+                .and_then(Value::as_str)
+                == Some(default.name())
+                && let Some(required) = variant.get_mut("required").and_then(Value::as_array_mut)
+            {
+                required.retain(|key| key != "mode");
+            }
+        }
+        schema
+    }
+}
+
+/// Mode of a `[defaults]` table without a `mode` key, when `meta` (the
+/// metadata of its text) recommends another mode than the default one, see
+/// [`TextMeta::preferred_mode`].
+fn text_mode(mut defaults: toml::Table, meta: &TextMeta) -> Option<Mode> {
+    let name = meta.preferred_mode(Mode::default().name())?;
+    defaults.insert("mode".to_string(), toml::Value::String(name.to_string()));
+    toml::Value::Table(defaults).try_into().ok()
+}
+
+/// Returns the keys of `content` (dotted, e.g. `defaults.duration`) and their
+/// line, that `config` doesn't have once serialized back. `config` must have
+/// been parsed from `content`.
+fn unknown_keys(content: &str, config: &Config) -> Result<Vec<(String, usize)>> {
+    let known = toml::Table::try_from(config)?;
+    let table = DeTable::parse(content)?;
+
+    let mut unknown = Vec::new();
+    collect_unknown_keys(table.get_ref(), &known, "", content, &mut unknown);
+    Ok(unknown)
+}
+
+fn collect_unknown_keys(
+    table: &DeTable,
+    known: &toml::Table,
+    prefix: &str,
+    content: &str,
+    unknown: &mut Vec<(String, usize)>,
+) {
+    for (key, value) in table {
+        let path = format!("{}{}", prefix, key.get_ref());
+        match (known.get(key.get_ref().as_ref()), value.get_ref()) {
+            (None, _) => {
+                let line = content[..key.span().start].matches('\n').count() + 1;
+                unknown.push((path, line));
+            }
+            (Some(toml::Value::Table(known)), DeValue::Table(table)) => {
+                collect_unknown_keys(table, known, &format!("{}.", path), content, unknown);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Daily practice goal, either target can be left out.
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Debug, PartialEq)]
+pub struct Goal {
+    /// Tests to complete each day.
+    pub tests: Option<usize>,
+    /// Words to type each day.
+    pub words: Option<usize>,
+}
+
+/// Restricts the words drawn from texts, e.g. to practice long words only.
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Debug, PartialEq)]
+pub struct WordFilter {
+    /// Shortest word length, in characters.
+    pub min_word_len: Option<usize>,
+    /// Longest word length, in characters.
+    pub max_word_len: Option<usize>,
+    /// Whether to leave out words with non-ASCII characters (accents...).
+    #[serde(default)]
+    pub ascii_only: bool,
+}
+
+impl WordFilter {
+    /// Whether `word` passes the filter.
+    pub fn accepts(&self, word: &str) -> bool {
+        let len = word.chars().count();
+        self.min_word_len.is_none_or(|min| len >= min)
+            && self.max_word_len.is_none_or(|max| len <= max)
+            && (!self.ascii_only || word.is_ascii())
+    }
+}
+
+/// Words to leave out of every text, or the only ones to keep.
+///
+/// Words are compared ignoring case.
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Debug, PartialEq)]
+pub struct WordRules {
+    /// Words never drawn, e.g. proper nouns.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// When not empty, only these words are drawn.
+    #[serde(default)]
+    pub include_only: Vec<String>,
+}
+
+impl WordRules {
+    /// Whether `word` passes the rules.
+    pub fn accepts(&self, word: &str) -> bool {
+        let matches = |other: &String| {
+            other
+                .chars()
+                .flat_map(char::to_lowercase)
+                .eq(word.chars().flat_map(char::to_lowercase))
+        };
+
+        !self.exclude.iter().any(matches)
+            && (self.include_only.is_empty() || self.include_only.iter().any(matches))
+    }
+
+    /// Whether every word of `text` passes the rules, ignoring the
+    /// punctuation around them.
+    pub fn accepts_text(&self, text: &str) -> bool {
+        text.split_whitespace()
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|word| !word.is_empty())
+            .all(|word| self.accepts(word))
+    }
+}
+
+/// Lines drawn on the WPM charts.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct ChartOptions {
+    /// Amount of points averaged by the smoothed line, 0 or 1 hides it.
+    #[serde(default = "default_smoothing")]
+    pub smoothing: usize,
+    /// Whether to draw the raw WPM of each second on the results chart.
+    #[serde(default)]
+    pub raw: bool,
+}
+
+impl Default for ChartOptions {
+    fn default() -> Self {
+        Self {
+            smoothing: default_smoothing(),
+            raw: false,
+        }
+    }
+}
+
+fn default_smoothing() -> usize {
+    5
+}
+
+/// How typing tests react to input.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct Behavior {
+    /// Whether mistakes prevent moving on.
+    #[serde(default)]
+    pub stop_on_error: StopOnError,
+    /// Whether backspace can go back into correct words.
+    #[serde(default)]
+    pub backspace: Backspace,
+    /// Key abandoning the test in progress for a new one.
+    #[serde(default)]
+    pub restart_key: RestartKey,
+    /// Whether the restart key restarts right away, otherwise it has to be
+    /// pressed twice.
+    #[serde(default = "default_true")]
+    pub quick_restart: bool,
+    /// Accuracy (in percent) below which a test is invalid, and doesn't count
+    /// towards personal bests. 0 accepts every test.
+    #[serde(default)]
+    pub min_accuracy: f64,
+}
+
+impl Default for Behavior {
+    fn default() -> Self {
+        Self {
+            stop_on_error: StopOnError::default(),
+            backspace: Backspace::default(),
+            restart_key: RestartKey::default(),
+            quick_restart: true,
+            min_accuracy: 0.0,
+        }
+    }
+}
+
+/// Whether mistakes prevent moving on.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum StopOnError {
+    /// Mistakes are typed like any other character.
+    #[default]
+    Off,
+    /// Space doesn't move on to the next word while the word is wrong.
+    Word,
+    /// Only the correct key moves the cursor forward.
+    Letter,
+}
+
+impl Behavior {
+    /// Whether a test of `accuracy` percent is below the minimum accuracy.
+    pub fn is_invalid(&self, accuracy: f64) -> bool {
+        accuracy < self.min_accuracy
+    }
+}
+
+/// How far backspace can go back.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Backspace {
+    /// Correct words are kept once confirmed, only mistakes can be erased.
+    /// Backspace at the start of a word goes back onto the previous one if
+    /// it was mistyped.
+    #[default]
+    Strict,
+    /// Any character can be erased, including those of correct words, going
+    /// back across words.
+    Free,
+}
+
+/// Key restarting a test.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartKey {
+    #[default]
+    Tab,
+    Enter,
+    CtrlR,
+    F5,
+}
+
+impl RestartKey {
+    /// The key event of this key.
+    pub fn event(self) -> KeyEvent {
+        match self {
+            Self::Tab => KeyCode::Tab.into(),
+            Self::Enter => KeyCode::Enter.into(),
+            Self::CtrlR => KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+            Self::F5 => KeyCode::F(5).into(),
+        }
+    }
+
+    /// Whether `key` is a press of this key.
+    pub fn matches(self, key: &KeyEvent) -> bool {
+        let event = self.event();
+        key.code == event.code && key.modifiers.contains(event.modifiers)
+    }
+
+    /// Name of the key in key hints.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Tab => "TAB",
+            Self::Enter => "ENTER",
+            Self::CtrlR => "^R",
+            Self::F5 => "F5",
+        }
+    }
+}
+
+/// Appearance of the UI.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct UiOptions {
+    /// Whether to show the logo and a few hints at startup, until a key is
+    /// pressed.
+    #[serde(default = "default_true")]
+    pub splash: bool,
+    /// How the cursor of the typing area is drawn.
+    #[serde(default)]
+    pub cursor: CursorStyle,
+    /// Whether the cursor blinks while no key is pressed.
+    #[serde(default = "default_true")]
+    pub cursor_blink: bool,
+    /// Widest the typing area gets in columns, centered in wider terminals, 0
+    /// for the full width.
+    #[serde(default)]
+    pub max_width: u16,
+    /// Whether options and footer hints can be clicked. Capturing the mouse
+    /// stops the terminal from selecting text.
+    #[serde(default = "default_true")]
+    pub mouse: bool,
+    /// Whether to show the timer (or word counter) during a test.
+    #[serde(default = "default_true")]
+    pub live_timer: bool,
+    /// How the clock mode shows the time left.
+    #[serde(default)]
+    pub timer: TimerStyle,
+    /// Whether to dim the words typed before the current one, with the `typed`
+    /// style of the theme.
+    #[serde(default)]
+    pub dim_typed: bool,
+    /// Whether to draw a progress bar under the text, in modes with a fixed
+    /// length.
+    #[serde(default)]
+    pub progress_bar: bool,
+    /// Whether to show a status line with the mode, its settings, the profile
+    /// and the personal best.
+    #[serde(default)]
+    pub status_bar: bool,
+    /// Whether to show the WPM during a test, counting confirmed words.
+    #[serde(default)]
+    pub live_wpm: bool,
+    /// Whether to draw the WPM of the last seconds next to the timer during a
+    /// test.
+    #[serde(default)]
+    pub sparkline: bool,
+    /// Whether to show the accuracy during a test.
+    #[serde(default)]
+    pub live_accuracy: bool,
+    /// Whether confetti falls over the results screen after a new personal
+    /// best.
+    #[serde(default = "default_true")]
+    pub celebration: bool,
+    /// Whether to draw the WPM chart on the results screen.
+    #[serde(default = "default_true")]
+    pub results_chart: bool,
+    /// Caret moving through the text at a target speed during a test.
+    #[serde(default)]
+    pub pace_caret: PaceCaret,
+    /// Speed of the `custom` pace caret, in WPM.
+    #[serde(default = "default_pace_wpm")]
+    pub pace_wpm: f64,
+}
+
+impl Default for UiOptions {
+    fn default() -> Self {
+        Self {
+            splash: true,
+            cursor: CursorStyle::default(),
+            cursor_blink: true,
+            max_width: 0,
+            mouse: true,
+            live_timer: true,
+            progress_bar: false,
+            timer: TimerStyle::default(),
+            dim_typed: false,
+            status_bar: false,
+            live_wpm: false,
+            live_accuracy: false,
+            sparkline: false,
+            results_chart: true,
+            celebration: true,
+            pace_caret: PaceCaret::default(),
+            pace_wpm: default_pace_wpm(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_pace_wpm() -> f64 {
+    60.0
+}
+
+/// How the cursor of the typing area is drawn.
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum CursorStyle {
+    /// The `cursor` style of the theme, applied to the character to type.
+    #[default]
+    StyleOnly,
+    /// The terminal cursor, as a block.
+    Block,
+    /// The terminal cursor, as an underline.
+    Underline,
+    /// The terminal cursor, as a vertical bar.
+    Bar,
+}
+
+impl CursorStyle {
+    /// Whether the terminal cursor is shown, rather than a styled character.
+    pub fn uses_terminal_cursor(self) -> bool {
+        self != Self::StyleOnly
+    }
+}
+
+/// How the clock mode shows the time left, see [`UiOptions::timer`].
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimerStyle {
+    /// Seconds left.
+    #[default]
+    Numeric,
+    /// A bar shrinking as time runs out, followed by the seconds left.
+    Bar,
+    /// A braille circle emptying as time runs out, followed by the seconds
+    /// left.
+    Arc,
+}
+
+/// Speed of the pace caret, see [`UiOptions::pace_caret`].
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum PaceCaret {
+    /// No pace caret.
+    #[default]
+    Off,
+    /// The personal best of the mode and its options.
+    Pb,
+    /// The speed of [`UiOptions::pace_wpm`].
+    Custom,
+}
+
+impl PaceCaret {
+    /// Speed of the caret in WPM, `None` when it is off or there is no
+    /// personal best yet.
+    pub fn wpm(self, custom: f64, personal_best: Option<f64>) -> Option<f64> {
+        match self {
+            Self::Off => None,
+            Self::Pb => personal_best,
+            Self::Custom => Some(custom),
+        }
+        .filter(|wpm| *wpm > 0.0)
+    }
 }
 
 /// Default settings for typing tests.
-#[derive(Serialize, Deserialize, Default)]
+#[derive(Serialize, Deserialize, JsonSchema, Default)]
 pub struct Defaults {
-    #[serde(flatten)]
-    #[serde(default)]
+    /// The mode to start with, the default one when `mode` is left out.
+    #[serde(flatten, deserialize_with = "default_mode::deserialize")]
+    #[schemars(schema_with = "default_mode::schema")]
     pub mode: Mode,
+
+    /// Amount of words typed in an unscored warm-up round before the first
+    /// test, 0 disables it.
+    #[serde(default)]
+    pub warmup: usize,
+
+    /// Whether to save a replay of every completed test.
+    #[serde(default)]
+    pub record_replays: bool,
+
+    /// How the words of word list modes are generated.
+    #[serde(default)]
+    pub generator: Generator,
+
+    /// Seed for the random words, so every run (and every user) gets the same
+    /// words. Random words are drawn on each run without it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+/// How the words of word list modes (clock, words...) are generated.
+#[derive(Serialize, Deserialize, JsonSchema, ValueEnum, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Generator {
+    /// Words of the text in random order
+    #[default]
+    Shuffle,
+    /// Pseudo-sentences following the word transitions of the text's corpus
+    Markov,
 }
 
 #[cfg(test)]
@@ -31,6 +1106,46 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn word_filter_accepts() {
+        let filter = WordFilter {
+            min_word_len: Some(3),
+            max_word_len: Some(5),
+            ascii_only: true,
+        };
+
+        assert!(filter.accepts("word"));
+        assert!(!filter.accepts("at"));
+        assert!(!filter.accepts("because"));
+        assert!(!filter.accepts("café"));
+        assert!(WordFilter::default().accepts("café"));
+    }
+
+    #[test]
+    fn word_rules_accepts() {
+        let rules = WordRules {
+            exclude: vec!["Paris".to_string()],
+            include_only: vec![],
+        };
+        assert!(rules.accepts("city"));
+        assert!(!rules.accepts("paris"));
+
+        let rules = WordRules {
+            exclude: vec![],
+            include_only: vec!["the".to_string(), "ÉTÉ".to_string()],
+        };
+        assert!(rules.accepts("The"));
+        assert!(rules.accepts("été"));
+        assert!(!rules.accepts("city"));
+
+        let rules = WordRules {
+            exclude: vec!["paris".to_string()],
+            include_only: vec![],
+        };
+        assert!(rules.accepts_text("Off to Rome."));
+        assert!(!rules.accepts_text("Off to \"Paris\"!"));
+    }
+
     #[test]
     fn config_serialize() {
         let config = Config::default();
@@ -50,9 +1165,15 @@ mod tests {
         let config: Config = toml::from_str(toml_str).unwrap();
 
         #[allow(irrefutable_let_patterns)]
-        if let Mode::Clock { duration, text } = config.defaults.mode {
+        if let Mode::Clock {
+            duration,
+            text,
+            penalty,
+        } = config.defaults.mode
+        {
             assert_eq!(duration, default_clock_duration());
             assert_eq!(text, default_text());
+            assert_eq!(penalty, 0);
         } else {
             panic!("Expected Clock mode");
         }
@@ -68,11 +1189,399 @@ mod tests {
         let config: Config = toml::from_str(toml_str).unwrap();
 
         #[allow(irrefutable_let_patterns)]
-        if let Mode::Clock { duration, text } = config.defaults.mode {
+        if let Mode::Clock {
+            duration,
+            text,
+            penalty,
+        } = config.defaults.mode
+        {
             assert_eq!(duration, 30);
             assert_eq!(text, default_text());
+            assert_eq!(penalty, 0);
         } else {
             panic!("Expected Clock mode");
         }
     }
+
+    #[test]
+    fn warmup_config_deserialize() {
+        let toml_str = r#"
+            [defaults]
+            mode = "words"
+            count = 25
+            warmup = 10
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        assert_eq!(config.defaults.warmup, 10);
+        assert!(!config.defaults.record_replays);
+        assert!(matches!(
+            config.defaults.mode,
+            Mode::Words { count: 25, .. }
+        ));
+    }
+
+    #[test]
+    fn goal_config_deserialize() {
+        let toml_str = r#"
+            [goal]
+            words = 500
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        assert_eq!(
+            config.goal,
+            Goal {
+                tests: None,
+                words: Some(500),
+            }
+        );
+        assert_eq!(Config::default().goal, Goal::default());
+    }
+
+    #[test]
+    fn strict_parse_reports_unknown_keys() {
+        let toml_str = "[defaults]\nmode = \"words\"\ncount = 25\nduration = 30\n\n[filtr]\nascii_only = true\n";
+
+        let error = Config::parse(toml_str, true).err().unwrap().to_string();
+        assert!(error.contains("unknown key `defaults.duration` at line 4"));
+        assert!(error.contains("unknown key `filtr` at line 6"));
+
+        let config = Config::parse(toml_str, false).unwrap();
+        assert!(matches!(
+            config.defaults.mode,
+            Mode::Words { count: 25, .. }
+        ));
+        assert!(Config::parse("[chart]\nsmoothing = 3\n", true).is_ok());
+    }
+
+    #[test]
+    fn defaults_without_mode_use_the_default_mode() {
+        let config = Config::parse("[defaults]\nseed = 4\n", true).unwrap();
+        assert_eq!(config.defaults.mode.name(), Mode::default().name());
+        assert_eq!(config.defaults.seed, Some(4));
+
+        // Options of another mode need its name
+        assert!(Config::parse("[defaults]\ncount = 25\n", true).is_err());
+        assert!(Config::parse("[defaults]\nduration = \"long\"\n", false).is_err());
+
+        let schema = Config::schema();
+        let required: Vec<&serde_json::Value> = schema["$defs"]["Defaults"]["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|variant| variant["required"][0] == "mode")
+            .collect();
+        assert_eq!(required.len(), Mode::VARIANTS.len() - 1);
+    }
+
+    #[test]
+    fn texts_pick_the_mode_of_defaults_without_mode() {
+        let defaults: toml::Table = toml::from_str("text = \"code/main\"\ncount = 25\n").unwrap();
+        let meta = TextMeta {
+            modes: vec!["zen".to_string(), "words".to_string()],
+            ..TextMeta::default()
+        };
+
+        let mode = text_mode(defaults.clone(), &meta).unwrap();
+        assert_eq!(mode.name(), "words");
+        assert_eq!(mode.text(), Some("code/main"));
+        assert!(matches!(mode, Mode::Words { count: 25, .. }));
+
+        assert!(text_mode(defaults, &TextMeta::default()).is_none());
+    }
+
+    #[test]
+    fn parse_reports_invalid_values() {
+        let error = Config::parse("[chart]\nsmoothing = \"high\"\n", true)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("line 2"));
+    }
+
+    #[test]
+    fn mode_tables_deserialize() {
+        let toml_str = r#"
+            [defaults]
+            mode = "clock"
+            duration = 15
+
+            [modes.clock]
+            duration = 60
+
+            [modes.words]
+            count = 25
+        "#;
+        let config = Config::parse(toml_str, true).unwrap();
+
+        assert!(matches!(
+            config.start_mode(),
+            Mode::Clock { duration: 60, .. }
+        ));
+        assert!(matches!(
+            config.mode("words"),
+            Mode::Words { count: 25, .. }
+        ));
+        assert!(matches!(config.mode("quote"), Mode::Quote { .. }));
+
+        let serialized = toml::to_string(&config).unwrap();
+        assert!(serialized.contains("[modes.words]\ncount = 25"));
+        assert!(!serialized.contains("mode = \"words\""));
+
+        assert!(Config::parse("[modes.clock]\ncount = 25\n", true).is_err());
+        assert!(Config::parse("[modes.unknown]\n", false).is_err());
+    }
+
+    #[test]
+    fn legacy_word_count_migrates() {
+        let toml_str = "[defaults]\nmode = \"words\"\nwords = 25\n";
+        let (migrated, notes) = Config::migrate(toml_str);
+        let config = Config::parse(&migrated, true).unwrap();
+
+        assert_eq!(notes, ["moved `defaults.words` to `defaults.count`"]);
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(matches!(config.start_mode(), Mode::Words { count: 25, .. }));
+
+        let toml_str = "[defaults]\nmode = \"clock\"\nwords = 25\n";
+        let (migrated, notes) = Config::migrate(toml_str);
+        let config = Config::parse(&migrated, true).unwrap();
+
+        assert_eq!(notes, ["moved `defaults.words` to `modes.words.count`"]);
+        assert!(matches!(
+            config.mode("words"),
+            Mode::Words { count: 25, .. }
+        ));
+    }
+
+    #[test]
+    fn migrated_configs_load_as_written() {
+        // Without a mode, the default one isn't the words mode
+        let (config, notes) = Config::load("[defaults]\nwords = 25\n", true).unwrap();
+        assert_eq!(notes, ["moved `defaults.words` to `modes.words.count`"]);
+        assert!(matches!(
+            config.mode("words"),
+            Mode::Words { count: 25, .. }
+        ));
+
+        // Errors are on the user's lines
+        let toml_str = "[defaults]\nmode = \"words\"\nwords = 25\n\n[filtr]\nascii_only = true\n";
+        let error = Config::load(toml_str, true).err().unwrap().to_string();
+        assert_eq!(error, "unknown key `filtr` at line 5");
+
+        // A move that doesn't give a valid config isn't made
+        let toml_str = "[defaults]\nmode = \"words\"\nwords = \"many\"\n";
+        let error = Config::load(toml_str, true).err().unwrap().to_string();
+        assert_eq!(error, "unknown key `defaults.words` at line 3");
+        let (_, notes) = Config::load(toml_str, false).unwrap();
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    fn current_configs_are_not_migrated() {
+        let config = toml::to_string(&Config::default()).unwrap();
+        assert!(config.starts_with(&format!("version = {}", CONFIG_VERSION)));
+
+        for config in [config.as_str(), "[defaults]\nmode = \"clock\"\n"] {
+            let (migrated, notes) = Config::migrate(config);
+            assert!(matches!(migrated, Cow::Borrowed(_)));
+            assert!(notes.is_empty());
+        }
+
+        let newer = format!("version = {}\n", CONFIG_VERSION + 1);
+        assert!(Config::parse(&newer, true).is_err());
+        assert!(Config::parse(&newer, false).is_ok());
+    }
+
+    #[test]
+    fn ui_config_deserialize() {
+        let config = Config::parse("[ui]\ncursor = \"style-only\"\n", true).unwrap();
+        assert_eq!(config.ui.cursor, CursorStyle::StyleOnly);
+
+        assert!(config.ui.live_timer && config.ui.results_chart);
+        assert!(!config.ui.live_wpm && !config.ui.live_accuracy);
+
+        let config = Config::parse(
+            "[ui]\ncursor = \"bar\"\nlive_timer = false\nlive_wpm = true\n",
+            true,
+        )
+        .unwrap();
+        assert_eq!(config.ui.cursor, CursorStyle::Bar);
+        assert!(config.ui.cursor.uses_terminal_cursor());
+        assert!(!config.ui.live_timer && config.ui.live_wpm);
+    }
+
+    #[test]
+    fn behavior_config_deserialize() {
+        assert_eq!(Config::default().behavior.stop_on_error, StopOnError::Off);
+
+        let config = Config::parse(
+            "[behavior]\nstop_on_error = \"letter\"\nrestart_key = \"ctrl-r\"\n",
+            true,
+        )
+        .unwrap();
+        assert_eq!(config.behavior.stop_on_error, StopOnError::Letter);
+        assert_eq!(config.behavior.restart_key, RestartKey::CtrlR);
+        assert!(config.behavior.quick_restart);
+        assert!(
+            RestartKey::CtrlR.matches(&KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL))
+        );
+        assert!(!RestartKey::CtrlR.matches(&KeyCode::Char('r').into()));
+    }
+
+    #[test]
+    fn save_keeps_comments_and_unknown_keys() {
+        let existing = "# My settings\nversion = 1\n\n[defaults]\nmode = \"clock\" # favorite\nduration = 30\n\n[modes.words]\ncount = 10\n\n[plugins]\nenabled = true\n";
+        let mut config = Config::parse(existing, false).unwrap();
+        config.modes.clear();
+        config.defaults.mode = Mode::Clock {
+            text: default_text(),
+            duration: 60,
+            penalty: 0,
+        };
+
+        let merged = merge_into(existing, &toml::to_string(&config).unwrap()).unwrap();
+        assert!(merged.starts_with("# My settings\n"));
+        assert!(merged.contains("mode = \"clock\" # favorite\n"));
+        assert!(merged.contains("duration = 60"));
+        assert!(merged.contains("[plugins]\nenabled = true"));
+        assert!(!merged.contains("[modes.words]"));
+        assert!(Config::parse(&merged, false).is_ok());
+    }
+
+    #[test]
+    fn save_writes_changed_keys_only() {
+        let existing =
+            "theme = \"nord\"\n\n[defaults]\nmode = \"clock\"\n\n[behavior]\nmin_accuracy = 90\n";
+        let mut config = Config::parse(existing, false).unwrap();
+        config.behavior.backspace = Backspace::Free;
+
+        let merged = merge_into(existing, &toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(
+            merged,
+            "theme = \"nord\"\n\n[defaults]\nmode = \"clock\"\n\n[behavior]\nmin_accuracy = 90\nbackspace = \"free\"\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_follows_symlinks() {
+        let dir = std::env::temp_dir().join("ttt_config_symlink_test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("dotfiles.toml");
+        let link = dir.join("config.toml");
+        fs::write(&target, "[defaults]\nmode = \"clock\"\n").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let mut config = Config::default();
+        config.defaults.seed = Some(3);
+        config.save(&link).unwrap();
+
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert!(fs::read_to_string(&target).unwrap().contains("seed = 3"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn schema_describes_mode_tables() {
+        let schema = Config::schema();
+        let clock = &schema["properties"]["modes"]["properties"]["clock"];
+
+        assert!(clock["properties"]["duration"].is_object());
+        assert!(clock["properties"]["mode"].is_null());
+        assert!(schema["$defs"]["Theme"]["anyOf"].is_array());
+    }
+
+    #[test]
+    fn env_overrides_settings() {
+        let vars = [
+            ("TTT_MODE", "clock"),
+            ("TTT_DURATION", "60"),
+            ("TTT_SEED", "7"),
+            ("HOME", "/home/user"),
+        ]
+        .map(|(key, value)| (key.to_string(), value.to_string()));
+        let mut config = Config::default();
+        config.apply_env(vars, &[]).unwrap();
+
+        assert!(matches!(
+            config.defaults.mode,
+            Mode::Clock { duration: 60, .. }
+        ));
+        assert_eq!(config.defaults.seed, Some(7));
+
+        let vars = [("TTT_DURATION".to_string(), "soon".to_string())];
+        assert!(Config::default().apply_env(vars, &[]).is_err());
+    }
+
+    #[test]
+    fn env_checks_variable_names() {
+        let env = |vars: &[(&str, &str)]| {
+            vars.iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut config = Config::default();
+        config
+            .apply_env(
+                env(&[("TTT_MODE", "custom"), ("TTT_FILE", "notes.txt")]),
+                &[],
+            )
+            .unwrap();
+        assert!(matches!(
+            &config.defaults.mode,
+            Mode::Custom { file: Some(file) } if file.to_str() == Some("notes.txt")
+        ));
+
+        // Options of other modes don't apply, typos are errors
+        let mut config = Config::default();
+        config.defaults.mode = Mode::default_for("words");
+        config
+            .apply_env(env(&[("TTT_DURATION", "60")]), &[])
+            .unwrap();
+        assert!(matches!(config.defaults.mode, Mode::Words { .. }));
+        assert!(
+            Config::default()
+                .apply_env(env(&[("TTT_DURATON", "60")]), &[])
+                .is_err()
+        );
+
+        // Options given on the command line are kept
+        let mut config = Config::default();
+        config.defaults.mode = Mode::default_for("clock");
+        let vars = env(&[
+            ("TTT_MODE", "words"),
+            ("TTT_DURATION", "60"),
+            ("TTT_PENALTY", "2"),
+        ]);
+        config.apply_env(vars, &["mode", "duration"]).unwrap();
+        assert!(matches!(
+            config.defaults.mode,
+            Mode::Clock {
+                duration: 30,
+                penalty: 2,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn zen_config_deserialize() {
+        let toml_str = r#"
+            [defaults]
+            mode = "zen"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        assert!(matches!(config.defaults.mode, Mode::Zen));
+        assert_eq!(config.defaults.mode.name(), "zen");
+        assert!(matches!(Mode::default_for("zen"), Mode::Zen));
+    }
 }
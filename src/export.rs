@@ -0,0 +1,239 @@
+//! # Export Module
+//!
+//! This module converts the recorded history into formats other tools can
+//! read, such as spreadsheets.
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    history::TestRecord,
+    replay::{Key, Replay},
+};
+
+/// Output formats supported by the `export` subcommand.
+#[derive(ValueEnum, Clone, Copy, Default, PartialEq, Debug)]
+pub enum ExportFormat {
+    /// One row per test
+    #[default]
+    Csv,
+    /// One row per keystroke of the tests with a replay
+    Keystrokes,
+    /// Keystrokes of the tests with a replay, as a JSON array
+    KeystrokesJson,
+}
+
+/// A keystroke of a recorded test.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct KeystrokeRow {
+    /// When the test was completed.
+    pub test: DateTime<Utc>,
+    pub mode: String,
+    /// Seconds since the start of the test.
+    pub time: f64,
+    /// Seconds since the previous keystroke, `None` for the first one.
+    pub interval: Option<f64>,
+    /// The typed character, or "space", "backspace" or "clear_word".
+    pub key: String,
+}
+
+/// Keeps the records completed on or after `since`, if given.
+pub fn filter_since(records: Vec<TestRecord>, since: Option<NaiveDate>) -> Vec<TestRecord> {
+    match since {
+        Some(since) => records
+            .into_iter()
+            .filter(|record| record.timestamp.date_naive() >= since)
+            .collect(),
+        None => records,
+    }
+}
+
+/// Options of a mode as (name, value) pairs, e.g. `("duration", "30")`.
+fn mode_options(record: &TestRecord) -> Vec<(String, String)> {
+    match serde_json::to_value(&record.mode) {
+        Ok(serde_json::Value::Object(fields)) => fields
+            .into_iter()
+            .filter(|(name, _)| name != "mode")
+            .map(|(name, value)| match value {
+                serde_json::Value::String(value) => (name, value),
+                value => (name, value.to_string()),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Every keystroke of the given tests, in order.
+pub fn keystroke_rows(tests: &[(TestRecord, Replay)]) -> Vec<KeystrokeRow> {
+    let mut rows = Vec::new();
+
+    for (record, replay) in tests {
+        let mut previous: Option<f64> = None;
+        for keystroke in &replay.keystrokes {
+            rows.push(KeystrokeRow {
+                test: record.timestamp,
+                mode: replay.mode.clone(),
+                time: keystroke.time,
+                interval: previous.map(|time| keystroke.time - time),
+                key: match keystroke.key {
+                    Key::Char(' ') => "space".to_string(),
+                    Key::Char(c) => c.to_string(),
+                    Key::Backspace => "backspace".to_string(),
+                    Key::ClearWord => "clear_word".to_string(),
+                },
+            });
+            previous = Some(keystroke.time);
+        }
+    }
+
+    rows
+}
+
+/// One row per keystroke, see [`keystroke_rows`].
+pub fn keystrokes_to_csv(rows: &[KeystrokeRow]) -> String {
+    let mut csv = "test,mode,time,interval,key\n".to_string();
+
+    for row in rows {
+        let fields = [
+            row.test.to_rfc3339(),
+            row.mode.clone(),
+            format!("{:.3}", row.time),
+            row.interval
+                .map(|interval| format!("{:.3}", interval))
+                .unwrap_or_default(),
+            row.key.clone(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| escape(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// One row per test, with a column for each mode option found in the records.
+///
+/// Options a mode doesn't have are left empty.
+pub fn to_csv(records: &[TestRecord]) -> String {
+    let options: Vec<Vec<(String, String)>> = records.iter().map(mode_options).collect();
+    let columns: BTreeSet<&str> = options
+        .iter()
+        .flatten()
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    // Test duration is in "seconds", clock options already have a "duration"
+    let mut header = vec!["timestamp", "mode", "wpm", "raw_wpm", "accuracy", "seconds"];
+    header.extend(columns.iter().copied());
+
+    let mut csv = header.join(",");
+    csv.push('\n');
+
+    for (record, options) in records.iter().zip(&options) {
+        let mut row = vec![
+            record.timestamp.to_rfc3339(),
+            record.mode.name().to_string(),
+            format!("{:.2}", record.wpm),
+            format!("{:.2}", record.raw_wpm),
+            format!("{:.2}", record.accuracy),
+            format!("{:.2}", record.duration),
+        ];
+        row.extend(columns.iter().map(|column| {
+            options
+                .iter()
+                .find(|(name, _)| name == column)
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default()
+        }));
+
+        let row: Vec<String> = row.iter().map(|field| escape(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Backspace, StopOnError};
+    use crate::{
+        app::modes::{GameStats, Mode},
+        replay::Keystroke,
+    };
+
+    #[test]
+    fn csv_has_a_column_per_option() {
+        let clock = TestRecord::new(
+            Mode::default_for("clock"),
+            &GameStats::new(60.0, 95.0, 30.0),
+        );
+        let ladder = TestRecord::new(
+            Mode::default_for("ladder"),
+            &GameStats::new(50.0, 90.0, 40.0),
+        );
+
+        let csv = to_csv(&[clock, ladder]);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "timestamp,mode,wpm,raw_wpm,accuracy,seconds,duration,penalty,text,thresholds"
+        );
+        assert!(lines[1].ends_with(",clock,60.00,60.00,95.00,30.00,30,0,english,"));
+        assert!(
+            lines[2].ends_with(
+                ",ladder,50.00,50.00,90.00,40.00,,,english,\"[30,40,50,60,70,80,90,100]\""
+            )
+        );
+    }
+
+    #[test]
+    fn keystrokes_csv_has_a_row_per_keystroke() {
+        let record = TestRecord::new(Mode::default_for("words"), &GameStats::new(60.0, 95.0, 1.0));
+        let replay = Replay {
+            mode: "words".to_string(),
+            text: "english".to_string(),
+            target_words: vec!["a,".to_string()],
+            keystrokes: [
+                (0.0, Key::Char('a')),
+                (0.25, Key::Char(',')),
+                (0.5, Key::Backspace),
+            ]
+            .into_iter()
+            .map(|(time, key)| Keystroke { time, key })
+            .collect(),
+            duration: 1.0,
+            stop_on_error: StopOnError::Off,
+            backspace: Backspace::Strict,
+        };
+
+        let csv = keystrokes_to_csv(&keystroke_rows(&[(record, replay)]));
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].ends_with(",words,0.000,,a"));
+        assert!(lines[2].ends_with(",words,0.250,0.250,\",\""));
+        assert!(lines[3].ends_with(",words,0.500,0.250,backspace"));
+    }
+
+    #[test]
+    fn escape_quotes_fields() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape("a,b"), "\"a,b\"");
+        assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
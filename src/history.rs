@@ -0,0 +1,756 @@
+//! # History Module
+//!
+//! This module persists results of completed tests under the data directory,
+//! so that later sessions can build on them (e.g., practicing weak words).
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::modes::{GameStats, KeyResult, Mode, WordResult},
+    profile,
+};
+
+/// Returns the directory history files are stored in, a separate one for
+/// each profile.
+pub fn data_dir() -> Option<PathBuf> {
+    Some(profile::data_dir(crate::data_dir()?))
+}
+
+/// A completed test, stored as one JSON line in `history.jsonl`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TestRecord {
+    pub timestamp: DateTime<Utc>,
+    /// The mode and its options.
+    pub mode: Mode,
+    /// Name of the text the words were drawn from, if any.
+    pub text: Option<String>,
+    pub wpm: f64,
+    /// Gross WPM, mistakes included.
+    #[serde(default)]
+    pub raw_wpm: f64,
+    pub accuracy: f64,
+    /// Duration of the test in seconds.
+    pub duration: f64,
+    /// File name of the replay of the test, under the replays directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay: Option<String>,
+    /// Tag of the session the test was taken in, e.g. "work" or "practice".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Whether the test finished below the minimum accuracy, it doesn't count
+    /// towards personal bests.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub invalid: bool,
+}
+
+impl TestRecord {
+    /// Creates a record of a test completed just now.
+    pub fn new(mode: Mode, stats: &GameStats) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            text: mode.text().map(ToString::to_string),
+            mode,
+            wpm: stats.wpm(),
+            raw_wpm: stats.raw_wpm(),
+            accuracy: stats.accuracy(),
+            duration: stats.duration(),
+            replay: None,
+            tag: None,
+            invalid: false,
+        }
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(data_dir()
+            .ok_or_else(|| anyhow!("Couldn't find data directory"))?
+            .join("history.jsonl"))
+    }
+
+    /// Appends the record to the history file.
+    pub fn append(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Couldn't create data directory")?;
+        }
+
+        let line = serde_json::to_string(self).context("Couldn't serialize test record")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .context("Couldn't open history")?;
+        writeln!(file, "{}", line).context("Couldn't save test record")
+    }
+
+    /// Loads every recorded test, oldest first.
+    ///
+    /// Lines that can't be parsed (e.g., from a mode that was since removed)
+    /// are skipped. Imported tests are appended after the existing ones, so
+    /// records are sorted by timestamp.
+    pub fn load_all() -> Result<Vec<Self>> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path).context("Couldn't read history")?;
+        let mut records = Self::parse_all(&content);
+        records.sort_by_key(|record| record.timestamp);
+        Ok(records)
+    }
+
+    /// Parses records from JSON lines, skipping invalid ones.
+    pub fn parse_all(content: &str) -> Vec<Self> {
+        content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+}
+
+/// Best WPM reached for each mode and options combination.
+#[derive(Default, Debug)]
+pub struct PersonalBests {
+    pub bests: HashMap<String, f64>,
+}
+
+impl PersonalBests {
+    /// Key identifying a mode and its options, e.g. `clock/duration=30/penalty=0/text=english`.
+    pub fn key(mode: &Mode) -> String {
+        let mut key = mode.name().to_string();
+
+        if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(mode) {
+            let mut fields: Vec<(String, serde_json::Value)> = fields
+                .into_iter()
+                .filter(|(name, _)| name != "mode")
+                .collect();
+            fields.sort_by(|a, b| a.0.cmp(&b.0));
+
+            for (name, value) in fields {
+                match value {
+                    serde_json::Value::String(value) => {
+                        key.push_str(&format!("/{}={}", name, value))
+                    }
+                    value => key.push_str(&format!("/{}={}", name, value)),
+                }
+            }
+        }
+
+        key
+    }
+
+    /// Computes the personal bests from recorded tests, invalid tests left
+    /// out.
+    pub fn from_records(records: &[TestRecord]) -> Self {
+        let mut bests = Self::default();
+        for record in records.iter().filter(|record| !record.invalid) {
+            bests.update(&record.mode, record.wpm);
+        }
+        bests
+    }
+
+    /// Computes the personal bests from the history file.
+    pub fn load() -> Result<Self> {
+        Ok(Self::from_records(&TestRecord::load_all()?))
+    }
+
+    /// The best WPM reached with the given mode and options.
+    pub fn get(&self, mode: &Mode) -> Option<f64> {
+        self.bests.get(&Self::key(mode)).copied()
+    }
+
+    /// Adds a result, returns `true` if it beats the previous best.
+    pub fn update(&mut self, mode: &Mode, wpm: f64) -> bool {
+        if wpm <= 0.0 {
+            return false;
+        }
+
+        let best = self.bests.entry(Self::key(mode)).or_insert(0.0);
+        if wpm > *best {
+            *best = wpm;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// How often a single word was typed, and how often it was mistyped.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub struct WordStat {
+    pub attempts: u32,
+    pub errors: u32,
+}
+
+impl WordStat {
+    /// Error rate with add-one smoothing, so rarely typed words don't
+    /// dominate the ranking after a single mistake.
+    pub fn weakness(&self) -> f64 {
+        (self.errors as f64 + 1.0) / (self.attempts as f64 + 2.0)
+    }
+}
+
+/// Per-word accuracy accumulated across every completed test.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct WordStats {
+    pub words: HashMap<String, WordStat>,
+}
+
+impl WordStats {
+    fn path() -> Result<PathBuf> {
+        Ok(data_dir()
+            .ok_or_else(|| anyhow!("Couldn't find data directory"))?
+            .join("words.json"))
+    }
+
+    /// Loads the stored word stats, empty if none were recorded yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path).context("Couldn't read word stats")?;
+        serde_json::from_str(&content).context("Couldn't parse word stats")
+    }
+
+    /// Writes the word stats to the data directory.
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Couldn't create data directory")?;
+        }
+
+        let content = serde_json::to_string(self).context("Couldn't serialize word stats")?;
+        fs::write(path, content).context("Couldn't save word stats")
+    }
+
+    /// Adds the results of a test.
+    pub fn record(&mut self, results: &[WordResult]) {
+        for result in results {
+            let stat = self.words.entry(result.target.clone()).or_default();
+            stat.attempts += 1;
+            if !result.is_correct() {
+                stat.errors += 1;
+            }
+        }
+    }
+
+    /// Up to `count` words with at least one error, most errors first.
+    pub fn most_missed(&self, count: usize) -> Vec<(String, WordStat)> {
+        let mut words: Vec<(&String, &WordStat)> = self
+            .words
+            .iter()
+            .filter(|(_, stat)| stat.errors > 0)
+            .collect();
+
+        words.sort_by(|a, b| {
+            b.1.errors
+                .cmp(&a.1.errors)
+                .then_with(|| b.1.weakness().total_cmp(&a.1.weakness()))
+                .then_with(|| a.0.cmp(b.0))
+        });
+
+        words
+            .into_iter()
+            .take(count)
+            .map(|(word, stat)| (word.clone(), *stat))
+            .collect()
+    }
+
+    /// Up to `count` words with at least one error, weakest first.
+    pub fn weakest(&self, count: usize) -> Vec<String> {
+        let mut words: Vec<(&String, &WordStat)> = self
+            .words
+            .iter()
+            .filter(|(_, stat)| stat.errors > 0)
+            .collect();
+
+        words.sort_by(|a, b| {
+            b.1.weakness()
+                .total_cmp(&a.1.weakness())
+                .then_with(|| a.0.cmp(b.0))
+        });
+
+        words
+            .into_iter()
+            .take(count)
+            .map(|(word, _)| word.clone())
+            .collect()
+    }
+}
+
+/// Practice done on a single day.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct DayActivity {
+    pub tests: usize,
+    /// Correct words typed, derived from the WPM and duration of each test.
+    pub words: f64,
+}
+
+/// Practice per day (UTC, like the daily challenge), used for streaks and
+/// daily goals.
+#[derive(Default, Debug)]
+pub struct Activity {
+    pub days: BTreeMap<NaiveDate, DayActivity>,
+}
+
+impl Activity {
+    /// Computes the activity from recorded tests.
+    pub fn from_records(records: &[TestRecord]) -> Self {
+        let mut activity = Self::default();
+        for record in records {
+            activity.record(record);
+        }
+        activity
+    }
+
+    /// Computes the activity from the history file.
+    pub fn load() -> Result<Self> {
+        Ok(Self::from_records(&TestRecord::load_all()?))
+    }
+
+    /// Adds a completed test.
+    pub fn record(&mut self, record: &TestRecord) {
+        let day = self.days.entry(record.timestamp.date_naive()).or_default();
+        day.tests += 1;
+        day.words += record.wpm * record.duration / 60.0;
+    }
+
+    /// Practice done on `date`.
+    pub fn day(&self, date: NaiveDate) -> DayActivity {
+        self.days.get(&date).copied().unwrap_or_default()
+    }
+
+    /// Consecutive days with at least one test, up to `today`.
+    ///
+    /// A streak is still alive until the end of the day after its last test.
+    pub fn streak(&self, today: NaiveDate) -> usize {
+        let mut date = if self.days.contains_key(&today) {
+            today
+        } else {
+            match today.checked_sub_days(Days::new(1)) {
+                Some(yesterday) => yesterday,
+                None => return 0,
+            }
+        };
+
+        let mut streak = 0;
+        while self.days.contains_key(&date) {
+            streak += 1;
+            match date.checked_sub_days(Days::new(1)) {
+                Some(previous) => date = previous,
+                None => break,
+            }
+        }
+        streak
+    }
+}
+
+/// Accuracy and speed of a single key.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub struct KeyStat {
+    pub hits: u32,
+    pub misses: u32,
+    /// Sum of the latencies of timed presses, in seconds.
+    pub latency: f64,
+    /// Amount of presses with a latency (the first keystroke of a test has none).
+    pub timed: u32,
+}
+
+impl KeyStat {
+    pub fn presses(&self) -> u32 {
+        self.hits + self.misses
+    }
+
+    /// Percentage of presses that were correct.
+    pub fn accuracy(&self) -> f64 {
+        match self.presses() {
+            0 => 0.0,
+            presses => self.hits as f64 / presses as f64 * 100.0,
+        }
+    }
+
+    /// Average time taken to reach this key from the previous one, in seconds.
+    pub fn average_latency(&self) -> Option<f64> {
+        (self.timed > 0).then(|| self.latency / self.timed as f64)
+    }
+}
+
+/// Per-key accuracy and latency, either of a single test or accumulated
+/// across every completed test.
+///
+/// Letters are stored lowercase, as they share a key with their uppercase.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct KeyStats {
+    pub keys: BTreeMap<char, KeyStat>,
+}
+
+impl KeyStats {
+    fn path() -> Result<PathBuf> {
+        Ok(data_dir()
+            .ok_or_else(|| anyhow!("Couldn't find data directory"))?
+            .join("keys.json"))
+    }
+
+    /// Computes the stats of a single test.
+    pub fn from_results(results: &[KeyResult]) -> Self {
+        let mut stats = Self::default();
+        stats.record(results);
+        stats
+    }
+
+    /// Loads the stored key stats, empty if none were recorded yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path).context("Couldn't read key stats")?;
+        serde_json::from_str(&content).context("Couldn't parse key stats")
+    }
+
+    /// Writes the key stats to the data directory.
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Couldn't create data directory")?;
+        }
+
+        let content = serde_json::to_string(self).context("Couldn't serialize key stats")?;
+        fs::write(path, content).context("Couldn't save key stats")
+    }
+
+    /// Adds the results of a test.
+    pub fn record(&mut self, results: &[KeyResult]) {
+        for result in results {
+            let key = result.key.to_lowercase().next().unwrap_or(result.key);
+            let stat = self.keys.entry(key).or_default();
+            if result.correct {
+                stat.hits += 1;
+            } else {
+                stat.misses += 1;
+            }
+            if let Some(latency) = result.latency {
+                stat.latency += latency;
+                stat.timed += 1;
+            }
+        }
+    }
+
+    /// Stats of a key, if it was pressed.
+    pub fn get(&self, key: char) -> Option<&KeyStat> {
+        self.keys.get(&key).filter(|stat| stat.presses() > 0)
+    }
+
+    /// Average latency across every key, in seconds.
+    pub fn average_latency(&self) -> Option<f64> {
+        let (latency, timed) = self.keys.values().fold((0.0, 0), |(latency, timed), stat| {
+            (latency + stat.latency, timed + stat.timed)
+        });
+        (timed > 0).then(|| latency / timed as f64)
+    }
+
+    /// Up to `count` keys with the highest average latency, slowest first.
+    pub fn slowest(&self, count: usize) -> Vec<char> {
+        let mut keys: Vec<(char, f64)> = self
+            .keys
+            .iter()
+            .filter_map(|(&key, stat)| Some((key, stat.average_latency()?)))
+            .collect();
+        keys.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        keys.into_iter().take(count).map(|(key, _)| key).collect()
+    }
+}
+
+/// Score of a completed daily challenge.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct DailyResult {
+    pub wpm: f64,
+    pub accuracy: f64,
+}
+
+/// First completed score of each daily challenge, keyed by date (YYYY-MM-DD).
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct DailyResults {
+    pub days: BTreeMap<String, DailyResult>,
+}
+
+impl DailyResults {
+    fn path() -> Result<PathBuf> {
+        Ok(data_dir()
+            .ok_or_else(|| anyhow!("Couldn't find data directory"))?
+            .join("daily.json"))
+    }
+
+    /// Loads the stored daily results, empty if none were recorded yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path).context("Couldn't read daily results")?;
+        serde_json::from_str(&content).context("Couldn't parse daily results")
+    }
+
+    /// Writes the daily results to the data directory.
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Couldn't create data directory")?;
+        }
+
+        let content = serde_json::to_string(self).context("Couldn't serialize daily results")?;
+        fs::write(path, content).context("Couldn't save daily results")
+    }
+}
+
+/// Most levels cleared in the speed ladder, keyed by ladder (its thresholds
+/// joined with `-`).
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct LadderRecords {
+    pub ladders: BTreeMap<String, usize>,
+}
+
+impl LadderRecords {
+    fn path() -> Result<PathBuf> {
+        Ok(data_dir()
+            .ok_or_else(|| anyhow!("Couldn't find data directory"))?
+            .join("ladder.json"))
+    }
+
+    /// Key identifying a ladder by its thresholds.
+    pub fn key(thresholds: &[u32]) -> String {
+        thresholds
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+
+    /// Loads the stored ladder records, empty if none were recorded yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path).context("Couldn't read ladder records")?;
+        serde_json::from_str(&content).context("Couldn't parse ladder records")
+    }
+
+    /// Writes the ladder records to the data directory.
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Couldn't create data directory")?;
+        }
+
+        let content = serde_json::to_string(self).context("Couldn't serialize ladder records")?;
+        fs::write(path, content).context("Couldn't save ladder records")
+    }
+}
+
+/// Records the word results of a completed test.
+pub fn record(results: &[WordResult]) -> Result<()> {
+    if results.is_empty() {
+        return Ok(());
+    }
+
+    let mut stats = WordStats::load()?;
+    stats.record(results);
+    stats.save()
+}
+
+/// Records the key results of a completed test.
+pub fn record_keys(results: &[KeyResult]) -> Result<()> {
+    if results.is_empty() {
+        return Ok(());
+    }
+
+    let mut stats = KeyStats::load()?;
+    stats.record(results);
+    stats.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(target: &str, typed: &str) -> WordResult {
+        WordResult {
+            target: target.to_string(),
+            typed: typed.to_string(),
+        }
+    }
+
+    #[test]
+    fn weakest_ranks_by_error_rate() {
+        let mut stats = WordStats::default();
+        stats.record(&[
+            result("the", "teh"),
+            result("the", "the"),
+            result("the", "the"),
+            result("which", "whcih"),
+            result("which", "wihch"),
+            result("and", "and"),
+        ]);
+
+        assert_eq!(stats.words["the"].attempts, 3);
+        assert_eq!(stats.words["the"].errors, 1);
+        assert_eq!(stats.weakest(10), vec!["which", "the"]);
+        assert_eq!(stats.weakest(1), vec!["which"]);
+    }
+
+    #[test]
+    fn most_missed_ranks_by_error_count() {
+        let mut stats = WordStats::default();
+        stats.record(&[
+            result("the", "teh"),
+            result("the", "hte"),
+            result("the", "the"),
+            result("of", "fo"),
+            result("and", "and"),
+        ]);
+
+        let missed = stats.most_missed(5);
+        assert_eq!(missed.len(), 2);
+        assert_eq!(missed[0].0, "the");
+        assert_eq!(missed[0].1.errors, 2);
+        assert_eq!(missed[1].0, "of");
+    }
+
+    #[test]
+    fn test_records_round_trip() {
+        let mode = Mode::Words {
+            count: 25,
+            text: "french".to_string(),
+        };
+        let record = TestRecord::new(mode, &GameStats::new(72.5, 96.0, 21.3));
+        let line = serde_json::to_string(&record).unwrap();
+
+        let records = TestRecord::parse_all(&format!("{}\nnot json\n", line));
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].text.as_deref(), Some("french"));
+        assert_eq!(records[0].mode.name(), "words");
+        assert_eq!(records[0].wpm, 72.5);
+        assert_eq!(records[0].timestamp, record.timestamp);
+    }
+
+    #[test]
+    fn personal_bests_per_options() {
+        let clock = |duration| Mode::Clock {
+            text: "english".to_string(),
+            duration,
+            penalty: 0,
+        };
+
+        assert_eq!(
+            PersonalBests::key(&clock(30)),
+            "clock/duration=30/penalty=0/text=english"
+        );
+
+        let mut bests = PersonalBests::default();
+        assert!(bests.update(&clock(30), 60.0));
+        assert!(!bests.update(&clock(30), 55.0));
+        assert!(bests.update(&clock(60), 50.0));
+        assert!(bests.update(&clock(30), 65.0));
+        assert!(!bests.update(&Mode::Zen, 0.0));
+
+        assert_eq!(bests.get(&clock(30)), Some(65.0));
+        assert_eq!(bests.get(&clock(60)), Some(50.0));
+        assert_eq!(bests.get(&clock(15)), None);
+    }
+
+    #[test]
+    fn personal_bests_leave_out_invalid_tests() {
+        let mut invalid = TestRecord::new(Mode::Zen, &GameStats::new(90.0, 60.0, 30.0));
+        invalid.invalid = true;
+        let valid = TestRecord::new(Mode::Zen, &GameStats::new(70.0, 98.0, 30.0));
+
+        let line = serde_json::to_string(&invalid).unwrap();
+        let records = TestRecord::parse_all(&format!("{}\n", line));
+        assert!(records[0].invalid);
+
+        let bests = PersonalBests::from_records(&[records[0].clone(), valid]);
+        assert_eq!(bests.get(&Mode::Zen), Some(70.0));
+    }
+
+    #[test]
+    fn key_stats_fold_case_and_average_latency() {
+        let results = [
+            KeyResult {
+                key: 'a',
+                correct: true,
+                latency: None,
+            },
+            KeyResult {
+                key: 'A',
+                correct: false,
+                latency: Some(0.3),
+            },
+            KeyResult {
+                key: 'b',
+                correct: true,
+                latency: Some(0.1),
+            },
+        ];
+        let stats = KeyStats::from_results(&results);
+
+        let a = stats.get('a').unwrap();
+        assert_eq!(a.presses(), 2);
+        assert_eq!(a.accuracy(), 50.0);
+        assert_eq!(a.average_latency(), Some(0.3));
+        assert!(stats.get('A').is_none());
+        assert_eq!(stats.slowest(1), vec!['a']);
+
+        let json = serde_json::to_string(&stats).unwrap();
+        let parsed: KeyStats = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.keys, stats.keys);
+    }
+
+    #[test]
+    fn streak_counts_consecutive_days() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let record_on = |d: u32| {
+            let mut record = TestRecord::new(
+                Mode::default_for("clock"),
+                &GameStats::new(60.0, 100.0, 30.0),
+            );
+            record.timestamp = day(d).and_hms_opt(12, 0, 0).unwrap().and_utc();
+            record
+        };
+        let activity = Activity::from_records(&[
+            record_on(1),
+            record_on(3),
+            record_on(4),
+            record_on(5),
+            record_on(5),
+        ]);
+
+        assert_eq!(activity.streak(day(5)), 3);
+        // No test yet today, yesterday's streak is still alive
+        assert_eq!(activity.streak(day(6)), 3);
+        assert_eq!(activity.streak(day(7)), 0);
+
+        let today = activity.day(day(5));
+        assert_eq!(today.tests, 2);
+        assert!((today.words - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ladder_key_joins_thresholds() {
+        assert_eq!(LadderRecords::key(&[30, 45, 60]), "30-45-60");
+    }
+}
@@ -0,0 +1,169 @@
+//! # Import Module
+//!
+//! This module converts results exported from other typing tools into
+//! history records, so stats carry over when switching to TTT.
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::{
+    app::modes::{Mode, default_text},
+    history::TestRecord,
+    resource::quotes::QuoteLength,
+};
+
+/// A test result from a Monkeytype account export.
+///
+/// Only the fields TTT has a counterpart for are read.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MonkeytypeResult {
+    wpm: f64,
+    acc: f64,
+    #[serde(default)]
+    raw_wpm: Option<f64>,
+    mode: String,
+    #[serde(default)]
+    mode2: String,
+    #[serde(default)]
+    quote_length: Option<i64>,
+    test_duration: f64,
+    #[serde(default)]
+    language: Option<String>,
+    /// Milliseconds since the Unix epoch.
+    timestamp: i64,
+}
+
+/// Monkeytype exports are either a plain array of results, or an API
+/// response wrapping it in `data`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MonkeytypeExport {
+    Results(Vec<MonkeytypeResult>),
+    Response { data: Vec<MonkeytypeResult> },
+}
+
+impl MonkeytypeResult {
+    /// The equivalent TTT mode, `None` for modes TTT doesn't have.
+    fn mode(&self) -> Option<Mode> {
+        let text = self.language.clone().unwrap_or_else(default_text);
+
+        match self.mode.as_str() {
+            "time" => Some(Mode::Clock {
+                duration: self.mode2.parse().ok()?,
+                text,
+                penalty: 0,
+            }),
+            "words" => Some(Mode::Words {
+                count: self.mode2.parse().ok()?,
+                text,
+            }),
+            "quote" => Some(Mode::Quote {
+                length: match self.quote_length {
+                    Some(0) => QuoteLength::Short,
+                    Some(1) => QuoteLength::Medium,
+                    Some(2) | Some(3) => QuoteLength::Long,
+                    _ => QuoteLength::All,
+                },
+            }),
+            "zen" => Some(Mode::Zen),
+            _ => None,
+        }
+    }
+
+    fn into_record(self) -> Option<TestRecord> {
+        let mode = self.mode()?;
+
+        Some(TestRecord {
+            timestamp: DateTime::<Utc>::from_timestamp_millis(self.timestamp)?,
+            text: mode.text().map(ToString::to_string),
+            mode,
+            wpm: self.wpm,
+            raw_wpm: self.raw_wpm.unwrap_or(self.wpm),
+            accuracy: self.acc,
+            duration: self.test_duration,
+            replay: None,
+            tag: None,
+            invalid: false,
+        })
+    }
+}
+
+/// Converts a Monkeytype results export (JSON) into history records, oldest
+/// first.
+///
+/// Returns the records and the amount of results that were skipped, either
+/// because their mode has no equivalent or because a record with the same
+/// timestamp is in `existing` (e.g. when importing the same file twice).
+pub fn monkeytype(content: &str, existing: &[TestRecord]) -> Result<(Vec<TestRecord>, usize)> {
+    let export: MonkeytypeExport =
+        serde_json::from_str(content).context("Couldn't parse Monkeytype results")?;
+    let results = match export {
+        MonkeytypeExport::Results(results) | MonkeytypeExport::Response { data: results } => {
+            results
+        }
+    };
+
+    let total = results.len();
+    let mut seen: HashSet<DateTime<Utc>> = existing.iter().map(|r| r.timestamp).collect();
+    let mut records: Vec<TestRecord> = results
+        .into_iter()
+        .filter_map(MonkeytypeResult::into_record)
+        .filter(|record| seen.insert(record.timestamp))
+        .collect();
+    records.sort_by_key(|record| record.timestamp);
+
+    let skipped = total - records.len();
+    Ok((records, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"[
+        {"wpm": 82.4, "acc": 96.5, "rawWpm": 88.1, "mode": "time", "mode2": "30",
+         "testDuration": 30.01, "language": "english", "timestamp": 1700000100000},
+        {"wpm": 75.0, "acc": 98.0, "mode": "words", "mode2": "25",
+         "testDuration": 19.2, "language": "french", "timestamp": 1700000000000},
+        {"wpm": 60.0, "acc": 90.0, "mode": "custom", "mode2": "custom",
+         "testDuration": 40.0, "timestamp": 1700000200000}
+    ]"#;
+
+    #[test]
+    fn converts_supported_modes() {
+        let (records, skipped) = monkeytype(EXPORT, &[]).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(skipped, 1);
+
+        // Sorted oldest first
+        assert!(matches!(records[0].mode, Mode::Words { count: 25, .. }));
+        assert_eq!(records[0].text.as_deref(), Some("french"));
+        assert_eq!(records[0].raw_wpm, 75.0);
+
+        assert!(matches!(records[1].mode, Mode::Clock { duration: 30, .. }));
+        assert_eq!(records[1].raw_wpm, 88.1);
+        assert_eq!(records[1].timestamp.timestamp(), 1_700_000_100);
+    }
+
+    #[test]
+    fn skips_already_imported_results() {
+        let (first, _) = monkeytype(EXPORT, &[]).unwrap();
+        let (second, skipped) = monkeytype(EXPORT, &first).unwrap();
+
+        assert!(second.is_empty());
+        assert_eq!(skipped, 3);
+    }
+
+    #[test]
+    fn accepts_api_responses() {
+        let response = format!(r#"{{"message": "Results retrieved", "data": {}}}"#, EXPORT);
+        let (records, _) = monkeytype(&response, &[]).unwrap();
+
+        assert_eq!(records.len(), 2);
+    }
+}
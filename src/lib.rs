@@ -4,14 +4,65 @@
 //! It manages application state, configuration, command-line parsing, and
 //! embedded resource management.
 
-use std::{fs, io::Error};
+use std::{
+    fs,
+    io::{Error, Read},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
 use directories::ProjectDirs;
+use flate2::read::GzDecoder;
 use rust_embed::Embed;
 
 pub mod app;
+pub mod article;
 pub mod cli;
 pub mod config;
+pub mod export;
+pub mod history;
+pub mod import;
+pub mod markov;
+pub mod plain;
+pub mod profile;
+pub mod replay;
+pub mod resource;
+pub mod stats;
+pub mod texts;
+
+/// Directory replacing the platform-specific directories, see [`set_base_dir`].
+static BASE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Standard input, read once so that it can be used again by the next tests.
+static STDIN: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// Returns the platform-specific project directories (config, data...) for TTT.
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("com", "semanavasco", "ttt")
+}
+
+/// Keeps config files, texts and themes in `dir`, and the history under its
+/// `data/` subdirectory, instead of the platform-specific directories. Only
+/// the first call has an effect.
+pub fn set_base_dir(dir: PathBuf) {
+    let _ = BASE_DIR.set(dir);
+}
+
+/// Returns the directory of the config files, texts and themes.
+pub fn config_dir() -> Option<PathBuf> {
+    match BASE_DIR.get() {
+        Some(dir) => Some(dir.clone()),
+        None => Some(project_dirs()?.config_dir().to_path_buf()),
+    }
+}
+
+/// Returns the directory of the history, replays and ghosts.
+pub fn data_dir() -> Option<PathBuf> {
+    match BASE_DIR.get() {
+        Some(dir) => Some(dir.join("data")),
+        None => Some(project_dirs()?.data_dir().to_path_buf()),
+    }
+}
 
 /// Manager for application resources.
 ///
@@ -21,52 +72,216 @@ pub mod config;
 #[folder = "res/"]
 pub struct Resource;
 
+/// Word lists generated at build time from the lists in `res/frequency/`,
+/// see `build.rs`.
+#[derive(Embed)]
+#[folder = "$OUT_DIR/texts/"]
+struct GeneratedTexts;
+
+/// Texts at the root of `res/` that aren't word lists.
+const NOT_WORD_LISTS: [&str; 3] = ["quotes", "quotes.json", "symbols"];
+
+/// Extension of gzip-compressed texts, left out of their name.
+pub(crate) const GZIP_EXTENSION: &str = ".gz";
+
+/// First bytes of gzip data.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Decompresses gzip data, other data is returned unchanged.
+fn decompress(bytes: Vec<u8>) -> Result<Vec<u8>, Error> {
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(bytes);
+    }
+
+    let mut decompressed = Vec::new();
+    GzDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// The name of a text stored at `path`, without its `.gz` extension.
+fn text_name(path: &str) -> String {
+    path.strip_suffix(GZIP_EXTENSION)
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Whether the text `name` is a word list, rather than quotes, code,
+/// passages or another text read as is.
+pub(crate) fn is_word_list(name: &str) -> bool {
+    !name.contains('/') && !NOT_WORD_LISTS.contains(&name)
+}
+
 impl Resource {
+    /// Lists the names of the word lists that can be passed to `--text`:
+    /// embedded, generated and user texts, sorted.
+    pub fn word_lists() -> Vec<String> {
+        let mut names: Vec<String> = Resource::iter()
+            .chain(GeneratedTexts::iter())
+            .map(|path| text_name(&path))
+            .filter(|name| is_word_list(name))
+            .collect();
+
+        if let Some(config_dir) = config_dir()
+            && let Ok(entries) = fs::read_dir(config_dir.join("texts"))
+        {
+            names.extend(
+                entries
+                    .flatten()
+                    .filter(|entry| entry.path().is_file())
+                    .map(|entry| text_name(&entry.file_name().to_string_lossy())),
+            );
+        }
+
+        // Metadata sidecars aren't texts, see `TextMeta`
+        names.retain(|name| !name.ends_with(texts::META_EXTENSION));
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Lists the names of the texts located under `prefix` (e.g., "code/rust").
+    ///
+    /// Both the user's `texts/` directory and the embedded resources are
+    /// searched. Names are sorted and deduplicated, and can be passed to
+    /// [`Resource::get_text`].
+    pub fn list(prefix: &str) -> Vec<String> {
+        let prefix = format!("{}/", prefix.trim_end_matches('/'));
+
+        let mut names: Vec<String> = Resource::iter()
+            .filter(|path| path.starts_with(&prefix))
+            .map(|path| text_name(&path))
+            .collect();
+
+        if let Some(config_dir) = config_dir()
+            && let Ok(entries) = fs::read_dir(config_dir.join("texts").join(&prefix))
+        {
+            names.extend(
+                entries
+                    .flatten()
+                    .filter(|entry| entry.path().is_file())
+                    .map(|entry| {
+                        text_name(&format!(
+                            "{}{}",
+                            prefix,
+                            entry.file_name().to_string_lossy()
+                        ))
+                    }),
+            );
+        }
+
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Retrieves text data from an arbitrary file path, decompressed if it is
+    /// gzip data.
+    ///
+    /// A path of `-` reads the text from standard input instead, which allows
+    /// piping text into the application. Standard input is only read once,
+    /// later calls get the same text.
+    ///
+    /// # Errors
+    /// Returns an [`Error`] if the file (or standard input) cannot be read.
+    pub fn get_file(path: &Path) -> Result<Vec<u8>, Error> {
+        let bytes = if path == Path::new("-") {
+            match STDIN.get() {
+                Some(bytes) => bytes.clone(),
+                None => {
+                    let mut bytes = Vec::new();
+                    std::io::stdin().read_to_end(&mut bytes)?;
+                    STDIN.get_or_init(|| bytes).clone()
+                }
+            }
+        } else {
+            fs::read(path)?
+        };
+        decompress(bytes)
+    }
+
+    /// Returns the path of the user text `name` in the `texts/` directory,
+    /// compressed or not, if there is one.
+    pub fn user_text_path(name: &str) -> Option<PathBuf> {
+        let texts_path = config_dir()?.join("texts");
+
+        [name.to_string(), format!("{}{}", name, GZIP_EXTENSION)]
+            .iter()
+            .map(|name| texts_path.join(name))
+            .find(|path| path.exists())
+    }
+
     /// Retrieves text data by name.
     ///
     /// It first checks the user's local configuration directory for a matching
     /// file in the `texts/` subdirectory. If not found, it falls back to
-    /// searching the embedded resources.
+    /// searching the embedded resources, generated word lists included.
+    ///
+    /// Texts can be gzip-compressed, a `<name>.gz` file is used when `<name>`
+    /// doesn't exist and decompressed transparently.
     ///
     /// # Arguments
     /// * `name` - The identifier of the text to retrieve (e.g., "english", "lorem").
     ///
     /// # Errors
-    /// Returns an [`Error`] if the config directory cannot be determined or if
-    /// the requested text does not exist in either local storage or embedded resources.
+    /// Returns an [`Error`] if the requested text does not exist in either
+    /// local storage or embedded resources.
     pub fn get_text(name: &str) -> Result<Vec<u8>, Error> {
-        let project_dir = ProjectDirs::from("com", "semanavasco", "ttt").ok_or_else(|| {
-            Error::new(
-                std::io::ErrorKind::NotFound,
-                "Could not determine config dir".to_string(),
-            )
-        })?;
-
-        let texts_path = project_dir
-            .config_dir()
-            .to_path_buf()
-            .join("texts")
-            .join(name);
-
-        if texts_path.exists() {
-            fs::read(&texts_path)
-        } else {
-            Resource::get(name)
-                .map(|f| f.data.into_owned())
-                .ok_or_else(|| {
-                    Error::new(
-                        std::io::ErrorKind::NotFound,
-                        format!("Text '{}' not found", name),
-                    )
-                })
+        match Self::user_text_path(name) {
+            Some(path) => decompress(fs::read(&path)?),
+            None => Self::get_embedded_text(name),
         }
     }
+
+    /// Retrieves an embedded text by name, ignoring the user's `texts/`
+    /// directory, so it is the same for everyone.
+    ///
+    /// # Errors
+    /// Returns an [`Error`] if the requested text isn't embedded.
+    pub fn get_embedded_text(name: &str) -> Result<Vec<u8>, Error> {
+        let bytes = [name.to_string(), format!("{}{}", name, GZIP_EXTENSION)]
+            .iter()
+            .find_map(|name| Resource::get(name).or_else(|| GeneratedTexts::get(name)))
+            .map(|f| f.data.into_owned())
+            .ok_or_else(|| {
+                Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("Text '{}' not found", name),
+                )
+            })?;
+        decompress(bytes)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn frequency_variants_are_generated() {
+        let words = Resource::get_text("english-1k").unwrap();
+        let words = std::str::from_utf8(&words).unwrap();
+
+        assert_eq!(words.lines().count(), 1000);
+        assert!(Resource::word_lists().contains(&"english-200".to_string()));
+        assert!(!Resource::word_lists().contains(&"quotes.json".to_string()));
+    }
+
+    #[test]
+    fn gzip_texts_are_decompressed() {
+        use std::io::Write;
+
+        use flate2::{Compression, write::GzEncoder};
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"the\nbe\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(decompress(compressed).unwrap(), b"the\nbe\n");
+        assert_eq!(decompress(b"plain".to_vec()).unwrap(), b"plain");
+        assert!(decompress(GZIP_MAGIC.to_vec()).is_err());
+        assert_eq!(text_name("english-10k.gz"), "english-10k");
+    }
+
     #[test]
     fn lorem_is_present() {
         let lorem_text = Resource::get("lorem");
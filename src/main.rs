@@ -1,54 +1,389 @@
-use std::io::stdout;
+use std::{
+    env, fs,
+    io::{IsTerminal, Write, stderr, stdin, stdout},
+    path::Path,
+    process,
+};
 
-use anyhow::{Context, Result, anyhow};
-use clap::Parser;
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{Local, Utc};
+use clap::CommandFactory;
+use crossterm::cursor::SetCursorStyle;
 use crossterm::event::{
-    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
 };
 use crossterm::execute;
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::{Terminal, backend::CrosstermBackend};
+use ttt::app::ui::theme::{Theme, ThemeSource};
 use ttt::app::{self, App};
-use ttt::cli::Args;
-use ttt::config::Config;
+use ttt::cli::{
+    Args, Command, ConfigAction, ImportSource, ProfileAction, TextsAction, ThemeAction,
+};
+use ttt::config::{Config, CursorStyle};
+use ttt::export::ExportFormat;
+use ttt::history::{Activity, KeyStats, TestRecord, WordStats};
+use ttt::replay::{Replay, replays_dir};
+use ttt::{export, import, plain, profile, stats, texts};
+
+/// Amount of words listed by `ttt stats --errors`.
+const MISSED_WORDS: usize = 20;
+
+/// Editor of `ttt config edit` when neither `VISUAL` nor `EDITOR` is set.
+const DEFAULT_EDITOR: &str = if cfg!(windows) { "notepad" } else { "vi" };
 
 fn main() -> Result<()> {
-    let args = Args::parse();
-    let config = if args.use_defaults() {
-        Config::default()
+    let args = Args::parse_args();
+    if let Some(dir) = args.base_dir() {
+        ttt::set_base_dir(dir.clone());
+    }
+    profile::activate(args.profile(), args.creates_profile())?;
+
+    // Handled before loading the config, so an invalid file can be fixed
+    if let Some(Command::Config { action }) = args.command() {
+        return run_config_command(action, &args);
+    }
+
+    let mut config = load_config(&args)?;
+
+    let playback = match args.command() {
+        Some(Command::Replay { file }) => Some(Replay::load(file)?),
+        Some(command) => return run_command(command, &config, args.tag()),
+        None => None,
+    };
+
+    // Checked before entering raw mode, the warning is shown on the Home screen
+    let notice = app::fallback_text(&mut config);
+    let mut app = App::from_config(&config)?;
+    app.tag = args.tag().map(ToString::to_string);
+    app.notice = notice;
+
+    // Escape sequences of the UI would end up in a pipe or a file
+    let terminal = if args.json() {
+        stderr().is_terminal()
     } else {
-        args.get_config()
+        stdout().is_terminal()
+    };
+    if args.plain() || !terminal {
+        if playback.is_some() {
+            bail!("Replays are played back in the UI, run ttt in a terminal");
+        }
+        return run_plain(&app, args.json());
+    }
+
+    if let Some(replay) = playback {
+        app.start_playback(replay);
+    }
+    let result = if args.json() {
+        // Draw on stderr, so stdout only carries the JSON output
+        run_ui(stderr, &mut app, &config)
+    } else {
+        run_ui(stdout, &mut app, &config)
     };
 
-    if args.should_save() {
-        let config_str = toml::to_string(&config).context("Couldn't serialize config")?;
+    // Printed once the terminal is restored, so it can be piped
+    if args.json()
+        && let Some(stats) = app.final_stats()
+    {
+        println!(
+            "{}",
+            serde_json::to_string(&stats).context("Couldn't serialize stats")?
+        );
+    }
+
+    result
+}
+
+/// Runs a test typed as a line, see [`plain::run`]. The text goes to stderr,
+/// leaving stdout to the results.
+fn run_plain(app: &App, json: bool) -> Result<()> {
+    let stats = plain::run(app, stdin().lock(), stderr())?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string(&stats).context("Couldn't serialize stats")?
+        );
+    } else {
+        println!("{}", plain::summary(&app.current_config(), &stats));
+    }
+    Ok(())
+}
+
+/// Resolves the settings of this session, see [`Args::get_config`].
+fn load_config(args: &Args) -> Result<Config> {
+    if args.use_defaults() {
+        Ok(Config::default())
+    } else {
+        args.get_config()
+    }
+}
 
-        let config_path = args
-            .config_dir()
-            .ok_or_else(|| anyhow!("Couldn't find config directory"))?;
+/// Runs a `config` command.
+fn run_config_command(action: &ConfigAction, args: &Args) -> Result<()> {
+    let path = args
+        .config_path()
+        .ok_or_else(|| anyhow!("Couldn't find config directory"))?;
 
-        if let Some(parent) = config_path.parent() {
-            std::fs::create_dir_all(parent).context("Couldn't create config directory")?;
+    match action {
+        ConfigAction::Show => {
+            let config = load_config(args)?;
+            print!(
+                "{}",
+                toml::to_string(&config).context("Couldn't serialize config")?
+            );
+        }
+        ConfigAction::Edit => edit_config(&path)?,
+        ConfigAction::Path => println!("{}", path.display()),
+        ConfigAction::Save(_) => {
+            load_config(args)?.save(&path)?;
+            println!("Saved config to {}", path.display());
         }
+        ConfigAction::Schema => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&Config::schema())
+                    .context("Couldn't serialize schema")?
+            );
+        }
+    }
 
-        let config_file_path = config_path.join("config.toml");
-        std::fs::create_dir_all(&config_path).context("Couldn't create config directory")?;
+    Ok(())
+}
 
-        std::fs::write(&config_file_path, config_str).context("Couldn't save config")?;
+/// Opens the config file at `path` in the user's editor, creating it with the
+/// default settings if needed, and checks it once the editor is closed.
+fn edit_config(path: &Path) -> Result<()> {
+    if !path.exists() {
+        Config::default().save(path)?;
+    }
 
-        println!("Saved config to {}", config_file_path.display());
-        std::process::exit(0);
-    };
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| DEFAULT_EDITOR.to_string());
+    // Editors can be given with arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let status = process::Command::new(words.next().unwrap_or(DEFAULT_EDITOR))
+        .args(words)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Couldn't run editor \"{}\"", editor))?;
+    if !status.success() {
+        bail!("Editor \"{}\" exited with {}", editor, status);
+    }
+
+    let content = fs::read_to_string(path).context("Couldn't read config file")?;
+    match Config::load(&content, true) {
+        Ok(_) => println!("Saved config to {}", path.display()),
+        Err(error) => bail!(
+            "Invalid config file {}:\n{}\n\nRun `ttt config edit` again to fix it",
+            path.display(),
+            error.to_string().trim_end()
+        ),
+    }
+    Ok(())
+}
 
-    let mut terminal = ratatui::init();
+/// Runs the UI on the terminal behind `output` until the user quits.
+///
+/// The terminal is restored afterwards, and on panic.
+fn run_ui<W: Write + 'static>(output: fn() -> W, app: &mut App, config: &Config) -> Result<()> {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(output(), DisableMouseCapture, LeaveAlternateScreen);
+        hook(info);
+    }));
 
+    enable_raw_mode()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(output()))?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
     let _ = execute!(
-        stdout(),
+        terminal.backend_mut(),
         PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
     );
+    if let Some(shape) = cursor_shape(config.ui.cursor, config.ui.cursor_blink) {
+        let _ = execute!(terminal.backend_mut(), shape);
+    }
+    if config.ui.mouse {
+        let _ = execute!(terminal.backend_mut(), EnableMouseCapture);
+    }
 
-    let mut app = App::from_config(&config)?;
-    let result = app::run(&mut terminal, &mut app, &config);
+    let result = app::run(&mut terminal, app, config);
 
-    let _ = execute!(stdout(), PopKeyboardEnhancementFlags);
-    ratatui::restore();
+    let _ = execute!(
+        terminal.backend_mut(),
+        PopKeyboardEnhancementFlags,
+        DisableMouseCapture,
+        SetCursorStyle::DefaultUserShape,
+        LeaveAlternateScreen
+    );
+    let _ = disable_raw_mode();
+    let _ = terminal.show_cursor();
     result
 }
+
+/// Shape of the terminal cursor for a [`CursorStyle`], blinking or not,
+/// `None` when the terminal cursor isn't used.
+fn cursor_shape(style: CursorStyle, blink: bool) -> Option<SetCursorStyle> {
+    match (style, blink) {
+        (CursorStyle::StyleOnly, _) => None,
+        (CursorStyle::Block, false) => Some(SetCursorStyle::SteadyBlock),
+        (CursorStyle::Block, true) => Some(SetCursorStyle::BlinkingBlock),
+        (CursorStyle::Underline, false) => Some(SetCursorStyle::SteadyUnderScore),
+        (CursorStyle::Underline, true) => Some(SetCursorStyle::BlinkingUnderScore),
+        (CursorStyle::Bar, false) => Some(SetCursorStyle::SteadyBar),
+        (CursorStyle::Bar, true) => Some(SetCursorStyle::BlinkingBar),
+    }
+}
+
+/// Runs a command that doesn't start the UI.
+///
+/// `tag` narrows the stats down to the tests of a tagged session.
+fn run_command(command: &Command, config: &Config, tag: Option<&str>) -> Result<()> {
+    match command {
+        Command::Stats { keys, errors } => {
+            let report = if *keys {
+                stats::keys(&KeyStats::load()?)
+            } else if *errors {
+                stats::errors(&WordStats::load()?, MISSED_WORDS)
+            } else {
+                let records = stats::filter_tag(TestRecord::load_all()?, tag);
+                let mut report = format!(
+                    "{}\n{}",
+                    stats::summary(&records),
+                    stats::activity(
+                        &Activity::from_records(&records),
+                        &config.goal,
+                        Utc::now().date_naive()
+                    )
+                );
+                for breakdown in [stats::by_text(&records), stats::by_hour(&records, &Local)] {
+                    if !breakdown.is_empty() {
+                        report.push_str("\n\n");
+                        report.push_str(&breakdown);
+                    }
+                }
+                report
+            };
+            println!("{}", report);
+        }
+        Command::Export { format, since } => {
+            let records = export::filter_since(TestRecord::load_all()?, *since);
+            match format {
+                ExportFormat::Csv => print!("{}", export::to_csv(&records)),
+                ExportFormat::Keystrokes | ExportFormat::KeystrokesJson => {
+                    // Tests without a readable replay are left out
+                    let dir = replays_dir()?;
+                    let tests: Vec<(TestRecord, Replay)> = records
+                        .into_iter()
+                        .filter_map(|record| {
+                            let replay = Replay::load(&dir.join(record.replay.as_ref()?)).ok()?;
+                            Some((record, replay))
+                        })
+                        .collect();
+                    if tests.is_empty() {
+                        eprintln!("No replays found, set `record_replays = true` to record them");
+                    }
+
+                    let rows = export::keystroke_rows(&tests);
+                    if *format == ExportFormat::Keystrokes {
+                        print!("{}", export::keystrokes_to_csv(&rows));
+                    } else {
+                        println!(
+                            "{}",
+                            serde_json::to_string(&rows)
+                                .context("Couldn't serialize keystrokes")?
+                        );
+                    }
+                }
+            }
+        }
+        Command::Import {
+            source: ImportSource::Monkeytype { file },
+        } => {
+            let content = std::fs::read_to_string(file)
+                .context(format!("Couldn't read \"{}\"", file.display()))?;
+            let (records, skipped) = import::monkeytype(&content, &TestRecord::load_all()?)?;
+            for record in &records {
+                record.append()?;
+            }
+            println!(
+                "Imported {} tests, skipped {} (unsupported mode or already imported)",
+                records.len(),
+                skipped
+            );
+        }
+        Command::Texts {
+            action: TextsAction::Add { file, name, force },
+        } => {
+            let name = match name {
+                Some(name) => name.clone(),
+                None => file
+                    .file_stem()
+                    .ok_or_else(|| anyhow!("Couldn't name the text, use --name"))?
+                    .to_string_lossy()
+                    .into_owned(),
+            };
+            let path = texts::add(file, &name, *force)?;
+            println!("Added text \"{}\" at {}", name, path.display());
+        }
+        Command::Texts {
+            action: TextsAction::List,
+        } => println!("{}", texts::list()),
+        Command::Texts {
+            action: TextsAction::Preview { name, count },
+        } => println!("{}", texts::preview(name, *count, config)?.join(" ")),
+        Command::Texts {
+            action: TextsAction::Remove { name },
+        } => {
+            let path = texts::remove(name)?;
+            println!("Removed text \"{}\" from {}", name, path.display());
+        }
+        Command::Theme {
+            action: ThemeAction::List,
+        } => {
+            for (name, source) in Theme::list() {
+                match source {
+                    ThemeSource::BuiltIn => println!("{}", name),
+                    ThemeSource::User => println!("{} (user)", name),
+                }
+            }
+        }
+        Command::Profile {
+            action: ProfileAction::List,
+        } => {
+            let active = profile::active().unwrap_or(profile::DEFAULT);
+            for name in profile::list() {
+                let marker = if name == active { "*" } else { " " };
+                println!("{} {}", marker, name);
+            }
+        }
+        Command::Profile {
+            action: ProfileAction::Create { name, force },
+        } => {
+            let path = profile::create(name, config, *force)?;
+            println!("Created profile \"{}\" at {}", name, path.display());
+        }
+        Command::Profile {
+            action: ProfileAction::Switch { name },
+        } => {
+            profile::switch(name)?;
+            println!("Switched to profile \"{}\"", name);
+        }
+        Command::Completions { shell } => {
+            clap_complete::generate(
+                *shell,
+                &mut <Args as CommandFactory>::command(),
+                "ttt",
+                &mut stdout(),
+            );
+        }
+        // Started in the UI, or needing the arguments
+        Command::Run(_) | Command::Legacy(_) | Command::Replay { .. } | Command::Config { .. } => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! # Markov Module
+//!
+//! This module builds pseudo-sentences from a corpus with a word-level Markov
+//! chain, giving more natural transitions than shuffled word lists.
+
+use std::collections::HashMap;
+
+use rand::{Rng, seq::IndexedRandom};
+
+/// A first-order Markov chain over the words of a corpus.
+///
+/// Words keep their punctuation, so generated text has commas and sentences
+/// ending where the corpus had them.
+#[derive(Default, Debug)]
+pub struct MarkovChain {
+    /// Words starting a sentence in the corpus.
+    starts: Vec<String>,
+    /// Words found after each word of the corpus, repeated as often as they
+    /// follow it.
+    transitions: HashMap<String, Vec<String>>,
+}
+
+impl MarkovChain {
+    /// Builds the chain from prose, split on whitespace.
+    pub fn from_corpus(corpus: &str) -> Self {
+        let mut chain = Self::default();
+        let mut previous: Option<&str> = None;
+
+        for word in corpus.split_whitespace() {
+            match previous {
+                Some(previous) if !ends_sentence(previous) => chain
+                    .transitions
+                    .entry(previous.to_string())
+                    .or_default()
+                    .push(word.to_string()),
+                _ => chain.starts.push(word.to_string()),
+            }
+            previous = Some(word);
+        }
+
+        chain
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Generates `count` words, starting a new sentence after each sentence
+    /// end and whenever the chain reaches a word nothing follows.
+    pub fn generate<R: Rng + ?Sized>(&self, count: usize, rng: &mut R) -> Vec<String> {
+        let mut words: Vec<String> = Vec::with_capacity(count);
+
+        while words.len() < count {
+            let next = words
+                .last()
+                .and_then(|last| self.transitions.get(last))
+                .and_then(|next| next.choose(rng))
+                .or_else(|| self.starts.choose(rng));
+
+            match next {
+                Some(word) => words.push(word.clone()),
+                None => break,
+            }
+        }
+
+        words
+    }
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.ends_with(['.', '!', '?'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_words_following_the_corpus() {
+        let chain = MarkovChain::from_corpus("The cat sat. The dog ran, then sat.");
+        let words = chain.generate(50, &mut rand::rng());
+
+        assert_eq!(words.len(), 50);
+        assert_eq!(words[0], "The");
+        for pair in words.windows(2) {
+            let valid = match pair[0].as_str() {
+                "The" => ["cat", "dog"].contains(&pair[1].as_str()),
+                "cat" | "then" => pair[1] == "sat.",
+                "dog" => pair[1] == "ran,",
+                "ran," => pair[1] == "then",
+                _ => pair[1] == "The",
+            };
+            assert!(valid, "{} → {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn empty_corpus_generates_nothing() {
+        let chain = MarkovChain::from_corpus("  ");
+
+        assert!(chain.is_empty());
+        assert!(chain.generate(10, &mut rand::rng()).is_empty());
+    }
+}
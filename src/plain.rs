@@ -0,0 +1,91 @@
+//! # Plain Module
+//!
+//! This module runs a typing test without the UI, one line at a time, for
+//! when the output isn't a terminal or `--plain` is given: the text is
+//! printed, and the line typed back is scored once `ENTER` is pressed.
+
+use std::{
+    io::{BufRead, Write},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::app::{
+    App,
+    modes::{GameStats, Mode, util::normalize},
+};
+
+/// Prints the text of the current mode to `output`, reads the line typed
+/// back from `input` and returns its stats, timed from when the text is shown.
+///
+/// Fails for modes without a text to type, or when there is no input to read.
+pub fn run(app: &App, mut input: impl BufRead, mut output: impl Write) -> Result<GameStats> {
+    let chars = app.mode.get_characters();
+    let text = text(chars.iter().map(|c| c.symbol.as_str()));
+    if text.is_empty() {
+        bail!(
+            "The {} mode needs the interactive UI, run ttt in a terminal",
+            app.current_mode_name()
+        );
+    }
+
+    writeln!(output, "{}\n", text)?;
+    write!(output, "Type the text above, then press ENTER:\n> ")?;
+    output.flush()?;
+
+    let shown_at = Instant::now();
+    let mut line = String::new();
+    let read = input
+        .read_line(&mut line)
+        .context("Couldn't read the typed text")?;
+    if read == 0 {
+        bail!("No input to read, run ttt in a terminal or pipe the typed text to it");
+    }
+
+    Ok(score(&text, &line, shown_at.elapsed()))
+}
+
+/// Text of a test from its characters, on a single line.
+fn text<'a>(symbols: impl Iterator<Item = &'a str>) -> String {
+    let text: String = symbols.collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Stats of `line` typed against `text` in `duration`, word by word.
+fn score(text: &str, line: &str, duration: Duration) -> GameStats {
+    let target: Vec<String> = text.split_whitespace().map(ToString::to_string).collect();
+    let typed: Vec<String> = line.split_whitespace().map(normalize).collect();
+    GameStats::calculate(duration, &typed, &target)
+}
+
+/// Results of a test on a single line, e.g. "clock: 62.4 WPM, 97.1% accuracy
+/// in 31.0s".
+pub fn summary(mode: &Mode, stats: &GameStats) -> String {
+    format!(
+        "{}: {:.1} WPM, {:.1}% accuracy in {:.1}s",
+        mode.name(),
+        stats.wpm(),
+        stats.accuracy(),
+        stats.duration()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::modes::util::graphemes;
+
+    #[test]
+    fn typed_line_is_scored_by_word() {
+        let text = text(graphemes("the quick\nbrown  fox ").into_iter());
+        assert_eq!(text, "the quick brown fox");
+
+        let stats = score(&text, "the quick brown fox\n", Duration::from_secs(12));
+        assert_eq!(stats.accuracy(), 100.0);
+        assert!((stats.wpm() - 19.0).abs() < 1e-9);
+
+        let stats = score(&text, "the quikc\n", Duration::from_secs(12));
+        assert!(stats.accuracy() < 100.0);
+    }
+}
@@ -0,0 +1,191 @@
+//! # Profile Module
+//!
+//! This module manages named configuration profiles. Every profile has its own
+//! `config.<name>.toml` file, and keeps its history apart from the others.
+
+use std::{fs, path::PathBuf, sync::OnceLock};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::config::Config;
+
+/// Name of the profile using `config.toml` and the top-level data directory.
+pub const DEFAULT: &str = "default";
+
+/// File of the config directory naming the profile used without `--profile`.
+const SELECTED_FILE: &str = "profile";
+
+/// Profile of this session, set once at startup.
+static ACTIVE: OnceLock<Option<String>> = OnceLock::new();
+
+/// Sets the profile of this session, `None` (or [`DEFAULT`]) for the default
+/// profile. Only the first call has an effect.
+///
+/// The profile has to exist, unless `create` is set because its settings are
+/// about to be saved.
+pub fn activate(name: Option<String>, create: bool) -> Result<()> {
+    if let Some(name) = &name {
+        validate_name(name)?;
+        if !create && !exists(name) {
+            bail!(
+                "No such profile \"{}\", create it with `ttt profile create {}`",
+                name,
+                name
+            );
+        }
+    }
+    let _ = ACTIVE.set(name.filter(|name| name != DEFAULT));
+    Ok(())
+}
+
+/// Returns the profile of this session, `None` for the default profile.
+pub fn active() -> Option<&'static str> {
+    ACTIVE.get()?.as_deref()
+}
+
+/// Returns the directory config files are stored in.
+pub fn config_dir() -> Result<PathBuf> {
+    crate::config_dir().ok_or_else(|| anyhow!("Couldn't find config directory"))
+}
+
+/// Returns the name of the config file of a profile, `None` for the default
+/// profile.
+pub fn config_file(name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("config.{}.toml", name),
+        None => "config.toml".to_string(),
+    }
+}
+
+/// Returns true if the profile `name` has a config file, the default profile
+/// always exists.
+pub fn exists(name: &str) -> bool {
+    name == DEFAULT || config_dir().is_ok_and(|dir| dir.join(config_file(Some(name))).is_file())
+}
+
+/// Returns the profile of a config file name, if it is a profile's.
+fn profile_of(file_name: &str) -> Option<&str> {
+    file_name
+        .strip_prefix("config.")?
+        .strip_suffix(".toml")
+        .filter(|name| !name.is_empty())
+}
+
+/// Returns the data directory of the active profile, under `data_dir`.
+pub fn data_dir(data_dir: PathBuf) -> PathBuf {
+    match active() {
+        Some(name) => data_dir.join("profiles").join(name),
+        None => data_dir,
+    }
+}
+
+/// Returns the profile selected with `ttt profile switch`, if any.
+pub fn selected() -> Option<String> {
+    let name = fs::read_to_string(config_dir().ok()?.join(SELECTED_FILE)).ok()?;
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+/// Checks that `name` can be used in a file name.
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if !valid {
+        bail!(
+            "Invalid profile name \"{}\", use letters, digits, '-' and '_'",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Names of the existing profiles, the default profile first, followed by the
+/// others in alphabetical order.
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = config_dir()
+        .ok()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            profile_of(file_name.to_str()?).map(ToString::to_string)
+        })
+        .filter(|name| validate_name(name).is_ok())
+        .collect();
+    names.sort();
+
+    names.insert(0, DEFAULT.to_string());
+    names
+}
+
+/// Creates the profile `name` with `config` as its settings. Returns the path
+/// of its config file.
+pub fn create(name: &str, config: &Config, force: bool) -> Result<PathBuf> {
+    validate_name(name)?;
+    if name == DEFAULT {
+        bail!("The default profile already exists");
+    }
+
+    let dir = config_dir()?;
+    let path = dir.join(config_file(Some(name)));
+    if path.exists() && !force {
+        bail!(
+            "Profile \"{}\" already exists, use --force to replace it",
+            name
+        );
+    }
+
+    let content = toml::to_string(config).context("Couldn't serialize config")?;
+    fs::create_dir_all(&dir).context("Couldn't create config directory")?;
+    fs::write(&path, content).context("Couldn't save profile")?;
+    Ok(path)
+}
+
+/// Makes `name` the profile used when `--profile` isn't given.
+pub fn switch(name: &str) -> Result<()> {
+    validate_name(name)?;
+
+    let dir = config_dir()?;
+    let path = dir.join(SELECTED_FILE);
+    if name == DEFAULT {
+        if path.exists() {
+            fs::remove_file(&path).context("Couldn't switch profile")?;
+        }
+        return Ok(());
+    }
+
+    if !exists(name) {
+        bail!(
+            "Couldn't find \"{}\" profile, create it with `ttt profile create {}`",
+            name,
+            name
+        );
+    }
+    fs::write(&path, format!("{}\n", name)).context("Couldn't switch profile")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_files_name_their_profile() {
+        assert_eq!(config_file(None), "config.toml");
+        assert_eq!(profile_of(&config_file(Some("work"))), Some("work"));
+        assert_eq!(profile_of("config.toml"), None);
+        assert_eq!(profile_of("config..toml"), None);
+        assert_eq!(profile_of("theme.toml"), None);
+    }
+
+    #[test]
+    fn profile_names_are_file_safe() {
+        assert!(validate_name("work_2-laptop").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../work").is_err());
+        assert!(validate_name("my work").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! # Replay Module
+//!
+//! This module defines the keystroke recording format used to replay typing
+//! sessions, such as the ghost of a previous run.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{Backspace, StopOnError},
+    history::data_dir,
+};
+
+/// Returns the directory recorded replays are stored in.
+pub fn replays_dir() -> Result<PathBuf> {
+    Ok(data_dir()
+        .ok_or_else(|| anyhow!("Couldn't find data directory"))?
+        .join("replays"))
+}
+
+/// A key as recorded in a replay, independent of the terminal backend.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Key {
+    Char(char),
+    Backspace,
+    /// Clears the current word (Ctrl+H).
+    ClearWord,
+}
+
+/// A single recorded keystroke.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Keystroke {
+    /// Seconds elapsed since the start of the test.
+    pub time: f64,
+    pub key: Key,
+}
+
+/// A recorded typing session: the target text and every keystroke typed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Replay {
+    /// Name of the mode the session was recorded in.
+    pub mode: String,
+    /// Name of the text the target words were generated from.
+    pub text: String,
+    pub target_words: Vec<String>,
+    pub keystrokes: Vec<Keystroke>,
+    /// Total duration of the session in seconds.
+    pub duration: f64,
+    /// Whether mistakes prevented moving on, blocked keystrokes are recorded
+    /// but not applied.
+    #[serde(default)]
+    pub stop_on_error: StopOnError,
+    /// Whether backspace could go back into correct words.
+    #[serde(default)]
+    pub backspace: Backspace,
+}
+
+impl Replay {
+    /// Reads a replay from a JSON file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .context(format!("Couldn't read replay \"{}\"", path.display()))?;
+        serde_json::from_str(&content).context("Couldn't parse replay")
+    }
+
+    /// Writes the replay to a JSON file, creating parent directories as needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Couldn't create replay directory")?;
+        }
+
+        let content = serde_json::to_string(self).context("Couldn't serialize replay")?;
+        fs::write(path, content).context("Couldn't save replay")
+    }
+
+    /// File name of the replay of a test completed at `timestamp`.
+    pub fn file_name(timestamp: &DateTime<Utc>) -> String {
+        format!("{}.json", timestamp.format("%Y%m%d-%H%M%S"))
+    }
+
+    /// Keystrokes typed within the first `seconds` of the session.
+    pub fn keystrokes_until(&self, seconds: f64) -> impl Iterator<Item = &Keystroke> {
+        self.keystrokes
+            .iter()
+            .take_while(move |k| k.time <= seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay_round_trip() {
+        let replay = Replay {
+            mode: "ghost".to_string(),
+            text: "english".to_string(),
+            target_words: vec!["hi".to_string()],
+            keystrokes: vec![
+                Keystroke {
+                    time: 0.0,
+                    key: Key::Char('h'),
+                },
+                Keystroke {
+                    time: 0.5,
+                    key: Key::Backspace,
+                },
+            ],
+            duration: 1.0,
+            stop_on_error: StopOnError::Off,
+            backspace: Backspace::Strict,
+        };
+
+        let path = std::env::temp_dir().join("ttt_replay_round_trip.json");
+        replay.save(&path).unwrap();
+        let loaded = Replay::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, replay);
+        assert_eq!(loaded.keystrokes_until(0.1).count(), 1);
+    }
+
+    #[test]
+    fn file_name_uses_timestamp() {
+        let timestamp = DateTime::parse_from_rfc3339("2025-03-04T05:06:07Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(Replay::file_name(&timestamp), "20250304-050607.json");
+    }
+}
@@ -0,0 +1,7 @@
+//! # Resource Module
+//!
+//! This module caches the texts retrieved through [`Resource`](crate::Resource)
+//! and parses the structured ones (quotes...).
+
+pub mod quotes;
+pub mod store;
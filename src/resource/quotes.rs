@@ -0,0 +1,219 @@
+//! # Quotes Module
+//!
+//! This module loads the quotes database used by the `quote` mode.
+//!
+//! Quotes are stored as a JSON array in the `quotes.json` text:
+//!
+//! ```json
+//! [{"text": "Call me Ishmael.", "author": "Herman Melville", "source": "Moby-Dick", "length": 16}]
+//! ```
+//!
+//! Only `text` is required. A user `quotes` text in the older tab-separated
+//! format (`text<TAB>author[<TAB>source]`, one quote per line) is read too.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+use crate::{config::WordRules, resource::store::TextStore};
+
+/// A range of quote lengths, in characters.
+#[derive(
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    ValueEnum,
+    Display,
+    EnumIter,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Debug,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum QuoteLength {
+    #[default]
+    All,
+    /// Less than 50 characters.
+    Short,
+    /// From 50 to 99 characters.
+    Medium,
+    /// 100 characters or more.
+    Long,
+}
+
+impl QuoteLength {
+    /// Whether a quote of `length` characters falls in the range.
+    pub fn contains(&self, length: usize) -> bool {
+        match self {
+            QuoteLength::All => true,
+            QuoteLength::Short => length < 50,
+            QuoteLength::Medium => (50..100).contains(&length),
+            QuoteLength::Long => length >= 100,
+        }
+    }
+}
+
+/// A quote and where it comes from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct QuoteEntry {
+    pub text: String,
+    #[serde(default = "unknown_author")]
+    pub author: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Length of the text in characters, computed when left out.
+    #[serde(default)]
+    pub length: usize,
+}
+
+fn unknown_author() -> String {
+    "Unknown".to_string()
+}
+
+impl QuoteEntry {
+    pub fn new(text: &str, author: &str, source: Option<&str>) -> Self {
+        Self {
+            text: text.to_string(),
+            author: author.to_string(),
+            source: source.map(ToString::to_string),
+            length: text.chars().count(),
+        }
+    }
+}
+
+/// Parses a quotes database: a JSON array of [`QuoteEntry`], or tab-separated
+/// lines (`text<TAB>author[<TAB>source]`).
+///
+/// Empty quotes are skipped and missing lengths computed. A missing author
+/// defaults to "Unknown".
+pub fn parse(content: &str) -> Result<Vec<QuoteEntry>> {
+    let quotes = if content.trim_start().starts_with('[') {
+        serde_json::from_str(content).context("Couldn't parse quotes")?
+    } else {
+        parse_tsv(content)
+    };
+
+    Ok(quotes
+        .into_iter()
+        .filter(|quote: &QuoteEntry| !quote.text.trim().is_empty())
+        .map(|mut quote| {
+            if quote.length == 0 {
+                quote.length = quote.text.chars().count();
+            }
+            quote
+        })
+        .collect())
+}
+
+fn parse_tsv(content: &str) -> Vec<QuoteEntry> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut fields = line.split('\t').map(str::trim);
+            let text = fields.next().unwrap_or_default();
+            let author = fields.next().filter(|a| !a.is_empty()).unwrap_or("Unknown");
+            let source = fields.next().filter(|s| !s.is_empty());
+
+            QuoteEntry::new(text, author, source)
+        })
+        .collect()
+}
+
+/// Loads the quotes database.
+///
+/// A user `quotes` text (older tab-separated format) takes precedence over
+/// `quotes.json`, which falls back to the embedded set.
+pub fn load() -> Result<Vec<QuoteEntry>> {
+    let content = TextStore::text("quotes").or_else(|_| TextStore::text("quotes.json"))?;
+
+    parse(&content)
+}
+
+/// Keeps the quotes of `length` whose words all pass `rules`.
+pub fn filter(quotes: Vec<QuoteEntry>, length: QuoteLength, rules: &WordRules) -> Vec<QuoteEntry> {
+    quotes
+        .into_iter()
+        .filter(|quote| length.contains(quote.length) && rules.accepts_text(&quote.text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn parse_json_quotes() {
+        let content = r#"[
+            {"text": "Call me Ishmael.", "author": "Herman Melville", "source": "Moby-Dick", "length": 16},
+            {"text": "Hello there."},
+            {"text": "  "}
+        ]"#;
+        let quotes = parse(content).unwrap();
+
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(
+            quotes[0],
+            QuoteEntry::new("Call me Ishmael.", "Herman Melville", Some("Moby-Dick"))
+        );
+        assert_eq!(quotes[1].author, "Unknown");
+        assert_eq!(quotes[1].length, 12);
+        assert!(parse("[{\"author\": \"Nobody\"}]").is_err());
+    }
+
+    #[test]
+    fn parse_tsv_quotes() {
+        let content = "Call me Ishmael.\tHerman Melville\tMoby-Dick\n\nHello there.\t\n";
+        let quotes = parse(content).unwrap();
+
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].source.as_deref(), Some("Moby-Dick"));
+        assert_eq!(quotes[1].author, "Unknown");
+        assert_eq!(quotes[1].source, None);
+    }
+
+    #[test]
+    fn embedded_quotes_are_valid() {
+        use crate::Resource;
+
+        let bytes = Resource::get("quotes.json").unwrap().data;
+        let quotes = parse(std::str::from_utf8(&bytes).unwrap()).unwrap();
+
+        assert!(!quotes.is_empty());
+        assert!(
+            quotes
+                .iter()
+                .all(|q| !q.text.is_empty() && q.length == q.text.chars().count())
+        );
+
+        // Every length filter has quotes to pick from
+        for length in QuoteLength::iter() {
+            assert!(!filter(quotes.clone(), length, &WordRules::default()).is_empty());
+        }
+
+        // Quotes with an excluded word are left out
+        let rules = WordRules {
+            exclude: vec!["the".to_string()],
+            include_only: vec![],
+        };
+        let kept = filter(quotes.clone(), QuoteLength::All, &rules);
+        assert!(kept.len() < quotes.len());
+        assert!(kept.iter().all(|q| rules.accepts_text(&q.text)));
+    }
+
+    #[test]
+    fn length_ranges() {
+        assert!(QuoteLength::Short.contains(49));
+        assert!(!QuoteLength::Medium.contains(49));
+        assert!(QuoteLength::Medium.contains(99));
+        assert!(QuoteLength::Long.contains(100));
+        assert!(QuoteLength::All.contains(0));
+    }
+}
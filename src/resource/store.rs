@@ -0,0 +1,103 @@
+//! # Store Module
+//!
+//! This module caches the texts read through [`Resource::get_text`], so
+//! restarting a test doesn't read and decode its text again.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, LazyLock, Mutex, PoisonError},
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+
+use crate::{Resource, texts};
+
+static STORE: LazyLock<Mutex<TextStore>> = LazyLock::new(Mutex::default);
+
+/// The user file a text was read from and when it was last modified, `None`
+/// for embedded texts.
+type Origin = Option<(PathBuf, Option<SystemTime>)>;
+
+fn origin(name: &str) -> Origin {
+    let path = Resource::user_text_path(name)?;
+    let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+    Some((path, modified))
+}
+
+/// Texts decoded once and shared by every mode.
+///
+/// User texts are read again once their file changes (or is added or
+/// removed), so edits show up on the next test without restarting.
+#[derive(Default)]
+pub struct TextStore {
+    texts: HashMap<String, (Origin, Arc<str>)>,
+    /// Parsed words, with the text they were parsed from.
+    words: HashMap<String, (Arc<str>, Arc<[String]>)>,
+}
+
+impl TextStore {
+    /// Returns the text `name`, only reading it on first use and when its
+    /// user file changed.
+    ///
+    /// # Errors
+    /// Returns an error if the text doesn't exist or isn't valid UTF-8.
+    pub fn text(name: &str) -> Result<Arc<str>> {
+        let origin = origin(name);
+
+        let mut store = STORE.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((cached, text)) = store.texts.get(name)
+            && *cached == origin
+        {
+            return Ok(text.clone());
+        }
+
+        let bytes = Resource::get_text(name).context(format!("Couldn't find \"{}\" text", name))?;
+        let text: Arc<str> = String::from_utf8(bytes)
+            .context("Text contains non-utf8 characters")?
+            .into();
+
+        store.texts.insert(name.to_string(), (origin, text.clone()));
+        Ok(text)
+    }
+
+    /// Returns the words of the text `name`, one per line (or the words of a
+    /// Monkeytype language file), parsed again only when the text changed.
+    pub fn words(name: &str) -> Result<Arc<[String]>> {
+        let text = Self::text(name)?;
+
+        let mut store = STORE.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((source, words)) = store.words.get(name)
+            && Arc::ptr_eq(source, &text)
+        {
+            return Ok(words.clone());
+        }
+
+        let words: Arc<[String]> = texts::monkeytype_words(&text)
+            .unwrap_or_else(|| text.lines().map(ToString::to_string).collect())
+            .into();
+
+        store.words.insert(name.to_string(), (text, words.clone()));
+        Ok(words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn texts_are_cached() {
+        let first = TextStore::words("english").unwrap();
+        let second = TextStore::words("english").unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(Arc::ptr_eq(
+            &TextStore::text("lorem").unwrap(),
+            &TextStore::text("lorem").unwrap()
+        ));
+        assert!(TextStore::text("missing text").is_err());
+    }
+}
@@ -0,0 +1,376 @@
+//! # Stats Module
+//!
+//! This module builds the text reports printed by the `stats` subcommand
+//! from the recorded history.
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use chrono::{NaiveDate, TimeZone, Timelike};
+
+use crate::{
+    config::Goal,
+    history::{Activity, DayActivity, KeyStat, KeyStats, TestRecord, WordStats},
+};
+
+/// Keeps the records tagged with `tag`, if given.
+pub fn filter_tag(records: Vec<TestRecord>, tag: Option<&str>) -> Vec<TestRecord> {
+    match tag {
+        Some(tag) => records
+            .into_iter()
+            .filter(|record| record.tag.as_deref() == Some(tag))
+            .collect(),
+        None => records,
+    }
+}
+
+/// Overview of every recorded test.
+pub fn summary(records: &[TestRecord]) -> String {
+    if records.is_empty() {
+        return "No tests recorded yet.".to_string();
+    }
+
+    let count = records.len() as f64;
+    let average_wpm = records.iter().map(|r| r.wpm).sum::<f64>() / count;
+    let best_wpm = records
+        .iter()
+        .filter(|r| !r.invalid)
+        .map(|r| r.wpm)
+        .fold(0.0, f64::max);
+    let accuracy = records.iter().map(|r| r.accuracy).sum::<f64>() / count;
+    let time = records.iter().map(|r| r.duration).sum::<f64>();
+
+    let mut report = String::new();
+    let _ = writeln!(report, "Tests:        {}", records.len());
+    let _ = writeln!(report, "Average WPM:  {:.1}", average_wpm);
+    let _ = writeln!(report, "Best WPM:     {:.1}", best_wpm);
+    let _ = writeln!(report, "Accuracy:     {:.1}%", accuracy);
+    let _ = write!(report, "Time typing:  {:.0} min", time / 60.0);
+    report
+}
+
+/// Results grouped by the text used, most used first.
+///
+/// Tests of modes without a text (zen, quotes...) are left out.
+pub fn by_text(records: &[TestRecord]) -> String {
+    let mut texts: BTreeMap<&str, Vec<&TestRecord>> = BTreeMap::new();
+    for record in records {
+        if let Some(text) = &record.text {
+            texts.entry(text).or_default().push(record);
+        }
+    }
+    if texts.is_empty() {
+        return String::new();
+    }
+
+    let mut texts: Vec<(&str, Vec<&TestRecord>)> = texts.into_iter().collect();
+    texts.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+
+    let width = texts
+        .iter()
+        .map(|(text, _)| text.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let mut report = String::new();
+    let _ = write!(
+        report,
+        "{:<width$}  Tests  Avg WPM  Best WPM  Accuracy",
+        "Text"
+    );
+    for (text, records) in texts {
+        let count = records.len() as f64;
+        let _ = write!(
+            report,
+            "\n{:<width$}  {:>5}  {:>7.1}  {:>8.1}  {:>7.1}%",
+            text,
+            records.len(),
+            records.iter().map(|r| r.wpm).sum::<f64>() / count,
+            records
+                .iter()
+                .filter(|r| !r.invalid)
+                .map(|r| r.wpm)
+                .fold(0.0, f64::max),
+            records.iter().map(|r| r.accuracy).sum::<f64>() / count,
+        );
+    }
+    report
+}
+
+/// Width of the bars of [`by_hour`], for the best hour.
+const BAR_WIDTH: usize = 30;
+
+/// Average WPM for each hour of the day tests were taken at, in the `tz`
+/// time zone, as a bar chart.
+pub fn by_hour<Tz: TimeZone>(records: &[TestRecord], tz: &Tz) -> String {
+    let mut hours: BTreeMap<u32, Vec<f64>> = BTreeMap::new();
+    for record in records {
+        let hour = record.timestamp.with_timezone(tz).hour();
+        hours.entry(hour).or_default().push(record.wpm);
+    }
+
+    let averages: Vec<(u32, usize, f64)> = hours
+        .into_iter()
+        .map(|(hour, wpm)| (hour, wpm.len(), wpm.iter().sum::<f64>() / wpm.len() as f64))
+        .collect();
+    if averages.is_empty() {
+        return String::new();
+    }
+    let best = averages.iter().map(|(_, _, wpm)| *wpm).fold(0.0, f64::max);
+
+    let mut report = "Hour  Tests  Avg WPM".to_string();
+    for (hour, tests, wpm) in averages {
+        let bar = if best > 0.0 {
+            (wpm / best * BAR_WIDTH as f64).round() as usize
+        } else {
+            0
+        };
+        let _ = write!(
+            report,
+            "\n{:02}h   {:>5}  {:>7.1}  {}",
+            hour,
+            tests,
+            wpm,
+            "█".repeat(bar)
+        );
+    }
+    report
+}
+
+/// Progress towards the daily goal, e.g. `2/5 tests, 120/500 words`.
+///
+/// Returns `None` when no goal is set.
+pub fn goal_progress(goal: &Goal, day: &DayActivity) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(tests) = goal.tests {
+        parts.push(format!("{}/{} tests", day.tests, tests));
+    }
+    if let Some(words) = goal.words {
+        parts.push(format!("{:.0}/{} words", day.words.floor(), words));
+    }
+    if parts.is_empty() {
+        return None;
+    }
+
+    let reached = goal.tests.is_none_or(|tests| day.tests >= tests)
+        && goal.words.is_none_or(|words| day.words >= words as f64);
+    let mut progress = parts.join(", ");
+    if reached {
+        progress.push_str(" ✓");
+    }
+    Some(progress)
+}
+
+/// Streak and daily goal progress as of `today`.
+pub fn activity(activity: &Activity, goal: &Goal, today: NaiveDate) -> String {
+    let streak = activity.streak(today);
+    let mut report = format!(
+        "Streak:       {} day{}",
+        streak,
+        if streak == 1 { "" } else { "s" }
+    );
+    if let Some(progress) = goal_progress(goal, &activity.day(today)) {
+        let _ = write!(report, "\nDaily goal:   {}", progress);
+    }
+    report
+}
+
+/// Table of the words mistyped the most often, up to `count`.
+pub fn errors(stats: &WordStats, count: usize) -> String {
+    let missed = stats.most_missed(count);
+    if missed.is_empty() {
+        return "No mistyped words recorded yet.".to_string();
+    }
+
+    let width = missed
+        .iter()
+        .map(|(word, _)| word.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let mut report = String::new();
+    let _ = writeln!(report, "{:<width$}  Errors  Attempts  Error rate", "Word");
+    for (word, stat) in missed {
+        let _ = writeln!(
+            report,
+            "{:<width$}  {:>6}  {:>8}  {:>9.1}%",
+            word,
+            stat.errors,
+            stat.attempts,
+            stat.errors as f64 / stat.attempts as f64 * 100.0
+        );
+    }
+    let _ = write!(report, "Practice them with `ttt run weak-words`.");
+    report
+}
+
+/// Per-key table, slowest keys first.
+pub fn keys(stats: &KeyStats) -> String {
+    if stats.keys.is_empty() {
+        return "No keys recorded yet.".to_string();
+    }
+
+    let latency = |stat: &KeyStat| stat.average_latency().unwrap_or(0.0);
+    let mut keys: Vec<(&char, &KeyStat)> = stats.keys.iter().collect();
+    keys.sort_by(|a, b| {
+        latency(b.1)
+            .total_cmp(&latency(a.1))
+            .then_with(|| a.0.cmp(b.0))
+    });
+
+    let mut report = String::new();
+    let _ = writeln!(report, "Key  Presses  Accuracy  Latency");
+    for (key, stat) in keys {
+        let latency = match stat.average_latency() {
+            Some(latency) => format!("{:.0} ms", latency * 1000.0),
+            None => "-".to_string(),
+        };
+        let _ = writeln!(
+            report,
+            "{:<3}  {:>7}  {:>7.1}%  {:>7}",
+            key,
+            stat.presses(),
+            stat.accuracy(),
+            latency
+        );
+    }
+    if let Some(average) = stats.average_latency() {
+        let _ = write!(report, "Average latency: {:.0} ms", average * 1000.0);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::modes::{GameStats, KeyResult, Mode, WordResult};
+
+    #[test]
+    fn filter_tag_keeps_tagged_records() {
+        let record = |tag: Option<&str>| {
+            let mut record = TestRecord::new(
+                Mode::default_for("clock"),
+                &GameStats::new(50.0, 95.0, 30.0),
+            );
+            record.tag = tag.map(ToString::to_string);
+            record
+        };
+        let records = vec![record(Some("work")), record(None), record(Some("practice"))];
+
+        assert_eq!(filter_tag(records.clone(), None).len(), 3);
+        let work = filter_tag(records, Some("work"));
+        assert_eq!(work.len(), 1);
+        assert_eq!(work[0].tag.as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn by_text_groups_results() {
+        let record = |mode: &str, wpm| {
+            TestRecord::new(Mode::default_for(mode), &GameStats::new(wpm, 100.0, 30.0))
+        };
+        let mut french = record("words", 40.0);
+        french.text = Some("french".to_string());
+
+        let report = by_text(&[
+            record("clock", 60.0),
+            french,
+            record("words", 80.0),
+            record("zen", 100.0),
+        ]);
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "english      2     70.0      80.0    100.0%");
+        assert!(lines[2].starts_with("french       1     40.0"));
+    }
+
+    #[test]
+    fn by_hour_averages_each_hour() {
+        let record = |hour, wpm| {
+            let mut record =
+                TestRecord::new(Mode::default_for("clock"), &GameStats::new(wpm, 95.0, 30.0));
+            record.timestamp = chrono::Utc
+                .with_ymd_and_hms(2025, 1, 1, hour, 30, 0)
+                .unwrap();
+            record
+        };
+
+        let report = by_hour(
+            &[record(21, 40.0), record(9, 60.0), record(21, 20.0)],
+            &chrono::Utc,
+        );
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            format!("09h       1     60.0  {}", "█".repeat(30))
+        );
+        assert_eq!(
+            lines[2],
+            format!("21h       2     30.0  {}", "█".repeat(15))
+        );
+    }
+
+    #[test]
+    fn goal_progress_marks_reached_goals() {
+        let day = DayActivity {
+            tests: 3,
+            words: 120.6,
+        };
+        let goal = |tests, words| Goal { tests, words };
+
+        assert_eq!(goal_progress(&goal(None, None), &day), None);
+        assert_eq!(
+            goal_progress(&goal(Some(5), Some(100)), &day).unwrap(),
+            "3/5 tests, 120/100 words"
+        );
+        assert_eq!(
+            goal_progress(&goal(Some(3), None), &day).unwrap(),
+            "3/3 tests ✓"
+        );
+    }
+
+    #[test]
+    fn errors_lists_most_missed_words() {
+        let mut stats = WordStats::default();
+        stats.record(&[
+            WordResult {
+                target: "because".to_string(),
+                typed: "becuase".to_string(),
+            },
+            WordResult {
+                target: "because".to_string(),
+                typed: "because".to_string(),
+            },
+        ]);
+
+        let report = errors(&stats, 10);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "Word     Errors  Attempts  Error rate");
+        assert_eq!(lines[1], "because       1         2       50.0%");
+    }
+
+    #[test]
+    fn keys_lists_slowest_first() {
+        let stats = KeyStats::from_results(&[
+            KeyResult {
+                key: 'a',
+                correct: true,
+                latency: Some(0.1),
+            },
+            KeyResult {
+                key: 'q',
+                correct: false,
+                latency: Some(0.4),
+            },
+        ]);
+
+        let report = keys(&stats);
+        let lines: Vec<&str> = report.lines().collect();
+        assert!(lines[1].starts_with("q "));
+        assert!(lines[2].starts_with("a "));
+        assert_eq!(lines.last(), Some(&"Average latency: 250 ms"));
+    }
+}
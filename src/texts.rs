@@ -0,0 +1,340 @@
+//! # Texts Module
+//!
+//! This module manages the word lists in the user's `texts/` directory, used
+//! by the `texts` subcommand.
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::Deserialize;
+use strum::VariantNames;
+
+use crate::{
+    GZIP_EXTENSION, Resource,
+    app::modes::{
+        Mode,
+        util::{Dictionary, word_rng},
+    },
+    config::Config,
+    config_dir, is_word_list,
+};
+
+/// Extension of the metadata sidecar of a text, e.g. `klingon.meta.toml`.
+pub const META_EXTENSION: &str = ".meta.toml";
+
+/// Optional metadata about a text, read from its `<name>.meta.toml` sidecar.
+#[derive(Deserialize, Default, Debug, PartialEq)]
+#[serde(default)]
+pub struct TextMeta {
+    /// Language of the words, e.g. "french".
+    pub language: Option<String>,
+    /// What the text contains.
+    pub description: Option<String>,
+    /// Modes the text works best with, e.g. `["clock", "words"]`.
+    pub modes: Vec<String>,
+    /// Whether modes may add punctuation to the words, e.g. `false` for code
+    /// keywords. Defaults to `true`.
+    pub punctuation: Option<bool>,
+}
+
+impl TextMeta {
+    /// Loads the metadata of the text `name`, empty when it has no sidecar.
+    pub fn load(name: &str) -> Result<Self> {
+        match Resource::get_text(&format!("{}{}", name, META_EXTENSION)) {
+            Ok(bytes) => {
+                let content = std::str::from_utf8(&bytes)
+                    .context("Text metadata contains non-utf8 characters")?;
+                toml::from_str(content)
+                    .context(format!("Couldn't parse the metadata of \"{}\" text", name))
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Whether modes may add punctuation to the words of the text.
+    pub fn allows_punctuation(&self) -> bool {
+        self.punctuation.unwrap_or(true)
+    }
+
+    /// Mode to type the text in when none was chosen: the first recommended
+    /// mode typing a text, unless the text recommends `default`. Returns
+    /// `None` to keep `default`.
+    pub fn preferred_mode(&self, default: &str) -> Option<&str> {
+        if self.modes.iter().any(|mode| mode == default) {
+            return None;
+        }
+        self.modes
+            .iter()
+            .map(String::as_str)
+            .filter(|name| Mode::VARIANTS.contains(name))
+            .find(|&name| Mode::default_for(name).text().is_some())
+    }
+}
+
+/// Returns the directory user texts are stored in.
+pub fn texts_dir() -> Result<PathBuf> {
+    Ok(config_dir()
+        .ok_or_else(|| anyhow!("Couldn't find config directory"))?
+        .join("texts"))
+}
+
+/// Checks that `name` is a relative path without `..`, so texts can't be
+/// written outside of the texts directory.
+fn validate_name(name: &str) -> Result<()> {
+    let path = Path::new(name);
+    let valid = !name.is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+    if !valid {
+        bail!("Invalid text name \"{}\"", name);
+    }
+    Ok(())
+}
+
+/// A language file from Monkeytype, e.g. `english_1k.json`.
+///
+/// Only the words are read, the name and other settings are ignored.
+#[derive(Deserialize)]
+struct MonkeytypeLanguage {
+    words: Vec<String>,
+}
+
+/// The words of `content` if it is a Monkeytype language file
+/// (`{"name": ..., "words": [...]}`), `None` for any other text.
+pub fn monkeytype_words(content: &str) -> Option<Vec<String>> {
+    let content = content.trim_start_matches('\u{feff}').trim_start();
+    if !content.starts_with('{') {
+        return None;
+    }
+
+    let language: MonkeytypeLanguage = serde_json::from_str(content).ok()?;
+    Some(
+        language
+            .words
+            .iter()
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect(),
+    )
+}
+
+/// Turns `content` into a word list: one word per line, without duplicates.
+///
+/// Words are split on any whitespace, so prose works as well as lists.
+/// Monkeytype language files are converted too.
+pub fn normalize(content: &[u8]) -> Result<String> {
+    let content = std::str::from_utf8(content).context("Text contains non-utf8 characters")?;
+    let content = content.trim_start_matches('\u{feff}');
+
+    let words = monkeytype_words(content).unwrap_or_else(|| {
+        content
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect()
+    });
+    let mut seen = HashSet::new();
+    let words: Vec<String> = words
+        .into_iter()
+        .filter(|word| seen.insert(word.clone()))
+        .collect();
+
+    if words.is_empty() {
+        bail!("Text doesn't contain any word");
+    }
+    Ok(words.join("\n") + "\n")
+}
+
+/// Table of the word lists that can be used with `--text`, with their
+/// metadata.
+///
+/// A text with invalid metadata is listed without it.
+pub fn list() -> String {
+    let mut rows = vec![["Text", "Language", "Modes", "Description"].map(String::from)];
+    for name in Resource::word_lists() {
+        let meta = TextMeta::load(&name).unwrap_or_default();
+        rows.push([
+            name,
+            meta.language.unwrap_or_else(|| "-".to_string()),
+            meta.modes.join(", "),
+            meta.description.unwrap_or_default(),
+        ]);
+    }
+
+    let width = |column: usize| {
+        rows.iter()
+            .map(|row| row[column].chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let (name, language, modes) = (width(0), width(1), width(2));
+
+    rows.iter()
+        .map(|row| {
+            format!(
+                "{:<name$}  {:<language$}  {:<modes$}  {}",
+                row[0], row[1], row[2], row[3]
+            )
+            .trim_end()
+            .to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The first `count` words a test on the text `name` would draw with
+/// `config` (generator, word filter and rules, seed).
+pub fn preview(name: &str, count: usize, config: &Config) -> Result<Vec<String>> {
+    let dictionary = Dictionary::load(name, config)?;
+    Ok(dictionary.pick(count, &mut word_rng(config.defaults.seed)))
+}
+
+/// Copies the text at `path` into the texts directory as `name`. Returns the
+/// path written to.
+///
+/// Word lists are normalized with [`normalize`], other texts (quotes, code,
+/// passages...) are stored as they are.
+///
+/// An existing user text is only replaced with `force`.
+pub fn add(path: &Path, name: &str, force: bool) -> Result<PathBuf> {
+    validate_name(name)?;
+
+    let content =
+        Resource::get_file(path).context(format!("Couldn't read \"{}\"", path.display()))?;
+    let content = if is_word_list(name) {
+        normalize(&content)?.into_bytes()
+    } else {
+        content
+    };
+
+    let destination = texts_dir()?.join(name);
+    if destination.exists() && !force {
+        bail!(
+            "Text \"{}\" already exists, use --force to replace it",
+            name
+        );
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).context("Couldn't create texts directory")?;
+    }
+
+    fs::write(&destination, content).context("Couldn't save text")?;
+    Ok(destination)
+}
+
+/// Deletes the user text `name`, compressed or not. Returns the path removed.
+///
+/// Embedded texts can't be removed, a user text of the same name only
+/// replaces them.
+pub fn remove(name: &str) -> Result<PathBuf> {
+    validate_name(name)?;
+
+    let names = [name.to_string(), format!("{}{}", name, GZIP_EXTENSION)];
+    let dir = texts_dir()?;
+    let Some(path) = names
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+    else {
+        if names.iter().any(|name| Resource::get(name).is_some()) {
+            bail!("Text \"{}\" is embedded and can't be removed", name);
+        }
+        bail!("Couldn't find \"{}\" text", name);
+    };
+
+    fs::remove_file(&path).context("Couldn't remove text")?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_makes_word_lists() {
+        let content = "\u{feff}the quick\r\nbrown  the\n\n\tfox\n".as_bytes();
+
+        assert_eq!(normalize(content).unwrap(), "the\nquick\nbrown\nfox\n");
+        assert!(normalize(b" \n\n").is_err());
+        assert!(normalize(&[0xff, 0xfe]).is_err());
+    }
+
+    #[test]
+    fn reads_monkeytype_languages() {
+        let language =
+            r#"{"name": "english", "noLazyMode": true, "words": ["the", " be", "the", ""]}"#;
+
+        assert_eq!(
+            monkeytype_words(language).unwrap(),
+            vec!["the", "be", "the"]
+        );
+        assert_eq!(normalize(language.as_bytes()).unwrap(), "the\nbe\n");
+        assert_eq!(monkeytype_words("the\nbe"), None);
+        assert_eq!(monkeytype_words("{ not json"), None);
+    }
+
+    #[test]
+    fn text_meta_is_read_from_sidecars() {
+        let meta: TextMeta =
+            toml::from_str("language = \"rust\"\nmodes = [\"words\"]\npunctuation = false")
+                .unwrap();
+        assert_eq!(meta.language.as_deref(), Some("rust"));
+        assert!(!meta.allows_punctuation());
+        assert!(TextMeta::default().allows_punctuation());
+
+        let english = TextMeta::load("english").unwrap();
+        assert_eq!(english.language.as_deref(), Some("english"));
+        assert_eq!(TextMeta::load("missing text").unwrap(), TextMeta::default());
+    }
+
+    #[test]
+    fn text_meta_picks_a_mode() {
+        let meta = |modes: &[&str]| TextMeta {
+            modes: modes.iter().map(ToString::to_string).collect(),
+            ..TextMeta::default()
+        };
+
+        assert_eq!(meta(&["words", "clock"]).preferred_mode("clock"), None);
+        assert_eq!(
+            meta(&["zen", "words"]).preferred_mode("clock"),
+            Some("words")
+        );
+        assert_eq!(meta(&["zen", "unknown"]).preferred_mode("clock"), None);
+        assert_eq!(meta(&[]).preferred_mode("clock"), None);
+    }
+
+    #[test]
+    fn preview_applies_the_config() {
+        let mut config = Config::default();
+        config.defaults.seed = Some(7);
+        config.filter.min_word_len = Some(5);
+
+        let words = preview("english", 20, &config).unwrap();
+        assert_eq!(words.len(), 20);
+        assert!(words.iter().all(|word| word.chars().count() >= 5));
+        assert_eq!(words, preview("english", 20, &config).unwrap());
+    }
+
+    #[test]
+    fn names_stay_in_the_texts_directory() {
+        assert!(validate_name("klingon").is_ok());
+        assert!(validate_name("code/klingon").is_ok());
+
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../config.toml").is_err());
+        assert!(validate_name("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn only_word_lists_are_normalized() {
+        assert!(is_word_list("klingon"));
+        assert!(!is_word_list("quotes"));
+        assert!(!is_word_list("code/rust/klingon.rs"));
+        assert!(!is_word_list("passages/klingon"));
+    }
+}